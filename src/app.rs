@@ -1,26 +1,37 @@
-use std::{collections::HashMap, io::Write, path::PathBuf, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    path::PathBuf,
+    time::Instant,
+};
 
-use unicode_segmentation::GraphemeCursor;
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use unicode_width::UnicodeWidthStr;
 
 use typed_builder::*;
 
 use data_farmer::*;
-use data_harvester::{processes, temperature};
+use data_harvester::{processes, temperature, CollectionRates};
+use keybindings::*;
 use layout_manager::*;
 pub use states::*;
 
 use crate::{
     canvas, constants,
+    data_conversion::{convert_affinity_entry, convert_process_details, get_cpu_display_order},
     options::Config,
-    utils::error::{BottomError, Result},
+    utils::{
+        error::{BottomError, Result},
+        exporters::influx::InfluxTarget,
+    },
     Pid,
 };
 
+pub mod data_exporter;
 pub mod data_farmer;
 pub mod data_harvester;
+pub mod keybindings;
 pub mod layout_manager;
-mod process_killer;
+pub mod process_killer;
 pub mod query;
 pub mod states;
 
@@ -28,22 +39,49 @@ const MAX_SEARCH_LENGTH: usize = 200;
 
 /// AppConfigFields is meant to cover basic fields that would normally be set
 /// by config files or launch options.
+#[derive(Clone)]
 pub struct AppConfigFields {
     pub update_rate_in_milliseconds: u64,
     pub temperature_type: temperature::TemperatureType,
     pub use_dot: bool,
     pub left_legend: bool,
     pub show_average_cpu: bool,
+    pub show_cpu_freq: bool,
+    pub show_iowait: bool,
+    pub show_steal: bool,
+    pub show_fans: bool,
+    /// Whether to show the ZFS ARC in the memory widget.
+    pub show_zfs: bool,
+    /// Whether to read zram statistics for the swap label.
+    pub show_zram: bool,
+    pub hide_kernel_threads: bool,
     pub use_current_cpu_total: bool,
     pub use_basic_mode: bool,
     pub default_time_value: u64,
-    pub time_interval: u64,
+    /// How much zooming changes the time window by, or `None` to halve or double it.
+    pub time_interval: Option<u64>,
     pub hide_time: bool,
     pub autohide_time: bool,
     pub use_old_network_legend: bool,
     pub table_gap: u16,
     pub disable_click: bool,
     pub no_write: bool,
+    pub collection_rates: CollectionRates,
+    pub net_filter: Option<globset::GlobSet>,
+    /// The Docker socket to read containers from, if enabled.
+    pub docker_socket: Option<String>,
+    /// Where to write metrics at each update, if anywhere.
+    pub influx_target: Option<InfluxTarget>,
+    /// The combined I/O rate, in bytes per second, above which disks are highlighted.
+    pub disk_io_threshold: Option<u64>,
+    /// The RX and TX rates, in bytes per second, at which lines are drawn on the network graph.
+    pub network_thresholds: (Option<u64>, Option<u64>),
+    /// The high and critical temperatures, in Celsius, at which temperature rows are coloured.
+    pub temperature_thresholds: (f32, f32),
+    /// The high and critical percentages of used space at which the disk usage bar is coloured.
+    pub disk_usage_thresholds: (f64, f64),
+    /// User-defined rules for grouping processes by name.
+    pub process_groups: Vec<ProcessGroupRule>,
 }
 
 /// For filtering out information
@@ -58,6 +96,13 @@ pub struct Filter {
     pub list: Vec<regex::Regex>,
 }
 
+/// Groups all processes whose names match `regex` under `name` when grouping by name.
+#[derive(Clone, Debug)]
+pub struct ProcessGroupRule {
+    pub regex: regex::Regex,
+    pub name: String,
+}
+
 #[derive(TypedBuilder)]
 pub struct App {
     #[builder(default = false, setter(skip))]
@@ -90,6 +135,19 @@ pub struct App {
     #[builder(default, setter(skip))]
     pub help_dialog_state: AppHelpDialogState,
 
+    #[builder(default, setter(skip))]
+    pub process_info_dialog_state: AppProcessInfoDialogState,
+
+    #[builder(default, setter(skip))]
+    pub renice_dialog_state: AppReniceDialogState,
+
+    #[builder(default, setter(skip))]
+    pub user_filter_state: AppUserFilterState,
+
+    /// If set, only processes owned by this user are shown.
+    #[builder(default, setter(skip))]
+    pub username_filter: Option<String>,
+
     #[builder(default = false, setter(skip))]
     pub is_expanded: bool,
 
@@ -105,6 +163,28 @@ pub struct App {
     #[builder(default = false, setter(skip))]
     pub is_config_open: bool,
 
+    #[builder(default, setter(skip))]
+    pub export_receiver: Option<std::sync::mpsc::Receiver<std::result::Result<String, String>>>,
+
+    /// A short message shown in the status area, alongside how many more ticks to show it for.
+    #[builder(default, setter(skip))]
+    pub export_toast: Option<(String, u8)>,
+
+    #[builder(default, setter(skip))]
+    pub search_history: SearchHistory,
+
+    /// Text waiting to be copied to the clipboard, which is done between frames.
+    #[builder(default, setter(skip))]
+    pub clipboard_text: Option<String>,
+
+    /// Processes tagged in the process widget, which are killed together.
+    #[builder(default, setter(skip))]
+    pub tagged_pids: HashSet<Pid>,
+
+    /// Processes pinned to the top of the process widget.
+    #[builder(default, setter(skip))]
+    pub pinned_pids: HashSet<Pid>,
+
     pub cpu_state: CpuState,
     pub mem_state: MemState,
     pub net_state: NetState,
@@ -112,12 +192,14 @@ pub struct App {
     pub temp_state: TempState,
     pub disk_state: DiskState,
     pub battery_state: BatteryState,
+    pub gpu_state: GpuState,
     pub basic_table_widget_state: Option<BasicTableWidgetState>,
     pub app_config_fields: AppConfigFields,
     pub widget_map: HashMap<u64, BottomWidget>,
     pub current_widget: BottomWidget,
     pub used_widgets: UsedWidgets,
     pub filters: DataFilters,
+    pub key_bindings: KeybindingMap,
     pub config: Config,
     pub config_path: Option<PathBuf>,
 }
@@ -129,7 +211,12 @@ impl App {
 
         // Reset dialog state
         self.help_dialog_state.is_showing_help = false;
+        self.process_info_dialog_state.close();
+        self.renice_dialog_state.close();
+        self.user_filter_state.close();
         self.delete_dialog_state.is_showing_dd = false;
+        self.delete_dialog_state.is_choosing_signal = false;
+        self.delete_dialog_state.is_confirming_signal = false;
 
         // Close all searches and reset it
         self.proc_state
@@ -140,8 +227,9 @@ impl App {
             });
         self.proc_state.force_update_all = true;
 
-        // Clear current delete list
+        // Clear current delete list and tags
         self.to_delete_process_list = None;
+        self.tagged_pids.clear();
         self.dd_err = None;
 
         // Unfreeze.
@@ -163,6 +251,11 @@ impl App {
     fn close_dd(&mut self) {
         self.delete_dialog_state.is_showing_dd = false;
         self.delete_dialog_state.is_on_yes = false;
+        self.delete_dialog_state.is_choosing_signal = false;
+        self.delete_dialog_state.is_confirming_signal = false;
+        self.delete_dialog_state.signal_filter.clear();
+        self.delete_dialog_state.selected_signal_index = 0;
+        self.delete_dialog_state.is_killing_tagged = false;
         self.to_delete_process_list = None;
         self.dd_err = None;
     }
@@ -173,6 +266,12 @@ impl App {
             if self.help_dialog_state.is_showing_help {
                 self.help_dialog_state.is_showing_help = false;
                 self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            } else if self.process_info_dialog_state.is_showing_info {
+                self.process_info_dialog_state.close();
+            } else if self.renice_dialog_state.is_showing_renice {
+                self.renice_dialog_state.close();
+            } else if self.user_filter_state.is_showing_input {
+                self.user_filter_state.close();
             } else {
                 self.close_dd();
             }
@@ -204,6 +303,13 @@ impl App {
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id - 1)
                     {
+                        // Leaving the history first restores what was typed before.
+                        if let Some(draft) = self.search_history.stop_browsing() {
+                            current_proc_state.set_search_query(draft);
+                            self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
+                            return;
+                        }
+
                         if current_proc_state.is_search_enabled() {
                             current_proc_state
                                 .process_search_state
@@ -252,14 +358,123 @@ impl App {
         self.second_char = None;
     }
 
+    /// Whether a dialog, or the username filter input that behaves like one, is open.
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.help_dialog_state.is_showing_help
+            || self.delete_dialog_state.is_showing_dd
+            || self.process_info_dialog_state.is_showing_info
+            || self.renice_dialog_state.is_showing_renice
+            || self.user_filter_state.is_showing_input
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
         self.is_config_open || self.is_in_dialog()
     }
 
+    /// Whether keys go to a dialog, the config screen, or the search widget, rather than being
+    /// shortcuts.
+    pub fn is_capturing_keys(&self) -> bool {
+        self.ignore_normal_keybinds() || self.is_in_search_widget()
+    }
+
+    /// Returns the actions for a key event if the config changed what it does.  Bindings are
+    /// ignored in dialogs, and the search widget keeps its typing and editing keys.
+    pub fn get_key_actions(&self, event: &crossterm::event::KeyEvent) -> Option<Vec<KeyAction>> {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        if self.ignore_normal_keybinds() {
+            return None;
+        }
+
+        let is_editing = match event.code {
+            KeyCode::Char(c) => {
+                (event.modifiers - KeyModifiers::SHIFT).is_empty()
+                    || (event.modifiers == KeyModifiers::CONTROL && matches!(c, 'a' | 'e' | 'u'))
+            }
+            KeyCode::F(_) => false,
+            _ => event.modifiers.is_empty(),
+        };
+        if self.is_in_search_widget() && is_editing {
+            return None;
+        }
+
+        self.key_bindings.get(event).map(|actions| actions.to_vec())
+    }
+
+    /// Performs a user-bound action.  Quitting and resetting are handled by the caller, as they
+    /// require access outside of the app state.
+    pub fn on_key_action(&mut self, action: KeyAction) {
+        self.reset_multi_tap_keys();
+
+        match action {
+            KeyAction::Quit | KeyAction::Reset => {}
+            KeyAction::ShowHelp => {
+                self.help_dialog_state.is_showing_help = true;
+                self.is_force_redraw = true;
+            }
+            KeyAction::ToggleFreeze => self.toggle_freeze(),
+            KeyAction::ExpandWidget => self.toggle_expand_widget(),
+            KeyAction::MoveLeft => self.move_widget_selection(&WidgetDirection::Left),
+            KeyAction::MoveRight => self.move_widget_selection(&WidgetDirection::Right),
+            KeyAction::MoveUp => self.move_widget_selection(&WidgetDirection::Up),
+            KeyAction::MoveDown => self.move_widget_selection(&WidgetDirection::Down),
+            KeyAction::ScrollUp => self.on_up_key(),
+            KeyAction::ScrollDown => self.on_down_key(),
+            KeyAction::SkipToFirst => self.skip_to_first(),
+            KeyAction::SkipToLast => self.skip_to_last(),
+            KeyAction::PageUp => self.skip_page_up(),
+            KeyAction::PageDown => self.skip_page_down(),
+            KeyAction::ZoomIn => self.zoom_in(),
+            KeyAction::ZoomOut => self.zoom_out(),
+            KeyAction::ResetZoom => self.reset_zoom(),
+            KeyAction::KillProcess => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    self.start_dd();
+                }
+            }
+            KeyAction::SendSignal => self.start_signal_dialog(),
+            KeyAction::EnableSearching => self.on_slash(),
+            KeyAction::ToggleGrouping => self.on_tab(),
+            KeyAction::ToggleTreeMode => self.toggle_tree_mode(),
+            KeyAction::ToggleCollapse => self.toggle_collapsing_process_branch(),
+            KeyAction::FilterByUser => self.open_user_filter(),
+            KeyAction::FilterByState => self.cycle_state_filter(),
+            KeyAction::ToggleTag => self.toggle_tagging_process(),
+            KeyAction::ClearTags => self.clear_tagged_processes(),
+            KeyAction::TogglePin => self.toggle_pinning_process(),
+            KeyAction::FollowProcess => self.toggle_following_process(),
+            KeyAction::Renice => self.start_renice_dialog(),
+            KeyAction::DecreaseNice => self.renice_selected_process(false),
+            KeyAction::IncreaseNice => self.renice_selected_process(true),
+            KeyAction::ShowDetails => self.show_process_details(),
+            KeyAction::ShowCommandLine => self.show_process_cmdline(),
+            KeyAction::ToggleSort => self.toggle_sort(),
+            KeyAction::InvertSort => self.invert_sort(),
+            KeyAction::SortPreviousColumn => self.cycle_sort_column(false),
+            KeyAction::SortNextColumn => self.cycle_sort_column(true),
+            KeyAction::TogglePercentages => self.toggle_percentages(),
+            KeyAction::ToggleKernelThreads => self.toggle_kernel_threads(),
+            KeyAction::ToggleCurrentUsage => self.toggle_current_usage(),
+            KeyAction::ToggleIdle => self.toggle_idle_processes(),
+            KeyAction::ToggleHugePages => self.toggle_huge_pages(),
+            KeyAction::ToggleCommand => self.toggle_command(),
+            KeyAction::ShowEnvironment => self.show_process_environment(),
+            KeyAction::ShowOpenFiles => self.show_open_files(),
+            KeyAction::ShowConnections => self.show_process_connections(),
+            KeyAction::ToggleNumaGrouping => self.toggle_numa_grouping(),
+            KeyAction::ToggleCore => self.toggle_selected_core(),
+            KeyAction::ShowAllCores => self.show_all_cores(),
+            KeyAction::ToggleAverageCpu => self.toggle_average_cpu(),
+            KeyAction::ToggleCpuBreakdown => self.toggle_cpu_breakdown(),
+            KeyAction::ToggleCpuInterrupts => self.toggle_cpu_interrupts(),
+            KeyAction::ExportData => self.export_data(),
+            KeyAction::ExportProcesses => self.export_process_table(),
+            KeyAction::ToggleIgnoreCase => self.toggle_ignore_case(),
+            KeyAction::ToggleWholeWord => self.toggle_search_whole_word(),
+            KeyAction::ToggleRegex => self.toggle_search_regex(),
+        }
+    }
+
     pub fn on_tab(&mut self) {
         // Allow usage whilst only in processes
 
@@ -274,15 +489,58 @@ impl App {
                             !cpu_widget_state.is_multi_graph_mode;
                     }
                 }
+                BottomWidgetType::ProcSearch => {
+                    // Completes process names instead of changing the grouping.
+                    let is_in_search_widget = self.is_in_search_widget();
+                    if let Some(proc_widget_state) = self
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id - 1)
+                    {
+                        if is_in_search_widget && proc_widget_state.is_search_enabled() {
+                            proc_widget_state.complete_search_token(
+                                self.data_collection
+                                    .process_harvest
+                                    .iter()
+                                    .map(|process| process.name.as_str()),
+                            );
+                            self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
+                        }
+                    }
+                }
                 BottomWidgetType::Proc => {
                     if let Some(proc_widget_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
                         // Do NOT allow when in tree mode!
-                        if !proc_widget_state.is_tree_mode {
+                        let next_grouping_key = match proc_widget_state.grouping_key {
+                            ProcessGroupingKey::Name => Some(ProcessGroupingKey::User),
+                            ProcessGroupingKey::User
+                                if self.app_config_fields.docker_socket.is_some() =>
+                            {
+                                Some(ProcessGroupingKey::Container)
+                            }
+                            _ => None,
+                        };
+
+                        if let (true, Some(next_grouping_key)) =
+                            (proc_widget_state.is_grouped, next_grouping_key)
+                        {
+                            // Cycle from grouping by name, to by user, to by container
+                            proc_widget_state.grouping_key = next_grouping_key;
+                            proc_widget_state.requires_redraw = true;
+                            self.proc_state.force_update = Some(self.current_widget.widget_id);
+                        } else if !proc_widget_state.is_tree_mode {
                             // Toggles process widget grouping state
                             proc_widget_state.is_grouped = !(proc_widget_state.is_grouped);
+                            proc_widget_state.grouping_key = ProcessGroupingKey::Name;
+
+                            proc_widget_state
+                                .columns
+                                .toggle(&processes::ProcessSorting::Count);
+                            proc_widget_state
+                                .columns
+                                .toggle(&processes::ProcessSorting::Pid);
 
                             // Forcefully switch off column if we were on it...
                             if (proc_widget_state.is_grouped
@@ -292,25 +550,12 @@ impl App {
                                     && proc_widget_state.process_sorting_type
                                         == data_harvester::processes::ProcessSorting::Count)
                             {
+                                // Go back to default, negate PID for group
                                 proc_widget_state.process_sorting_type =
-                                    data_harvester::processes::ProcessSorting::CpuPercent; // Go back to default, negate PID for group
+                                    proc_widget_state.columns.get_default_sorting();
                                 proc_widget_state.is_process_sort_descending = true;
                             }
 
-                            proc_widget_state
-                                .columns
-                                .column_mapping
-                                .get_mut(&processes::ProcessSorting::State)
-                                .unwrap()
-                                .enabled = !(proc_widget_state.is_grouped);
-
-                            proc_widget_state
-                                .columns
-                                .toggle(&processes::ProcessSorting::Count);
-                            proc_widget_state
-                                .columns
-                                .toggle(&processes::ProcessSorting::Pid);
-
                             proc_widget_state.requires_redraw = true;
                             self.proc_state.force_update = Some(self.current_widget.widget_id);
                         }
@@ -321,6 +566,167 @@ impl App {
         }
     }
 
+    /// Toggles grouping cores by NUMA node in the focused CPU widget.
+    pub fn toggle_numa_grouping(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let cpu_widget_id = match self.current_widget.widget_type {
+            BottomWidgetType::Cpu => self.current_widget.widget_id,
+            BottomWidgetType::CpuLegend => self.current_widget.widget_id - 1,
+            _ => return,
+        };
+        if let Some(cpu_widget_state) = self.cpu_state.get_mut_widget_state(cpu_widget_id) {
+            cpu_widget_state.is_numa_grouped = !cpu_widget_state.is_numa_grouped;
+            // Positions are by display order, so keep the selection on "All" rather than
+            // jumping to some other core.
+            cpu_widget_state.scroll_state.current_scroll_position = 0;
+            cpu_widget_state.scroll_state.previous_scroll_position = 0;
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Returns the ID of the focused CPU widget, if the CPU widget or its legend is focused.
+    fn get_focused_cpu_widget_id(&self) -> Option<u64> {
+        match self.current_widget.widget_type {
+            BottomWidgetType::Cpu => Some(self.current_widget.widget_id),
+            BottomWidgetType::CpuLegend => Some(self.current_widget.widget_id - 1),
+            _ => None,
+        }
+    }
+
+    /// Hides or shows the core selected in the legend of the focused CPU widget on the chart.
+    /// The "All" entry can't be hidden.
+    pub fn toggle_selected_core(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(cpu_widget_id) = self.get_focused_cpu_widget_id() {
+            let cpu_data = &self.canvas_data.cpu_data;
+            if let Some(cpu_widget_state) = self.cpu_state.widget_states.get_mut(&cpu_widget_id) {
+                let selected_index =
+                    get_cpu_display_order(cpu_data, cpu_widget_state.is_numa_grouped)
+                        .get(cpu_widget_state.scroll_state.current_scroll_position)
+                        .copied();
+                if let Some(selected_index) = selected_index.filter(|index| *index != 0) {
+                    if !cpu_widget_state.hidden_cores.remove(&selected_index) {
+                        cpu_widget_state.hidden_cores.insert(selected_index);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keeps the core selected in the legend of the focused CPU widget emphasized on the chart
+    /// after focus moves elsewhere, or stops doing so if it already is.  Selecting the "All" entry
+    /// unlocks any core.
+    pub fn toggle_locked_core(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(cpu_widget_id) = self.get_focused_cpu_widget_id() {
+            let cpu_data = &self.canvas_data.cpu_data;
+            if let Some(cpu_widget_state) = self.cpu_state.widget_states.get_mut(&cpu_widget_id) {
+                let selected_index =
+                    get_cpu_display_order(cpu_data, cpu_widget_state.is_numa_grouped)
+                        .get(cpu_widget_state.scroll_state.current_scroll_position)
+                        .copied()
+                        .filter(|index| *index != 0);
+                cpu_widget_state.locked_core = if cpu_widget_state.locked_core == selected_index {
+                    None
+                } else {
+                    selected_index
+                };
+            }
+        }
+    }
+
+    /// Shows every core of the focused CPU widget on the chart again.
+    pub fn show_all_cores(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(cpu_widget_id) = self.get_focused_cpu_widget_id() {
+            if let Some(cpu_widget_state) = self.cpu_state.get_mut_widget_state(cpu_widget_id) {
+                cpu_widget_state.hidden_cores.clear();
+            }
+        }
+    }
+
+    /// Shows or hides the average CPU entry in all CPU widgets.
+    pub fn toggle_average_cpu(&mut self) {
+        if self.ignore_normal_keybinds()
+            || !matches!(
+                self.current_widget.widget_type,
+                BottomWidgetType::Cpu | BottomWidgetType::CpuLegend | BottomWidgetType::BasicCpu
+            )
+        {
+            return;
+        }
+
+        let show_average_cpu = !self.app_config_fields.show_average_cpu;
+        self.app_config_fields.show_average_cpu = show_average_cpu;
+        for cpu_widget_state in self.cpu_state.widget_states.values_mut() {
+            // The average comes right after "All", so every core after it moves by one.
+            cpu_widget_state.hidden_cores = cpu_widget_state
+                .hidden_cores
+                .iter()
+                .filter_map(|index| {
+                    if show_average_cpu {
+                        Some(index + 1)
+                    } else if *index > 1 {
+                        Some(index - 1)
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            cpu_widget_state.scroll_state.current_scroll_position = 0;
+            cpu_widget_state.scroll_state.previous_scroll_position = 0;
+        }
+        self.cpu_state.force_update = Some(self.current_widget.widget_id);
+        self.is_force_redraw = true;
+    }
+
+    /// Shows or hides the breakdown of CPU usage into system and I/O wait time in the focused CPU
+    /// widget.
+    pub fn toggle_cpu_breakdown(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(cpu_widget_id) = self.get_focused_cpu_widget_id() {
+            if let Some(cpu_widget_state) = self.cpu_state.get_mut_widget_state(cpu_widget_id) {
+                cpu_widget_state.is_showing_breakdown = !cpu_widget_state.is_showing_breakdown;
+            }
+        }
+    }
+
+    /// Switches the focused CPU widget's chart between the usage of each core and the stacked
+    /// split of CPU time between user, system, IRQ, softIRQ, I/O wait, steal, and idle time.
+    pub fn toggle_cpu_interrupts(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(cpu_widget_id) = self.get_focused_cpu_widget_id() {
+            if let Some(cpu_widget_state) = self.cpu_state.get_mut_widget_state(cpu_widget_id) {
+                cpu_widget_state.is_showing_interrupts = !cpu_widget_state.is_showing_interrupts;
+            }
+        }
+    }
+
+    fn is_in_tree_mode(&self, widget_id: u64) -> bool {
+        matches!(
+            self.proc_state.widget_states.get(&widget_id),
+            Some(proc_widget_state) if proc_widget_state.is_tree_mode
+        )
+    }
+
     /// I don't like this, but removing it causes a bunch of breakage.
     /// Use ``proc_widget_state.is_grouped`` if possible!
     pub fn is_grouped(&self, widget_id: u64) -> bool {
@@ -408,10 +814,447 @@ impl App {
                     self.proc_state.force_update = Some(widget_id);
                 }
             }
+            BottomWidgetType::Disk => {
+                self.disk_state.is_sort_descending = !self.disk_state.is_sort_descending;
+                self.disk_state.force_update = Some(self.current_widget.widget_id);
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves the process or disk sort to the next or previous sortable column.
+    pub fn cycle_sort_column(&mut self, forward: bool) {
+        match &self.current_widget.widget_type {
+            BottomWidgetType::Proc | BottomWidgetType::ProcSort => {
+                let widget_id = self.current_widget.widget_id
+                    - match &self.current_widget.widget_type {
+                        BottomWidgetType::Proc => 0,
+                        BottomWidgetType::ProcSort => 2,
+                        _ => 0,
+                    };
+
+                if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+                    proc_widget_state.cycle_sort_column(forward);
+                    self.proc_state.force_update = Some(widget_id);
+                }
+            }
+            BottomWidgetType::Disk => {
+                self.disk_state.cycle_sort_column(forward);
+                self.disk_state.force_update = Some(self.current_widget.widget_id);
+            }
             _ => {}
         }
     }
 
+    /// Toggles the process widget between showing the process name and its full command.
+    pub fn toggle_command(&mut self) {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                proc_widget_state.is_using_command = !proc_widget_state.is_using_command;
+                proc_widget_state.toggle_command_and_name(proc_widget_state.is_using_command);
+
+                match &proc_widget_state.process_sorting_type {
+                    processes::ProcessSorting::Command | processes::ProcessSorting::ProcessName => {
+                        if proc_widget_state.is_using_command {
+                            proc_widget_state.process_sorting_type =
+                                processes::ProcessSorting::Command;
+                        } else {
+                            proc_widget_state.process_sorting_type =
+                                processes::ProcessSorting::ProcessName;
+                        }
+                    }
+                    _ => {}
+                }
+                proc_widget_state.requires_redraw = true;
+                self.proc_state.force_update = Some(self.current_widget.widget_id);
+            }
+        }
+    }
+
+    /// Returns the currently selected entry in the process widget, if there is one.
+    fn get_selected_process(&self) -> Option<&crate::data_conversion::ConvertedProcessData> {
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            let proc_widget_state = self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id)?;
+
+            self.canvas_data
+                .finalized_process_data_map
+                .get(&self.current_widget.widget_id)?
+                .get(proc_widget_state.scroll_state.current_scroll_position)
+        } else {
+            None
+        }
+    }
+
+    /// Opens a dialog listing the environment variables of the selected process.
+    pub fn show_process_environment(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(process) = self.get_selected_process() {
+            let pid = process.pid;
+            let title = format!("Environment of \"{}\" (PID {})", process.name, pid);
+
+            // Like the command line, the environment is only read once it's asked for.
+            let harvested_process = self
+                .data_collection
+                .process_harvest
+                .iter_mut()
+                .find(|harvested_process| harvested_process.pid == pid);
+            let envs = match harvested_process {
+                Some(harvested_process) => match &harvested_process.envs {
+                    Some(envs) => Ok(envs.clone()),
+                    None => {
+                        let envs = processes::get_process_environment(pid);
+                        if let Ok(envs) = &envs {
+                            harvested_process.envs = Some(envs.clone());
+                        }
+                        envs
+                    }
+                },
+                None => processes::get_process_environment(pid),
+            };
+
+            let entries = match envs {
+                Ok(envs) => envs
+                    .into_iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect(),
+                Err(err) => vec![format!("Unable to read the environment: {}", err)],
+            };
+
+            self.process_info_dialog_state.open(title, entries);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Opens a dialog showing the full command line of the selected process, which can be
+    /// scrolled sideways and copied.
+    pub fn show_process_cmdline(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(process) = self.get_selected_process() {
+            let pid = process.pid;
+            let title = format!("Command line of \"{}\" (PID {})", process.name, pid);
+
+            // The command line is only read once it's asked for, and kept until the next update.
+            let harvested_process = self
+                .data_collection
+                .process_harvest
+                .iter_mut()
+                .find(|harvested_process| harvested_process.pid == pid);
+            let cmdline = match harvested_process {
+                Some(harvested_process) => match &harvested_process.cmdline {
+                    Some(cmdline) => Ok(cmdline.clone()),
+                    None => {
+                        let cmdline = processes::get_process_cmdline(pid);
+                        if let Ok(cmdline) = &cmdline {
+                            harvested_process.cmdline = Some(cmdline.clone());
+                        }
+                        cmdline
+                    }
+                },
+                None => processes::get_process_cmdline(pid),
+            };
+
+            let entries = match cmdline {
+                Ok(cmdline) => vec![cmdline],
+                Err(err) => vec![format!("Unable to read the command line: {}", err)],
+            };
+            self.process_info_dialog_state.open(title, entries);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Copies the shown entries of the process info dialog to the clipboard, once the current
+    /// frame is drawn.
+    pub fn copy_process_info(&mut self) {
+        self.clipboard_text = Some(
+            self.process_info_dialog_state
+                .get_filtered_entries()
+                .join("\n"),
+        );
+    }
+
+    /// Opens a dialog with details about the selected process, which are kept up to date until
+    /// the dialog is closed.
+    pub fn show_process_details(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(process) = self.get_selected_process() {
+            let pid = process.pid;
+            let title = format!("Details of \"{}\" (PID {})", process.name, pid);
+            match processes::get_process_details(pid) {
+                Ok(details) => {
+                    self.process_info_dialog_state
+                        .open(title, convert_process_details(pid, &details));
+                    self.process_info_dialog_state.highlighted_entries =
+                        get_highlighted_process_details(&details);
+                    self.process_info_dialog_state.details_of = Some((pid, details.start_time));
+                }
+                Err(err) => self.process_info_dialog_state.open(
+                    title,
+                    vec![format!("Unable to read the process details: {}", err)],
+                ),
+            }
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Refreshes the process details dialog, if it is open.
+    pub fn update_process_details(&mut self) {
+        if let Some((pid, start_time)) = self.process_info_dialog_state.details_of {
+            match processes::get_process_details(pid) {
+                Ok(details) if details.start_time == start_time => {
+                    self.process_info_dialog_state.entries = convert_process_details(pid, &details);
+                    self.process_info_dialog_state.highlighted_entries =
+                        get_highlighted_process_details(&details);
+                }
+                _ => {
+                    // Don't keep showing stale numbers for a process that's gone.
+                    self.process_info_dialog_state.entries =
+                        vec!["This process has exited.".to_string()];
+                    self.process_info_dialog_state.highlighted_entries.clear();
+                    self.process_info_dialog_state.details_of = None;
+                }
+            }
+        }
+    }
+
+    /// Opens a dialog listing the TCP and UDP connections of the selected process.
+    pub fn show_process_connections(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(process) = self.get_selected_process() {
+            let title = format!("Connections of \"{}\" (PID {})", process.name, process.pid);
+            match processes::get_process_connections(process.pid) {
+                Ok(connections) => {
+                    let connections = connections
+                        .into_iter()
+                        .map(|connection| {
+                            (
+                                connection.proto,
+                                connection.local_addr.to_string(),
+                                connection.remote_addr.to_string(),
+                                connection.state,
+                            )
+                        })
+                        .collect::<Vec<_>>();
+
+                    // Size the address columns to fit, as IPv6 addresses can get quite long.
+                    const LOCAL_HEADER: &str = "Local Address";
+                    const REMOTE_HEADER: &str = "Remote Address";
+                    let local_width = connections
+                        .iter()
+                        .map(|(_, local_addr, _, _)| local_addr.len())
+                        .fold(LOCAL_HEADER.len(), std::cmp::max);
+                    let remote_width = connections
+                        .iter()
+                        .map(|(_, _, remote_addr, _)| remote_addr.len())
+                        .fold(REMOTE_HEADER.len(), std::cmp::max);
+
+                    let header = format!(
+                        "{:<5}  {:<local_width$}  {:<remote_width$}  {}",
+                        "Proto",
+                        LOCAL_HEADER,
+                        REMOTE_HEADER,
+                        "State",
+                        local_width = local_width,
+                        remote_width = remote_width
+                    );
+                    let entries = connections
+                        .into_iter()
+                        .map(|(proto, local_addr, remote_addr, state)| {
+                            format!(
+                                "{:<5}  {:<local_width$}  {:<remote_width$}  {}",
+                                proto,
+                                local_addr,
+                                remote_addr,
+                                state,
+                                local_width = local_width,
+                                remote_width = remote_width
+                            )
+                        })
+                        .collect();
+
+                    self.process_info_dialog_state
+                        .open_with_header(title, Some(header), entries);
+                }
+                Err(err) => self.process_info_dialog_state.open(
+                    title,
+                    vec![format!("Unable to read the connections: {}", err)],
+                ),
+            }
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Writes the current data to a CSV file in the background.
+    pub fn export_data(&mut self) {
+        if self.export_receiver.is_some() {
+            return;
+        }
+
+        self.export_receiver = Some(
+            data_exporter::ExportSnapshot::new(
+                &self.data_collection,
+                &self.app_config_fields.temperature_type,
+            )
+            .export_in_background(),
+        );
+    }
+
+    /// Writes the rows of the selected process widget, as displayed, to a CSV file in the
+    /// background.
+    pub fn export_process_table(&mut self) {
+        if self.export_receiver.is_some() {
+            return;
+        }
+
+        let widget_id = match self.current_widget.widget_type {
+            BottomWidgetType::Proc => self.current_widget.widget_id,
+            BottomWidgetType::ProcSearch => self.current_widget.widget_id - 1,
+            BottomWidgetType::ProcSort => self.current_widget.widget_id - 2,
+            _ => {
+                self.export_toast = Some(("Select a process widget to export".to_string(), 2));
+                return;
+            }
+        };
+
+        if let (Some(proc_widget_state), Some(stringified_process_data)) = (
+            self.proc_state.get_widget_state(widget_id),
+            self.canvas_data
+                .stringified_process_data_map
+                .get(&widget_id),
+        ) {
+            let header = proc_widget_state
+                .columns
+                .get_enabled_columns()
+                .iter()
+                .map(|column| column.to_string())
+                .collect();
+            // Tree mode keeps filtered out processes around as disabled entries.
+            let rows = stringified_process_data
+                .iter()
+                .filter(|(_row, is_disabled)| !is_disabled)
+                .map(|(row, _is_disabled)| row.iter().map(|(text, _)| text.clone()).collect())
+                .collect();
+
+            self.export_receiver =
+                Some(data_exporter::ProcessTableSnapshot::new(header, rows).export_in_background());
+        }
+    }
+
+    /// Checks on any pending export and counts down the toast.  Should be called once per tick.
+    pub fn update_export_status(&mut self) {
+        if let Some((_, ticks_left)) = &mut self.export_toast {
+            if *ticks_left <= 1 {
+                self.export_toast = None;
+            } else {
+                *ticks_left -= 1;
+            }
+        }
+
+        if let Some(receiver) = &self.export_receiver {
+            let message = match receiver.try_recv() {
+                Ok(Ok(file_name)) => format!("Exported to {}", file_name),
+                Ok(Err(err)) => format!("Export failed: {}", err),
+                Err(std::sync::mpsc::TryRecvError::Empty) => return,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => "Export failed".to_string(),
+            };
+            self.export_receiver = None;
+            self.export_toast = Some((message, 2));
+        }
+    }
+
+    /// Opens a dialog listing the open file descriptors of the selected process.
+    pub fn show_open_files(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(process) = self.get_selected_process() {
+            let title = format!("Open files of \"{}\" (PID {})", process.name, process.pid);
+            let entries = match processes::get_open_fds(process.pid) {
+                Ok(fd_entries) => fd_entries
+                    .into_iter()
+                    .map(|fd_entry| {
+                        format!(
+                            "{:>6}  {:<6}  {}",
+                            fd_entry.fd, fd_entry.fd_type, fd_entry.target
+                        )
+                    })
+                    .collect(),
+                Err(err) => vec![format!("Unable to read the open files: {}", err)],
+            };
+
+            self.process_info_dialog_state.open(title, entries);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Toggles whether kernel threads are harvested at all.  Only supported on Linux.
+    pub fn toggle_kernel_threads(&mut self) {
+        if cfg!(target_os = "linux") && !self.ignore_normal_keybinds() {
+            self.app_config_fields.hide_kernel_threads =
+                !self.app_config_fields.hide_kernel_threads;
+        }
+    }
+
+    /// Toggles whether process CPU usage is a percentage of the CPU currently in use, rather than
+    /// of all CPUs.
+    pub fn toggle_current_usage(&mut self) {
+        if !self.ignore_normal_keybinds() {
+            self.app_config_fields.use_current_cpu_total =
+                !self.app_config_fields.use_current_cpu_total;
+        }
+    }
+
+    /// Toggles whether processes using no CPU and almost no memory are hidden.
+    pub fn toggle_idle_processes(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if let Some(proc_widget_state) = self
+                .proc_state
+                .get_mut_widget_state(self.current_widget.widget_id)
+            {
+                proc_widget_state.is_hiding_idle = !proc_widget_state.is_hiding_idle;
+                self.proc_state.force_update = Some(self.current_widget.widget_id);
+            }
+        }
+    }
+
+    /// Toggles whether huge page statistics are shown in the memory widget.
+    pub fn toggle_huge_pages(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let BottomWidgetType::Mem = self.current_widget.widget_type {
+            if let Some(mem_widget_state) = self
+                .mem_state
+                .widget_states
+                .get_mut(&self.current_widget.widget_id)
+            {
+                mem_widget_state.is_showing_huge_pages = !mem_widget_state.is_showing_huge_pages;
+            }
+        }
+    }
+
     pub fn toggle_percentages(&mut self) {
         match &self.current_widget.widget_type {
             BottomWidgetType::BasicMem => {
@@ -453,6 +1296,14 @@ impl App {
     }
 
     pub fn toggle_ignore_case(&mut self) {
+        if self.process_info_dialog_state.is_showing_info {
+            self.process_info_dialog_state
+                .filter
+                .search_toggle_ignore_case();
+            self.process_info_dialog_state.update_filter();
+            return;
+        }
+
         let is_in_search_widget = self.is_in_search_widget();
         if let Some(proc_widget_state) = self
             .proc_state
@@ -465,13 +1316,19 @@ impl App {
                     .search_toggle_ignore_case();
                 proc_widget_state.update_query();
                 self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
-
-                // Also toggle it in the config file.
             }
         }
     }
 
     pub fn toggle_search_whole_word(&mut self) {
+        if self.process_info_dialog_state.is_showing_info {
+            self.process_info_dialog_state
+                .filter
+                .search_toggle_whole_word();
+            self.process_info_dialog_state.update_filter();
+            return;
+        }
+
         let is_in_search_widget = self.is_in_search_widget();
         if let Some(proc_widget_state) = self
             .proc_state
@@ -489,6 +1346,12 @@ impl App {
     }
 
     pub fn toggle_search_regex(&mut self) {
+        if self.process_info_dialog_state.is_showing_info {
+            self.process_info_dialog_state.filter.search_toggle_regex();
+            self.process_info_dialog_state.update_filter();
+            return;
+        }
+
         let is_in_search_widget = self.is_in_search_widget();
         if let Some(proc_widget_state) = self
             .proc_state
@@ -522,11 +1385,316 @@ impl App {
         }
     }
 
+    /// Collapses or expands the branch under the selected process in tree mode.
+    pub fn toggle_collapsing_process_branch(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let selected_pid = match self.get_selected_process() {
+            Some(process) => process.pid,
+            None => return,
+        };
+
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            if proc_widget_state.is_tree_mode {
+                if !proc_widget_state.collapsed_pids.remove(&selected_pid) {
+                    proc_widget_state.collapsed_pids.insert(selected_pid);
+                }
+                self.proc_state.force_update = Some(self.current_widget.widget_id);
+            }
+        }
+    }
+
+    /// Tags the selected process (or all processes in the selected group), or untags it if it is
+    /// already tagged, then moves on to the next process.
+    pub fn toggle_tagging_process(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let selected_pids = match self.get_selected_process() {
+            Some(process) if self.is_grouped(self.current_widget.widget_id) => {
+                process.group_pids.clone()
+            }
+            Some(process) => vec![process.pid],
+            None => return,
+        };
+
+        if selected_pids
+            .iter()
+            .all(|pid| self.tagged_pids.contains(pid))
+        {
+            for pid in &selected_pids {
+                self.tagged_pids.remove(pid);
+            }
+        } else {
+            self.tagged_pids.extend(selected_pids);
+        }
+
+        self.on_down_key();
+    }
+
+    pub fn clear_tagged_processes(&mut self) {
+        if !self.ignore_normal_keybinds() {
+            self.tagged_pids.clear();
+        }
+    }
+
+    /// Pins the selected process (or all processes in the selected group) to the top of the
+    /// process list, or unpins it if it is already pinned.
+    pub fn toggle_pinning_process(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let selected_pids = match self.get_selected_process() {
+            Some(process) if self.is_grouped(self.current_widget.widget_id) => {
+                process.group_pids.clone()
+            }
+            Some(process) => vec![process.pid],
+            None => return,
+        };
+
+        if selected_pids
+            .iter()
+            .any(|pid| self.pinned_pids.contains(pid))
+        {
+            for pid in &selected_pids {
+                self.pinned_pids.remove(pid);
+            }
+        } else {
+            self.pinned_pids.extend(selected_pids);
+        }
+
+        self.proc_state.force_update_all = true;
+    }
+
+    /// The processes to renice: every tagged process if there are any, like when killing, or
+    /// otherwise the selected process (or every process in the selected group).
+    fn get_pids_to_renice(&self) -> Option<Vec<Pid>> {
+        if !self.tagged_pids.is_empty() {
+            let mut tagged_pids = self.tagged_pids.iter().copied().collect::<Vec<_>>();
+            tagged_pids.sort_unstable();
+            Some(tagged_pids)
+        } else {
+            self.get_selected_process()
+                .map(|process| process.group_pids.clone())
+        }
+    }
+
+    /// Raises or lowers the nice value of the processes to renice by one.  The result is shown in
+    /// the widget title for a bit.
+    pub fn renice_selected_process(&mut self, increase: bool) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let pids = match self.get_pids_to_renice() {
+            Some(pids) => pids,
+            None => return,
+        };
+
+        let message = match adjust_nice_of_pids(&pids, if increase { 1 } else { -1 }) {
+            Ok(Some(new_nice)) => format!("nice set to {}", new_nice),
+            Ok(None) => format!(
+                "nice {} for {} processes",
+                if increase { "raised" } else { "lowered" },
+                pids.len()
+            ),
+            Err(err) => err,
+        };
+
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            proc_widget_state.status_message = Some((message, Instant::now()));
+        }
+    }
+
+    /// Opens a dialog to type in the nice value of the processes to renice.
+    pub fn start_renice_dialog(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if !self.tagged_pids.is_empty() {
+            if let Some(pids) = self.get_pids_to_renice() {
+                self.renice_dialog_state
+                    .open(String::default(), pids, None, None);
+                self.renice_dialog_state.is_renicing_tagged = true;
+                self.is_force_redraw = true;
+            }
+        } else if let Some(process) = self.get_selected_process() {
+            let (name, pids, nice) = (
+                process.name.clone(),
+                process.group_pids.clone(),
+                process.nice,
+            );
+            let grouping_key = self
+                .proc_state
+                .get_widget_state(self.current_widget.widget_id)
+                .filter(|proc_widget_state| proc_widget_state.is_grouped)
+                .map(|proc_widget_state| proc_widget_state.grouping_key);
+            self.renice_dialog_state
+                .open(name, pids, nice, grouping_key);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Applies the nice value typed into the renice dialog.  Invalid values are ignored, as the
+    /// dialog already points them out.
+    fn on_renice_confirmed(&mut self) {
+        if let Some(nice) = self.renice_dialog_state.get_nice() {
+            match renice_pids(&self.renice_dialog_state.pids, nice) {
+                Ok(()) => {
+                    self.renice_dialog_state.close();
+                    if let Some(proc_widget_state) = self
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_widget_state.status_message =
+                            Some((format!("nice set to {}", nice), Instant::now()));
+                    }
+                }
+                Err(err) => self.renice_dialog_state.error = Some(err),
+            }
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Keeps the selection on the selected process (or group) across refreshes, or stops doing
+    /// so if it already is.
+    pub fn toggle_following_process(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let is_grouped = self.is_grouped(self.current_widget.widget_id);
+        let selected_process = self.get_selected_process().map(|process| {
+            if is_grouped {
+                FollowedProcess::Group(process.name.clone())
+            } else {
+                FollowedProcess::Pid(process.pid)
+            }
+        });
+
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .get_mut_widget_state(self.current_widget.widget_id)
+        {
+            proc_widget_state.followed_process = if proc_widget_state.followed_process.is_some() {
+                None
+            } else {
+                selected_process
+            };
+            proc_widget_state.follow_lost_instant = None;
+        }
+    }
+
+    /// Drops tags, pins, and collapsed tree branches for processes that no longer exist.
+    pub fn prune_exited_processes(&mut self) {
+        let has_collapsed_pids = self
+            .proc_state
+            .widget_states
+            .values()
+            .any(|proc_widget_state| !proc_widget_state.collapsed_pids.is_empty());
+
+        if !self.tagged_pids.is_empty() || !self.pinned_pids.is_empty() || has_collapsed_pids {
+            let current_pids = self
+                .data_collection
+                .process_harvest
+                .iter()
+                .map(|process| process.pid)
+                .collect::<HashSet<_>>();
+            self.tagged_pids.retain(|pid| current_pids.contains(pid));
+            self.pinned_pids.retain(|pid| current_pids.contains(pid));
+            for proc_widget_state in self.proc_state.widget_states.values_mut() {
+                proc_widget_state
+                    .collapsed_pids
+                    .retain(|pid| current_pids.contains(pid));
+            }
+        }
+    }
+
+    /// Opens the input box for only showing processes owned by a given user.  It starts with the
+    /// current filter, or otherwise the selected process' user.
+    pub fn open_user_filter(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            let input = match &self.username_filter {
+                Some(username) => username.clone(),
+                None => self
+                    .get_selected_process()
+                    .map(|process| process.user.clone())
+                    .unwrap_or_default(),
+            };
+            self.user_filter_state.open(input);
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Applies the typed username filter to every process widget; a blank one shows all users.
+    fn on_user_filter_confirmed(&mut self) {
+        let username = self.user_filter_state.input.trim();
+        self.username_filter = if username.is_empty() {
+            None
+        } else {
+            Some(username.to_string())
+        };
+
+        self.user_filter_state.close();
+        self.proc_state.force_update_all = true;
+        self.is_force_redraw = true;
+    }
+
+    /// Cycles which process states are shown: all, running, sleeping, zombie, then stopped.
+    pub fn cycle_state_filter(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            proc_widget_state.state_filter = proc_widget_state.state_filter.next();
+            self.proc_state.force_update = Some(self.current_widget.widget_id);
+            proc_widget_state.requires_redraw = true;
+        }
+    }
+
     /// One of two functions allowed to run while in a dialog...
     pub fn on_enter(&mut self) {
-        if self.delete_dialog_state.is_showing_dd {
+        if self.process_info_dialog_state.is_showing_info {
+            self.process_info_dialog_state.close();
+            self.is_force_redraw = true;
+        } else if self.renice_dialog_state.is_showing_renice {
+            if self.renice_dialog_state.error.is_some() {
+                self.renice_dialog_state.close();
+                self.is_force_redraw = true;
+            } else {
+                self.on_renice_confirmed();
+            }
+        } else if self.user_filter_state.is_showing_input {
+            self.on_user_filter_confirmed();
+        } else if self.delete_dialog_state.is_showing_dd {
             if self.dd_err.is_some() {
                 self.close_dd();
+            } else if self.delete_dialog_state.is_choosing_signal {
+                // SIGKILL has to be confirmed with 'y', not ENTER.
+                if !self.delete_dialog_state.is_confirming_signal {
+                    self.on_signal_selected();
+                }
             } else if self.delete_dialog_state.is_on_yes {
                 // If within dd...
                 if self.dd_err.is_none() {
@@ -545,6 +1713,8 @@ impl App {
                 self.delete_dialog_state.is_showing_dd = false;
             }
             self.is_force_redraw = true;
+        } else if let BottomWidgetType::CpuLegend = self.current_widget.widget_type {
+            self.toggle_locked_core();
         } else if let BottomWidgetType::ProcSort = self.current_widget.widget_type {
             if let Some(proc_widget_state) = self
                 .proc_state
@@ -555,60 +1725,101 @@ impl App {
                 proc_widget_state.update_sorting_with_columns();
                 self.toggle_sort();
             }
-        }
-    }
-
-    pub fn on_delete(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
-            let is_in_search_widget = self.is_in_search_widget();
+        } else if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            if self.is_in_tree_mode(self.current_widget.widget_id) {
+                self.toggle_collapsing_process_branch();
+            } else {
+                self.show_process_details();
+            }
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             if let Some(proc_widget_state) = self
                 .proc_state
                 .widget_states
-                .get_mut(&(self.current_widget.widget_id - 1))
+                .get(&(self.current_widget.widget_id - 1))
             {
-                if is_in_search_widget {
-                    if proc_widget_state
-                        .process_search_state
-                        .search_state
-                        .is_enabled
-                        && proc_widget_state.get_cursor_position()
-                            < proc_widget_state
-                                .process_search_state
-                                .search_state
-                                .current_search_query
-                                .len()
-                    {
-                        proc_widget_state
-                            .process_search_state
-                            .search_state
-                            .current_search_query
-                            .remove(proc_widget_state.get_cursor_position());
+                self.search_history
+                    .push(proc_widget_state.get_current_search_query());
+            }
+        }
+    }
 
-                        proc_widget_state
+    /// Replaces the search query with an older (or newer) one from the search history.
+    fn browse_search_history(&mut self, is_older: bool) {
+        if let Some(proc_widget_state) = self
+            .proc_state
+            .widget_states
+            .get_mut(&(self.current_widget.widget_id - 1))
+        {
+            if proc_widget_state.is_search_enabled() {
+                let query = if is_older {
+                    self.search_history
+                        .older(proc_widget_state.get_current_search_query())
+                } else {
+                    self.search_history.newer()
+                }
+                .map(str::to_string);
+
+                if let Some(query) = query {
+                    proc_widget_state.set_search_query(query);
+                    self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
+                }
+            }
+        }
+    }
+
+    pub fn on_delete(&mut self) {
+        if self.process_info_dialog_state.is_showing_info {
+            if self.process_info_dialog_state.filter.search_state.delete() {
+                self.process_info_dialog_state.update_filter();
+            }
+            return;
+        }
+
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc if !self.ignore_normal_keybinds() => self.start_dd(),
+            BottomWidgetType::ProcSearch => {
+                let is_in_search_widget = self.is_in_search_widget();
+                if let Some(proc_widget_state) = self
+                    .proc_state
+                    .widget_states
+                    .get_mut(&(self.current_widget.widget_id - 1))
+                {
+                    if is_in_search_widget
+                        && proc_widget_state
                             .process_search_state
                             .search_state
-                            .grapheme_cursor = GraphemeCursor::new(
-                            proc_widget_state.get_cursor_position(),
-                            proc_widget_state
-                                .process_search_state
-                                .search_state
-                                .current_search_query
-                                .len(),
-                            true,
-                        );
-
+                            .is_enabled
+                        && proc_widget_state.process_search_state.search_state.delete()
+                    {
                         proc_widget_state.update_query();
                         self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
                     }
-                } else {
-                    self.start_dd()
                 }
             }
+            _ => {}
         }
     }
 
     pub fn on_backspace(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if self.process_info_dialog_state.is_showing_info {
+            if self
+                .process_info_dialog_state
+                .filter
+                .search_state
+                .backspace()
+            {
+                self.process_info_dialog_state.update_filter();
+            }
+        } else if self.renice_dialog_state.is_showing_renice {
+            self.renice_dialog_state.input.pop();
+        } else if self.user_filter_state.is_showing_input {
+            self.user_filter_state.input.pop();
+        } else if self.delete_dialog_state.is_choosing_signal
+            && !self.delete_dialog_state.is_confirming_signal
+        {
+            self.delete_dialog_state.signal_filter.pop();
+            self.delete_dialog_state.selected_signal_index = 0;
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
                 .proc_state
@@ -619,39 +1830,12 @@ impl App {
                     && proc_widget_state
                         .process_search_state
                         .search_state
-                        .is_enabled
-                    && proc_widget_state.get_cursor_position() > 0
-                {
-                    proc_widget_state.search_walk_back(proc_widget_state.get_cursor_position());
-
-                    let removed_char = proc_widget_state
-                        .process_search_state
-                        .search_state
-                        .current_search_query
-                        .remove(proc_widget_state.get_cursor_position());
-
-                    proc_widget_state
-                        .process_search_state
-                        .search_state
-                        .grapheme_cursor = GraphemeCursor::new(
-                        proc_widget_state.get_cursor_position(),
-                        proc_widget_state
-                            .process_search_state
-                            .search_state
-                            .current_search_query
-                            .len(),
-                        true,
-                    );
-
-                    proc_widget_state
-                        .process_search_state
-                        .search_state
-                        .char_cursor_position -= UnicodeWidthChar::width(removed_char).unwrap_or(0);
-                    proc_widget_state
+                        .is_enabled
+                    && proc_widget_state
                         .process_search_state
                         .search_state
-                        .cursor_direction = CursorDirection::Left;
-
+                        .backspace()
+                {
                     proc_widget_state.update_query();
                     self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
                 }
@@ -670,9 +1854,22 @@ impl App {
     pub fn on_up_key(&mut self) {
         if self.is_config_open {
         } else if !self.is_in_dialog() {
-            self.decrement_position_count();
+            if self.is_in_search_widget() {
+                self.browse_search_history(true);
+            } else {
+                self.decrement_position_count();
+            }
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.process_info_dialog_state.is_showing_info {
+            self.process_info_scroll_up();
+        } else if self.delete_dialog_state.is_choosing_signal
+            && !self.delete_dialog_state.is_confirming_signal
+        {
+            self.delete_dialog_state.selected_signal_index = self
+                .delete_dialog_state
+                .selected_signal_index
+                .saturating_sub(1);
         }
         self.reset_multi_tap_keys();
     }
@@ -680,9 +1877,26 @@ impl App {
     pub fn on_down_key(&mut self) {
         if self.is_config_open {
         } else if !self.is_in_dialog() {
-            self.increment_position_count();
+            if self.is_in_search_widget() {
+                self.browse_search_history(false);
+            } else {
+                self.increment_position_count();
+            }
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.process_info_dialog_state.is_showing_info {
+            self.process_info_scroll_down();
+        } else if self.delete_dialog_state.is_choosing_signal
+            && !self.delete_dialog_state.is_confirming_signal
+        {
+            #[cfg(target_family = "unix")]
+            {
+                if self.delete_dialog_state.selected_signal_index + 1
+                    < self.delete_dialog_state.get_filtered_signals().len()
+                {
+                    self.delete_dialog_state.selected_signal_index += 1;
+                }
+            }
         }
         self.reset_multi_tap_keys();
     }
@@ -712,24 +1926,10 @@ impl App {
                         .get_mut_widget_state(self.current_widget.widget_id - 1)
                     {
                         if is_in_search_widget {
-                            let prev_cursor = proc_widget_state.get_cursor_position();
                             proc_widget_state
-                                .search_walk_back(proc_widget_state.get_cursor_position());
-                            if proc_widget_state.get_cursor_position() < prev_cursor {
-                                let str_slice = &proc_widget_state
-                                    .process_search_state
-                                    .search_state
-                                    .current_search_query
-                                    [proc_widget_state.get_cursor_position()..prev_cursor];
-                                proc_widget_state
-                                    .process_search_state
-                                    .search_state
-                                    .char_cursor_position -= UnicodeWidthStr::width(str_slice);
-                                proc_widget_state
-                                    .process_search_state
-                                    .search_state
-                                    .cursor_direction = CursorDirection::Left;
-                            }
+                                .process_search_state
+                                .search_state
+                                .move_cursor_left();
                         }
                     }
                 }
@@ -747,6 +1947,11 @@ impl App {
                 }
                 _ => {}
             }
+        } else if self.process_info_dialog_state.is_showing_info {
+            let scroll_state = &mut self.process_info_dialog_state.horizontal_scroll_state;
+            scroll_state.current_scroll_index = scroll_state
+                .current_scroll_index
+                .saturating_sub(constants::PROCESS_INFO_HORIZONTAL_SCROLL_STEP);
         } else if self.delete_dialog_state.is_showing_dd && !self.delete_dialog_state.is_on_yes {
             self.delete_dialog_state.is_on_yes = true;
         }
@@ -779,24 +1984,10 @@ impl App {
                         .get_mut_widget_state(self.current_widget.widget_id - 1)
                     {
                         if is_in_search_widget {
-                            let prev_cursor = proc_widget_state.get_cursor_position();
                             proc_widget_state
-                                .search_walk_forward(proc_widget_state.get_cursor_position());
-                            if proc_widget_state.get_cursor_position() > prev_cursor {
-                                let str_slice = &proc_widget_state
-                                    .process_search_state
-                                    .search_state
-                                    .current_search_query
-                                    [prev_cursor..proc_widget_state.get_cursor_position()];
-                                proc_widget_state
-                                    .process_search_state
-                                    .search_state
-                                    .char_cursor_position += UnicodeWidthStr::width(str_slice);
-                                proc_widget_state
-                                    .process_search_state
-                                    .search_state
-                                    .cursor_direction = CursorDirection::Right;
-                            }
+                                .process_search_state
+                                .search_state
+                                .move_cursor_right();
                         }
                     }
                 }
@@ -817,6 +2008,12 @@ impl App {
                 }
                 _ => {}
             }
+        } else if self.process_info_dialog_state.is_showing_info {
+            let scroll_state = &mut self.process_info_dialog_state.horizontal_scroll_state;
+            scroll_state.current_scroll_index = std::cmp::min(
+                scroll_state.current_scroll_index + constants::PROCESS_INFO_HORIZONTAL_SCROLL_STEP,
+                scroll_state.max_scroll_index,
+            );
         } else if self.delete_dialog_state.is_showing_dd && self.delete_dialog_state.is_on_yes {
             self.delete_dialog_state.is_on_yes = false;
         }
@@ -835,26 +2032,15 @@ impl App {
                         proc_widget_state
                             .process_search_state
                             .search_state
-                            .grapheme_cursor = GraphemeCursor::new(
-                            0,
-                            proc_widget_state
-                                .process_search_state
-                                .search_state
-                                .current_search_query
-                                .len(),
-                            true,
-                        );
-                        proc_widget_state
-                            .process_search_state
-                            .search_state
-                            .char_cursor_position = 0;
-                        proc_widget_state
-                            .process_search_state
-                            .search_state
-                            .cursor_direction = CursorDirection::Left;
+                            .skip_cursor_to_start();
                     }
                 }
             }
+        } else if self.process_info_dialog_state.is_showing_info {
+            self.process_info_dialog_state
+                .filter
+                .search_state
+                .skip_cursor_to_start();
         } else if self.is_config_open {
         }
     }
@@ -872,42 +2058,24 @@ impl App {
                         proc_widget_state
                             .process_search_state
                             .search_state
-                            .grapheme_cursor = GraphemeCursor::new(
-                            proc_widget_state
-                                .process_search_state
-                                .search_state
-                                .current_search_query
-                                .len(),
-                            proc_widget_state
-                                .process_search_state
-                                .search_state
-                                .current_search_query
-                                .len(),
-                            true,
-                        );
-                        proc_widget_state
-                            .process_search_state
-                            .search_state
-                            .char_cursor_position = UnicodeWidthStr::width(
-                            proc_widget_state
-                                .process_search_state
-                                .search_state
-                                .current_search_query
-                                .as_str(),
-                        );
-                        proc_widget_state
-                            .process_search_state
-                            .search_state
-                            .cursor_direction = CursorDirection::Right;
+                            .skip_cursor_to_end();
                     }
                 }
             }
+        } else if self.process_info_dialog_state.is_showing_info {
+            self.process_info_dialog_state
+                .filter
+                .search_state
+                .skip_cursor_to_end();
         } else if self.is_config_open {
         }
     }
 
     pub fn clear_search(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if self.process_info_dialog_state.is_showing_info {
+            self.process_info_dialog_state.filter.search_state.reset();
+            self.process_info_dialog_state.update_filter();
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             if let Some(proc_widget_state) = self
                 .proc_state
                 .widget_states
@@ -922,6 +2090,17 @@ impl App {
     pub fn start_dd(&mut self) {
         self.reset_multi_tap_keys();
 
+        if !self.tagged_pids.is_empty() {
+            let mut tagged_pids = self.tagged_pids.iter().copied().collect::<Vec<_>>();
+            tagged_pids.sort_unstable();
+
+            self.to_delete_process_list = Some((String::default(), tagged_pids));
+            self.delete_dialog_state.is_killing_tagged = true;
+            self.delete_dialog_state.is_showing_dd = true;
+            self.is_determining_widget_boundary = true;
+            return;
+        }
+
         if let Some(proc_widget_state) = self
             .proc_state
             .widget_states
@@ -959,6 +2138,51 @@ impl App {
         }
     }
 
+    /// Opens the kill dialog, but lets the user pick which signal to send.  On platforms without
+    /// signals, this is the same as the normal kill dialog.
+    pub fn start_signal_dialog(&mut self) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            self.start_dd();
+        }
+
+        if cfg!(target_family = "unix") && self.delete_dialog_state.is_showing_dd {
+            self.delete_dialog_state.is_choosing_signal = true;
+            self.delete_dialog_state.is_confirming_signal = false;
+            self.delete_dialog_state.signal_filter.clear();
+            self.delete_dialog_state.selected_signal_index = 0;
+        }
+    }
+
+    /// Sends the signal selected in the signal dialog.  SIGKILL will first ask for confirmation.
+    fn on_signal_selected(&mut self) {
+        #[cfg(target_family = "unix")]
+        {
+            if let Some((_name, signal)) = self
+                .delete_dialog_state
+                .get_filtered_signals()
+                .get(self.delete_dialog_state.selected_signal_index)
+                .copied()
+            {
+                if signal == libc::SIGKILL && !self.delete_dialog_state.is_confirming_signal {
+                    self.delete_dialog_state.is_confirming_signal = true;
+                } else {
+                    self.delete_dialog_state.is_confirming_signal = false;
+                    if let Err(dd_err) = self.signal_highlighted_process(signal) {
+                        self.dd_err = Some(dd_err.to_string());
+                    } else {
+                        self.close_dd();
+                    }
+                }
+            }
+        }
+
+        self.is_force_redraw = true;
+    }
+
     pub fn on_char_key(&mut self, caught_char: char) {
         // Skip control code chars
         if caught_char.is_control() {
@@ -997,36 +2221,10 @@ impl App {
                         proc_widget_state
                             .process_search_state
                             .search_state
-                            .current_search_query
-                            .insert(proc_widget_state.get_cursor_position(), caught_char);
-
-                        proc_widget_state
-                            .process_search_state
-                            .search_state
-                            .grapheme_cursor = GraphemeCursor::new(
-                            proc_widget_state.get_cursor_position(),
-                            proc_widget_state
-                                .process_search_state
-                                .search_state
-                                .current_search_query
-                                .len(),
-                            true,
-                        );
-                        proc_widget_state
-                            .search_walk_forward(proc_widget_state.get_cursor_position());
-
-                        proc_widget_state
-                            .process_search_state
-                            .search_state
-                            .char_cursor_position +=
-                            UnicodeWidthChar::width(caught_char).unwrap_or(0);
+                            .insert_char(caught_char);
 
                         proc_widget_state.update_query();
                         self.proc_state.force_update = Some(self.current_widget.widget_id - 1);
-                        proc_widget_state
-                            .process_search_state
-                            .search_state
-                            .cursor_direction = CursorDirection::Right;
 
                         return;
                     }
@@ -1049,6 +2247,41 @@ impl App {
                 'j' | 'k' | 'g' | 'G' => self.handle_char(caught_char),
                 _ => {}
             }
+        } else if self.process_info_dialog_state.is_showing_info {
+            self.process_info_dialog_state
+                .filter
+                .search_state
+                .insert_char(caught_char);
+            self.process_info_dialog_state.update_filter();
+        } else if self.renice_dialog_state.is_showing_renice {
+            // Nice values are at most three characters long, like "-20".
+            if self.renice_dialog_state.error.is_none()
+                && (caught_char.is_ascii_digit() || caught_char == '-')
+                && self.renice_dialog_state.input.len() < 3
+            {
+                self.renice_dialog_state.input.push(caught_char);
+            }
+        } else if self.user_filter_state.is_showing_input {
+            self.user_filter_state.input.push(caught_char);
+        } else if self.delete_dialog_state.is_confirming_signal {
+            match caught_char {
+                'y' | 'Y' => self.on_signal_selected(),
+                'n' | 'N' => {
+                    self.delete_dialog_state.is_confirming_signal = false;
+                    self.is_force_redraw = true;
+                }
+                _ => {}
+            }
+        } else if self.delete_dialog_state.is_choosing_signal {
+            match caught_char {
+                'j' => self.on_down_key(),
+                'k' => self.on_up_key(),
+                '0'..='9' => {
+                    self.delete_dialog_state.signal_filter.push(caught_char);
+                    self.delete_dialog_state.selected_signal_index = 0;
+                }
+                _ => {}
+            }
         } else if self.delete_dialog_state.is_showing_dd {
             match caught_char {
                 'h' | 'j' => self.on_left_key(),
@@ -1102,20 +2335,18 @@ impl App {
             'G' => self.skip_to_last(),
             'k' => self.on_up_key(),
             'j' => self.on_down_key(),
-            'f' => {
-                self.is_frozen = !self.is_frozen;
-                if self.is_frozen {
-                    self.data_collection.set_frozen_time();
-                }
-            }
-            'C' => {
-                // self.open_config(),
-            }
+            'f' => self.toggle_freeze(),
+            'C' => self.show_process_cmdline(),
             'c' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
+                        .filter(|proc_widget_state| {
+                            proc_widget_state
+                                .columns
+                                .is_shown(&processes::ProcessSorting::CpuPercent)
+                        })
                     {
                         match proc_widget_state.process_sorting_type {
                             processes::ProcessSorting::CpuPercent => {
@@ -1129,8 +2360,6 @@ impl App {
                             }
                         }
                         self.proc_state.force_update = Some(self.current_widget.widget_id);
-
-                        self.skip_to_first();
                     }
                 }
             }
@@ -1139,6 +2368,11 @@ impl App {
                     if let Some(proc_widget_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
+                        .filter(|proc_widget_state| {
+                            proc_widget_state
+                                .columns
+                                .is_shown(&processes::ProcessSorting::MemPercent)
+                        })
                     {
                         match proc_widget_state.process_sorting_type {
                             processes::ProcessSorting::MemPercent => {
@@ -1152,7 +2386,6 @@ impl App {
                             }
                         }
                         self.proc_state.force_update = Some(self.current_widget.widget_id);
-                        self.skip_to_first();
                     }
                 }
             }
@@ -1162,58 +2395,46 @@ impl App {
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
                     {
-                        // Skip if grouped
-                        if !proc_widget_state.is_grouped {
-                            match proc_widget_state.process_sorting_type {
-                                processes::ProcessSorting::Pid => {
-                                    proc_widget_state.is_process_sort_descending =
-                                        !proc_widget_state.is_process_sort_descending
-                                }
-                                _ => {
-                                    proc_widget_state.process_sorting_type =
-                                        processes::ProcessSorting::Pid;
-                                    proc_widget_state.is_process_sort_descending = false;
-                                }
+                        // Grouped processes show how many PIDs are in each group instead.
+                        let (sorting_type, is_initially_descending) =
+                            if proc_widget_state.is_grouped {
+                                (processes::ProcessSorting::Count, true)
+                            } else {
+                                (processes::ProcessSorting::Pid, false)
+                            };
+
+                        // Skip if the column isn't shown
+                        if proc_widget_state.columns.is_shown(&sorting_type) {
+                            if proc_widget_state.process_sorting_type == sorting_type {
+                                proc_widget_state.is_process_sort_descending =
+                                    !proc_widget_state.is_process_sort_descending
+                            } else {
+                                proc_widget_state.process_sorting_type = sorting_type;
+                                proc_widget_state.is_process_sort_descending =
+                                    is_initially_descending;
                             }
                             self.proc_state.force_update = Some(self.current_widget.widget_id);
-                            self.skip_to_first();
-                        }
-                    }
-                }
-            }
-            'P' => {
-                if let BottomWidgetType::Proc = self.current_widget.widget_type {
-                    if let Some(proc_widget_state) = self
-                        .proc_state
-                        .get_mut_widget_state(self.current_widget.widget_id)
-                    {
-                        proc_widget_state.is_using_command = !proc_widget_state.is_using_command;
-                        proc_widget_state
-                            .toggle_command_and_name(proc_widget_state.is_using_command);
-
-                        match &proc_widget_state.process_sorting_type {
-                            processes::ProcessSorting::Command
-                            | processes::ProcessSorting::ProcessName => {
-                                if proc_widget_state.is_using_command {
-                                    proc_widget_state.process_sorting_type =
-                                        processes::ProcessSorting::Command;
-                                } else {
-                                    proc_widget_state.process_sorting_type =
-                                        processes::ProcessSorting::ProcessName;
-                                }
-                            }
-                            _ => {}
                         }
-                        proc_widget_state.requires_redraw = true;
-                        self.proc_state.force_update = Some(self.current_widget.widget_id);
                     }
                 }
             }
+            'P' => self.toggle_command(),
+            'E' => self.show_process_environment(),
+            'o' => self.show_open_files(),
+            'N' => match self.current_widget.widget_type {
+                BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => self.toggle_numa_grouping(),
+                _ => self.show_process_connections(),
+            },
             'n' => {
                 if let BottomWidgetType::Proc = self.current_widget.widget_type {
                     if let Some(proc_widget_state) = self
                         .proc_state
                         .get_mut_widget_state(self.current_widget.widget_id)
+                        .filter(|proc_widget_state| {
+                            proc_widget_state
+                                .columns
+                                .is_shown(&processes::ProcessSorting::ProcessName)
+                        })
                     {
                         match proc_widget_state.process_sorting_type {
                             processes::ProcessSorting::ProcessName
@@ -1232,7 +2453,6 @@ impl App {
                             }
                         }
                         self.proc_state.force_update = Some(self.current_widget.widget_id);
-                        self.skip_to_first();
                     }
                 }
             }
@@ -1247,11 +2467,32 @@ impl App {
             't' => self.toggle_tree_mode(),
             '+' => self.zoom_in(),
             '-' => self.zoom_out(),
-            '=' => self.reset_zoom(),
+            '=' | 'r' => self.reset_zoom(),
             'e' => self.toggle_expand_widget(),
             's' => self.toggle_sort(),
             'I' => self.invert_sort(),
+            '<' => self.cycle_sort_column(false),
+            '>' => self.cycle_sort_column(true),
             '%' => self.toggle_percentages(),
+            'T' => self.toggle_kernel_threads(),
+            'u' => self.toggle_current_usage(),
+            'i' => {
+                if self.get_focused_cpu_widget_id().is_some() {
+                    self.toggle_cpu_interrupts()
+                } else {
+                    self.toggle_idle_processes()
+                }
+            }
+            'h' => self.toggle_huge_pages(),
+            'a' => self.show_all_cores(),
+            'v' => self.toggle_average_cpu(),
+            'b' => self.toggle_cpu_breakdown(),
+            'U' => self.clear_tagged_processes(),
+            'M' => self.toggle_pinning_process(),
+            'F' => self.toggle_following_process(),
+            'R' => self.start_renice_dialog(),
+            '[' => self.renice_selected_process(false),
+            ']' => self.renice_selected_process(true),
             ' ' => self.on_space(),
             _ => {}
         }
@@ -1263,7 +2504,20 @@ impl App {
         }
     }
 
-    pub fn on_space(&mut self) {}
+    pub fn on_space(&mut self) {
+        match self.current_widget.widget_type {
+            BottomWidgetType::Proc => self.toggle_tagging_process(),
+            BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => self.toggle_selected_core(),
+            _ => {}
+        }
+    }
+
+    fn toggle_freeze(&mut self) {
+        self.is_frozen = !self.is_frozen;
+        if self.is_frozen {
+            self.data_collection.set_frozen_time();
+        }
+    }
 
     pub fn open_config(&mut self) {
         self.is_config_open = true;
@@ -1313,6 +2567,17 @@ impl App {
         }
     }
 
+    #[cfg(target_family = "unix")]
+    fn signal_highlighted_process(&mut self, signal: i32) -> Result<()> {
+        if let Some(current_selected_processes) = &self.to_delete_process_list {
+            for pid in &current_selected_processes.1 {
+                process_killer::send_signal_given_pid(*pid, signal)?;
+            }
+        }
+        self.to_delete_process_list = None;
+        Ok(())
+    }
+
     pub fn get_to_delete_processes(&self) -> Option<(String, Vec<Pid>)> {
         self.to_delete_process_list.clone()
     }
@@ -1929,6 +3194,74 @@ impl App {
         }
     }
 
+    /// Moves the selection of the focused table up by the number of rows it currently shows.
+    pub fn skip_page_up(&mut self) {
+        self.move_by_page(false);
+    }
+
+    /// Moves the selection of the focused table down by the number of rows it currently shows.
+    pub fn skip_page_down(&mut self) {
+        self.move_by_page(true);
+    }
+
+    fn move_by_page(&mut self, is_down: bool) {
+        if self.ignore_normal_keybinds() {
+            return;
+        }
+
+        let widget_id = self.current_widget.widget_id;
+        let scroll_info = match self.current_widget.widget_type {
+            BottomWidgetType::Proc => self.proc_state.get_widget_state(widget_id).map(|state| {
+                (
+                    &state.scroll_state,
+                    self.canvas_data
+                        .finalized_process_data_map
+                        .get(&widget_id)
+                        .map(|data| data.len())
+                        .unwrap_or(0),
+                )
+            }),
+            BottomWidgetType::Temp => self
+                .temp_state
+                .get_widget_state(widget_id)
+                .map(|state| (&state.scroll_state, self.canvas_data.temp_sensor_data.len())),
+            BottomWidgetType::Disk => self
+                .disk_state
+                .get_widget_state(widget_id)
+                .map(|state| (&state.scroll_state, self.canvas_data.disk_data.len())),
+            BottomWidgetType::CpuLegend => self
+                .cpu_state
+                .get_widget_state(widget_id - 1)
+                .map(|state| (&state.scroll_state, self.canvas_data.cpu_data.len())),
+            _ => None,
+        };
+
+        if let Some((scroll_state, num_entries)) = scroll_info {
+            let current_posn = scroll_state.current_scroll_position;
+            let page_size = std::cmp::max(scroll_state.num_visible_rows, 1);
+            let new_posn = if is_down {
+                std::cmp::min(current_posn + page_size, num_entries.saturating_sub(1))
+            } else {
+                current_posn.saturating_sub(page_size)
+            };
+
+            let num_to_change_by = new_posn as i64 - current_posn as i64;
+            if num_to_change_by != 0 {
+                match self.current_widget.widget_type {
+                    BottomWidgetType::Proc => self.increment_process_position(num_to_change_by),
+                    BottomWidgetType::Temp => self.increment_temp_position(num_to_change_by),
+                    BottomWidgetType::Disk => self.increment_disk_position(num_to_change_by),
+                    BottomWidgetType::CpuLegend => {
+                        self.increment_cpu_legend_position(num_to_change_by)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.reset_multi_tap_keys();
+    }
+
     pub fn decrement_position_count(&mut self) {
         if !self.ignore_normal_keybinds() {
             match self.current_widget.widget_type {
@@ -1936,7 +3269,9 @@ impl App {
                 BottomWidgetType::ProcSort => self.increment_process_sort_position(-1),
                 BottomWidgetType::Temp => self.increment_temp_position(-1),
                 BottomWidgetType::Disk => self.increment_disk_position(-1),
-                BottomWidgetType::CpuLegend => self.increment_cpu_legend_position(-1),
+                BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => {
+                    self.increment_cpu_legend_position(-1)
+                }
                 _ => {}
             }
         }
@@ -1949,7 +3284,9 @@ impl App {
                 BottomWidgetType::ProcSort => self.increment_process_sort_position(1),
                 BottomWidgetType::Temp => self.increment_temp_position(1),
                 BottomWidgetType::Disk => self.increment_disk_position(1),
-                BottomWidgetType::CpuLegend => self.increment_cpu_legend_position(1),
+                BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => {
+                    self.increment_cpu_legend_position(1)
+                }
                 _ => {}
             }
         }
@@ -1979,11 +3316,11 @@ impl App {
     }
 
     fn increment_cpu_legend_position(&mut self, num_to_change_by: i64) {
-        if let Some(cpu_widget_state) = self
-            .cpu_state
-            .widget_states
-            .get_mut(&(self.current_widget.widget_id - 1))
-        {
+        let cpu_widget_id = match self.get_focused_cpu_widget_id() {
+            Some(cpu_widget_id) => cpu_widget_id,
+            None => return,
+        };
+        if let Some(cpu_widget_state) = self.cpu_state.widget_states.get_mut(&cpu_widget_id) {
             let current_posn = cpu_widget_state.scroll_state.current_scroll_position;
 
             let cap = self.canvas_data.cpu_data.len();
@@ -2007,6 +3344,9 @@ impl App {
             .proc_state
             .get_mut_widget_state(self.current_widget.widget_id)
         {
+            // Moving the selection by hand means we're no longer following anything.
+            proc_widget_state.followed_process = None;
+
             let current_posn = proc_widget_state.scroll_state.current_scroll_position;
             if let Some(finalized_process_data) = self
                 .canvas_data
@@ -2099,9 +3439,23 @@ impl App {
         }
     }
 
+    fn process_info_scroll_up(&mut self) {
+        let scroll_state = &mut self.process_info_dialog_state.scroll_state;
+        scroll_state.current_scroll_index = scroll_state.current_scroll_index.saturating_sub(1);
+    }
+
+    fn process_info_scroll_down(&mut self) {
+        let scroll_state = &mut self.process_info_dialog_state.scroll_state;
+        if scroll_state.current_scroll_index < scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index += 1;
+        }
+    }
+
     pub fn handle_scroll_up(&mut self) {
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
+        } else if self.process_info_dialog_state.is_showing_info {
+            self.process_info_scroll_up();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_in();
         } else if self.current_widget.widget_type.is_widget_table() {
@@ -2112,6 +3466,8 @@ impl App {
     pub fn handle_scroll_down(&mut self) {
         if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
+        } else if self.process_info_dialog_state.is_showing_info {
+            self.process_info_scroll_down();
         } else if self.current_widget.widget_type.is_widget_graph() {
             self.zoom_out();
         } else if self.current_widget.widget_type.is_widget_table() {
@@ -2127,8 +3483,10 @@ impl App {
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = cpu_widget_state.current_display_time
-                        + self.app_config_fields.time_interval;
+                    let new_time = get_zoomed_out_time(
+                        cpu_widget_state.current_display_time,
+                        self.app_config_fields.time_interval,
+                    );
                     if new_time <= constants::STALE_MAX_MILLISECONDS {
                         cpu_widget_state.current_display_time = new_time;
                         self.cpu_state.force_update = Some(self.current_widget.widget_id);
@@ -2152,8 +3510,10 @@ impl App {
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = mem_widget_state.current_display_time
-                        + self.app_config_fields.time_interval;
+                    let new_time = get_zoomed_out_time(
+                        mem_widget_state.current_display_time,
+                        self.app_config_fields.time_interval,
+                    );
                     if new_time <= constants::STALE_MAX_MILLISECONDS {
                         mem_widget_state.current_display_time = new_time;
                         self.mem_state.force_update = Some(self.current_widget.widget_id);
@@ -2171,14 +3531,43 @@ impl App {
                     }
                 }
             }
+            BottomWidgetType::Gpu => {
+                if let Some(gpu_widget_state) = self
+                    .gpu_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = get_zoomed_out_time(
+                        gpu_widget_state.current_display_time,
+                        self.app_config_fields.time_interval,
+                    );
+                    if new_time <= constants::STALE_MAX_MILLISECONDS {
+                        gpu_widget_state.current_display_time = new_time;
+                        self.gpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            gpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if gpu_widget_state.current_display_time
+                        != constants::STALE_MAX_MILLISECONDS
+                    {
+                        gpu_widget_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
+                        self.gpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            gpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             BottomWidgetType::Net => {
                 if let Some(net_widget_state) = self
                     .net_state
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = net_widget_state.current_display_time
-                        + self.app_config_fields.time_interval;
+                    let new_time = get_zoomed_out_time(
+                        net_widget_state.current_display_time,
+                        self.app_config_fields.time_interval,
+                    );
                     if new_time <= constants::STALE_MAX_MILLISECONDS {
                         net_widget_state.current_display_time = new_time;
                         self.net_state.force_update = Some(self.current_widget.widget_id);
@@ -2208,8 +3597,10 @@ impl App {
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = cpu_widget_state.current_display_time
-                        - self.app_config_fields.time_interval;
+                    let new_time = get_zoomed_in_time(
+                        cpu_widget_state.current_display_time,
+                        self.app_config_fields.time_interval,
+                    );
                     if new_time >= constants::STALE_MIN_MILLISECONDS {
                         cpu_widget_state.current_display_time = new_time;
                         self.cpu_state.force_update = Some(self.current_widget.widget_id);
@@ -2233,8 +3624,10 @@ impl App {
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = mem_widget_state.current_display_time
-                        - self.app_config_fields.time_interval;
+                    let new_time = get_zoomed_in_time(
+                        mem_widget_state.current_display_time,
+                        self.app_config_fields.time_interval,
+                    );
                     if new_time >= constants::STALE_MIN_MILLISECONDS {
                         mem_widget_state.current_display_time = new_time;
                         self.mem_state.force_update = Some(self.current_widget.widget_id);
@@ -2252,14 +3645,43 @@ impl App {
                     }
                 }
             }
+            BottomWidgetType::Gpu => {
+                if let Some(gpu_widget_state) = self
+                    .gpu_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = get_zoomed_in_time(
+                        gpu_widget_state.current_display_time,
+                        self.app_config_fields.time_interval,
+                    );
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        gpu_widget_state.current_display_time = new_time;
+                        self.gpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            gpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if gpu_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
+                    {
+                        gpu_widget_state.current_display_time = constants::STALE_MIN_MILLISECONDS;
+                        self.gpu_state.force_update = Some(self.current_widget.widget_id);
+                        if self.app_config_fields.autohide_time {
+                            gpu_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             BottomWidgetType::Net => {
                 if let Some(net_widget_state) = self
                     .net_state
                     .widget_states
                     .get_mut(&self.current_widget.widget_id)
                 {
-                    let new_time = net_widget_state.current_display_time
-                        - self.app_config_fields.time_interval;
+                    let new_time = get_zoomed_in_time(
+                        net_widget_state.current_display_time,
+                        self.app_config_fields.time_interval,
+                    );
                     if new_time >= constants::STALE_MIN_MILLISECONDS {
                         net_widget_state.current_display_time = new_time;
                         self.net_state.force_update = Some(self.current_widget.widget_id);
@@ -2323,11 +3745,26 @@ impl App {
         }
     }
 
+    fn reset_gpu_zoom(&mut self) {
+        if let Some(gpu_widget_state) = self
+            .gpu_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            gpu_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            self.gpu_state.force_update = Some(self.current_widget.widget_id);
+            if self.app_config_fields.autohide_time {
+                gpu_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
     fn reset_zoom(&mut self) {
         match self.current_widget.widget_type {
             BottomWidgetType::Cpu => self.reset_cpu_zoom(),
             BottomWidgetType::Mem => self.reset_mem_zoom(),
             BottomWidgetType::Net => self.reset_net_zoom(),
+            BottomWidgetType::Gpu => self.reset_gpu_zoom(),
             _ => {}
         }
     }
@@ -2465,6 +3902,26 @@ impl App {
             return;
         }
 
+        // Clicking a process column header sorts by that column, just like the sort menu.
+        if let BottomWidgetType::Proc = self.current_widget.widget_type {
+            let widget_id = self.current_widget.widget_id;
+            if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget_id) {
+                if let Some(header_click_locs) = &proc_widget_state.header_click_locs {
+                    if let Some(column_index) =
+                        header_click_locs
+                            .iter()
+                            .position(|((tlc_x, tlc_y), (brc_x, brc_y))| {
+                                (x >= *tlc_x && y >= *tlc_y) && (x < *brc_x && y <= *brc_y)
+                            })
+                    {
+                        proc_widget_state.update_sorting_with_column_index(column_index);
+                        self.proc_state.force_update = Some(widget_id);
+                        return;
+                    }
+                }
+            }
+        }
+
         // Now handle click propagation down to widget.
         if let Some((_tlc_x, tlc_y)) = &self.current_widget.top_left_corner {
             match &self.current_widget.widget_type {
@@ -2519,12 +3976,18 @@ impl App {
                                     .cpu_state
                                     .get_widget_state(self.current_widget.widget_id - 1)
                                 {
-                                    if let Some(visual_index) =
-                                        cpu_widget_state.scroll_state.table_state.selected()
+                                    if let Some(entry_offset) = cpu_widget_state
+                                        .scroll_state
+                                        .table_state
+                                        .selected()
+                                        .and_then(|visual_index| {
+                                            cpu_widget_state.get_legend_entry_offset(
+                                                usize::from(offset_clicked_entry),
+                                                visual_index,
+                                            )
+                                        })
                                     {
-                                        self.increment_cpu_legend_position(
-                                            offset_clicked_entry as i64 - visual_index as i64,
-                                        );
+                                        self.increment_cpu_legend_position(entry_offset);
                                     }
                                 }
                             }
@@ -2596,3 +4059,63 @@ impl App {
         }
     }
 }
+
+/// Sets the nice value of every given process, stopping at the first failure.
+fn renice_pids(pids: &[Pid], nice: i32) -> std::result::Result<(), String> {
+    pids.iter()
+        .try_for_each(|pid| process_killer::set_nice_given_pid(*pid, nice))
+        .map_err(|err| format!("renice failed: {}", err))
+}
+
+/// Raises or lowers the nice value of every given process by `delta`, stopping at the first
+/// failure.  The current values are read first, rather than trusting the last refresh, so that
+/// quick repeated presses all count.  Returns the new nice value if every process ended up with
+/// the same one.
+fn adjust_nice_of_pids(pids: &[Pid], delta: i32) -> std::result::Result<Option<i32>, String> {
+    let mut new_nices = HashSet::new();
+    for pid in pids {
+        let nice = process_killer::get_nice_given_pid(*pid)
+            .ok_or_else(|| "renice failed: unable to read the nice value".to_string())?;
+        // Nice values range from -20 (highest priority) to 19 (lowest priority).
+        let new_nice = if delta > 0 {
+            std::cmp::min(nice + delta, 19)
+        } else {
+            std::cmp::max(nice + delta, -20)
+        };
+        renice_pids(&[*pid], new_nice)?;
+        new_nices.insert(new_nice);
+    }
+
+    Ok(if new_nices.len() == 1 {
+        new_nices.into_iter().next()
+    } else {
+        None
+    })
+}
+
+/// Returns the time window after zooming out once, which is doubled unless a fixed step was set.
+/// Clamping to the longest window is left to the caller.
+fn get_zoomed_out_time(current_display_time: u64, time_interval: Option<u64>) -> u64 {
+    match time_interval {
+        Some(time_interval) => current_display_time + time_interval,
+        None => current_display_time * 2,
+    }
+}
+
+/// Returns the time window after zooming in once, which is halved unless a fixed step was set.
+/// Clamping to the shortest window is left to the caller.
+fn get_zoomed_in_time(current_display_time: u64, time_interval: Option<u64>) -> u64 {
+    match time_interval {
+        Some(time_interval) => current_display_time.saturating_sub(time_interval),
+        None => current_display_time / 2,
+    }
+}
+
+/// Returns the process details worth calling attention to, which is currently just the affinity
+/// of a process pinned to a single core.
+fn get_highlighted_process_details(details: &processes::ProcessDetails) -> Vec<String> {
+    match &details.affinity {
+        Some(affinity) if affinity.len() == 1 => vec![convert_affinity_entry(details)],
+        _ => Vec::new(),
+    }
+}
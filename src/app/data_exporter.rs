@@ -0,0 +1,295 @@
+//! Exports a snapshot of the currently collected data to a CSV file.
+
+use std::{borrow::Cow, fmt::Write as _, fs, sync::mpsc, thread};
+
+use super::{
+    data_farmer::DataCollection,
+    data_harvester::{disks, fan, mem, network, processes, temperature},
+};
+
+/// A copy of the latest harvest, so that the export can be written without holding onto the app.
+pub struct ExportSnapshot {
+    process_harvest: Vec<processes::ProcessHarvest>,
+    disk_harvest: Vec<disks::DiskHarvest>,
+    io_labels: Vec<(String, String)>,
+    network_harvest: network::NetworkHarvest,
+    temp_harvest: Vec<temperature::TempHarvest>,
+    temperature_type: temperature::TemperatureType,
+    fan_harvest: Vec<fan::FanHarvest>,
+    memory_harvest: mem::MemHarvest,
+    swap_harvest: mem::MemHarvest,
+}
+
+impl ExportSnapshot {
+    pub fn new(
+        data_collection: &DataCollection, temperature_type: &temperature::TemperatureType,
+    ) -> Self {
+        ExportSnapshot {
+            process_harvest: data_collection.process_harvest.clone(),
+            disk_harvest: data_collection.disk_harvest.clone(),
+            io_labels: data_collection.io_labels.clone(),
+            network_harvest: data_collection.network_harvest.clone(),
+            temp_harvest: data_collection.temp_harvest.clone(),
+            temperature_type: temperature_type.clone(),
+            fan_harvest: data_collection.fan_harvest.clone(),
+            memory_harvest: data_collection.memory_harvest.clone(),
+            swap_harvest: data_collection.swap_harvest.clone(),
+        }
+    }
+
+    /// Writes the snapshot to a timestamped file in the working directory on another thread.
+    /// The receiver gets either the file name or an error message once it's done.
+    pub fn export_in_background(self) -> mpsc::Receiver<Result<String, String>> {
+        write_in_background("bottom_export", move || self.to_csv())
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::new();
+
+        write_section(
+            &mut csv,
+            "processes",
+            &[
+                "pid",
+                "parent_pid",
+                "name",
+                "command",
+                "cpu_percent",
+                "mem_percent",
+                "mem_bytes",
+                "cpu_time_secs",
+                "start_time_secs",
+                "read_bytes_per_sec",
+                "write_bytes_per_sec",
+                "total_read_bytes",
+                "total_write_bytes",
+                "fd_count",
+                "thread_count",
+                "nice",
+                "user",
+                "state",
+            ],
+            self.process_harvest.iter().map(|process| {
+                vec![
+                    process.pid.to_string(),
+                    process
+                        .parent_pid
+                        .map(|parent_pid| parent_pid.to_string())
+                        .unwrap_or_default(),
+                    process.name.clone(),
+                    process.command.clone(),
+                    format!("{:.1}", process.cpu_usage_percent),
+                    format!("{:.1}", process.mem_usage_percent),
+                    process.mem_usage_bytes.to_string(),
+                    process
+                        .total_cpu_time
+                        .map(|total_cpu_time| format!("{:.2}", total_cpu_time))
+                        .unwrap_or_default(),
+                    process
+                        .start_time_secs
+                        .map(|start_time_secs| start_time_secs.to_string())
+                        .unwrap_or_default(),
+                    process.read_bytes_per_sec.to_string(),
+                    process.write_bytes_per_sec.to_string(),
+                    process.total_read_bytes.to_string(),
+                    process.total_write_bytes.to_string(),
+                    process
+                        .fd_count
+                        .map(|fd_count| fd_count.to_string())
+                        .unwrap_or_default(),
+                    process
+                        .thread_count
+                        .map(|thread_count| thread_count.to_string())
+                        .unwrap_or_default(),
+                    process
+                        .nice
+                        .map(|nice| nice.to_string())
+                        .unwrap_or_default(),
+                    process.user.clone(),
+                    process.process_state.clone(),
+                ]
+            }),
+        );
+
+        write_section(
+            &mut csv,
+            "disks",
+            &[
+                "device",
+                "mount",
+                "used_bytes",
+                "total_bytes",
+                "read_per_sec",
+                "write_per_sec",
+            ],
+            self.disk_harvest
+                .iter()
+                .zip(&self.io_labels)
+                .map(|(disk, (io_read, io_write))| {
+                    vec![
+                        disk.name.clone(),
+                        disk.mount_point.clone(),
+                        disk.used_space.to_string(),
+                        disk.total_space.to_string(),
+                        io_read.clone(),
+                        io_write.clone(),
+                    ]
+                }),
+        );
+
+        // We only track the total across all interfaces.
+        write_section(
+            &mut csv,
+            "network",
+            &[
+                "interface",
+                "rx_bytes_per_sec",
+                "tx_bytes_per_sec",
+                "total_rx_bytes",
+                "total_tx_bytes",
+                "rx_packets_per_sec",
+                "tx_packets_per_sec",
+            ],
+            std::iter::once(vec![
+                "all".to_string(),
+                self.network_harvest.rx.to_string(),
+                self.network_harvest.tx.to_string(),
+                self.network_harvest.total_rx.to_string(),
+                self.network_harvest.total_tx.to_string(),
+                format!("{:.1}", self.network_harvest.rx_packets_per_sec),
+                format!("{:.1}", self.network_harvest.tx_packets_per_sec),
+            ]),
+        );
+
+        write_section(
+            &mut csv,
+            "temperatures",
+            &["name", "value"],
+            self.temp_harvest.iter().map(|temp| {
+                let name = match (&temp.component_name, &temp.component_label) {
+                    (Some(name), Some(label)) => format!("{}: {}", name, label),
+                    (None, Some(label)) => label.to_string(),
+                    (Some(name), None) => name.to_string(),
+                    (None, None) => String::default(),
+                };
+                vec![
+                    name,
+                    format!(
+                        "{:.1}",
+                        temperature::convert_celsius(temp.temperature, &self.temperature_type)
+                    ),
+                ]
+            }),
+        );
+
+        if !self.fan_harvest.is_empty() {
+            write_section(
+                &mut csv,
+                "fans",
+                &["name", "rpm"],
+                self.fan_harvest
+                    .iter()
+                    .map(|fan| vec![fan.name.clone(), fan.rpm.to_string()]),
+            );
+        }
+
+        write_section(
+            &mut csv,
+            "memory",
+            &["type", "used_mb", "total_mb"],
+            vec![("ram", &self.memory_harvest), ("swap", &self.swap_harvest)]
+                .into_iter()
+                .map(|(mem_type, mem)| {
+                    vec![
+                        mem_type.to_string(),
+                        mem.mem_used_in_mb.to_string(),
+                        mem.mem_total_in_mb.to_string(),
+                    ]
+                }),
+        );
+
+        csv
+    }
+}
+
+/// A copy of the rows of a process widget, as they are displayed: filtered, grouped, and sorted.
+pub struct ProcessTableSnapshot {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl ProcessTableSnapshot {
+    pub fn new(header: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        ProcessTableSnapshot { header, rows }
+    }
+
+    /// Writes the table to a timestamped file in the working directory on another thread, like
+    /// [`ExportSnapshot::export_in_background`].
+    pub fn export_in_background(self) -> mpsc::Receiver<Result<String, String>> {
+        write_in_background("bottom_processes", move || {
+            let mut csv = String::new();
+            let header = self
+                .header
+                .iter()
+                .map(|field| escape_field(field))
+                .collect::<Vec<_>>();
+            let _ = writeln!(csv, "{}", header.join(","));
+            for row in &self.rows {
+                let row = row
+                    .iter()
+                    .map(|field| escape_field(field))
+                    .collect::<Vec<_>>();
+                let _ = writeln!(csv, "{}", row.join(","));
+            }
+            csv
+        })
+    }
+}
+
+/// Writes the output of `to_csv` to a file named after the prefix and the current time, on
+/// another thread.
+fn write_in_background(
+    file_prefix: &str, to_csv: impl FnOnce() -> String + Send + 'static,
+) -> mpsc::Receiver<Result<String, String>> {
+    let (sender, receiver) = mpsc::channel();
+    let file_name = format!(
+        "{}_{}.csv",
+        file_prefix,
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    );
+
+    thread::spawn(move || {
+        let result = fs::write(&file_name, to_csv())
+            .map(|_| file_name)
+            .map_err(|err| err.to_string());
+        // The app may have quit in the meantime, in which case nobody cares about the result.
+        let _ = sender.send(result);
+    });
+
+    receiver
+}
+
+/// Writes a section, which is a title line, a header, and then the rows, followed by a blank line.
+fn write_section(
+    csv: &mut String, title: &str, header: &[&str], rows: impl Iterator<Item = Vec<String>>,
+) {
+    let _ = writeln!(csv, "# {}", title);
+    let _ = writeln!(csv, "{}", header.join(","));
+    for row in rows {
+        let row = row
+            .iter()
+            .map(|field| escape_field(field))
+            .collect::<Vec<_>>();
+        let _ = writeln!(csv, "{}", row.join(","));
+    }
+    csv.push('\n');
+}
+
+/// Quotes a field if it contains anything that would otherwise break the CSV.
+fn escape_field(field: &str) -> Cow<'_, str> {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
@@ -16,21 +16,33 @@ use lazy_static::lazy_static;
 use std::{time::Instant, vec::Vec};
 
 use crate::{
-    data_harvester::{battery_harvester, cpu, disks, mem, network, processes, temperature, Data},
-    utils::gen_util::get_simple_byte_values,
+    data_harvester::{
+        battery_harvester, cpu, disks, fan, gpu, mem, network, processes, sockets, temperature,
+        zfs, Data,
+    },
+    utils::gen_util::humanize_bytes,
 };
 use regex::Regex;
 
 pub type TimeOffset = f64;
 pub type Value = f64;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct TimedData {
     pub rx_data: Value,
     pub tx_data: Value,
     pub cpu_data: Vec<Value>,
+    pub cpu_iowait_per_core_data: Vec<Value>,
+    /// The share of all CPU time spent in the kernel and waiting on I/O, if known.
+    pub cpu_system_data: Value,
+    pub cpu_iowait_data: Value,
+    /// The running totals of user, system, IRQ, softIRQ, I/O wait, and steal time of all CPUs, so
+    /// that each can be drawn stacked on the ones before it.
+    pub cpu_stacked_data: Vec<Value>,
     pub mem_data: Value,
     pub swap_data: Value,
+    pub arc_data: Value,
+    pub gpu_data: Vec<Value>,
 }
 
 /// AppCollection represents the pooled data stored within the main app
@@ -45,6 +57,7 @@ pub struct TimedData {
 #[derive(Debug)]
 pub struct DataCollection {
     pub current_instant: Instant,
+    pub last_disk_instant: Instant,
     pub frozen_instant: Option<Instant>,
     pub timed_data_vec: Vec<(Instant, TimedData)>,
     pub network_harvest: network::NetworkHarvest,
@@ -57,13 +70,20 @@ pub struct DataCollection {
     pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
     pub io_labels: Vec<(String, String)>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
+    pub fan_harvest: Vec<fan::FanHarvest>,
     pub battery_harvest: Vec<battery_harvester::BatteryHarvest>,
+    pub gpu_harvest: Vec<gpu::GpuHarvest>,
+    pub zfs_harvest: Option<zfs::ZfsHarvest>,
+    pub load_avg_harvest: Option<cpu::LoadAvgHarvest>,
+    pub cpu_breakdown_harvest: Option<cpu::CpuBreakdownHarvest>,
+    pub socket_harvest: Option<sockets::SocketHarvest>,
 }
 
 impl Default for DataCollection {
     fn default() -> Self {
         DataCollection {
             current_instant: Instant::now(),
+            last_disk_instant: Instant::now(),
             frozen_instant: None,
             timed_data_vec: Vec::default(),
             network_harvest: network::NetworkHarvest::default(),
@@ -76,7 +96,13 @@ impl Default for DataCollection {
             io_labels_and_prev: Vec::default(),
             io_labels: Vec::default(),
             temp_harvest: Vec::default(),
+            fan_harvest: Vec::default(),
             battery_harvest: Vec::default(),
+            gpu_harvest: Vec::default(),
+            zfs_harvest: None,
+            load_avg_harvest: None,
+            cpu_breakdown_harvest: None,
+            socket_harvest: None,
         }
     }
 }
@@ -93,7 +119,13 @@ impl DataCollection {
         self.io_harvest = disks::IOHarvest::default();
         self.io_labels_and_prev = Vec::default();
         self.temp_harvest = Vec::default();
+        self.fan_harvest = Vec::default();
         self.battery_harvest = Vec::default();
+        self.gpu_harvest = Vec::default();
+        self.zfs_harvest = None;
+        self.load_avg_harvest = None;
+        self.cpu_breakdown_harvest = None;
+        self.socket_harvest = None;
     }
 
     pub fn set_frozen_time(&mut self) {
@@ -119,21 +151,83 @@ impl DataCollection {
         let harvested_time = harvested_data.last_collection_time;
         let mut new_entry = TimedData::default();
 
+        // Anything that wasn't collected this time (for example, due to its collection rate)
+        // keeps its previous value, rather than dropping to zero on the graphs.
+        let previous_entry = self
+            .timed_data_vec
+            .last()
+            .map(|(_, entry)| entry.clone())
+            .unwrap_or_default();
+
         // Network
         if let Some(network) = &harvested_data.network {
             self.eat_network(network, &mut new_entry);
+        } else {
+            new_entry.rx_data = previous_entry.rx_data;
+            new_entry.tx_data = previous_entry.tx_data;
         }
 
         // Memory and Swap
-        if let Some(memory) = &harvested_data.memory {
-            if let Some(swap) = &harvested_data.swap {
-                self.eat_memory_and_swap(memory, swap, &mut new_entry);
+        match (&harvested_data.memory, &harvested_data.swap) {
+            (Some(memory), Some(swap)) => self.eat_memory_and_swap(memory, swap, &mut new_entry),
+            _ => {
+                new_entry.mem_data = previous_entry.mem_data;
+                new_entry.swap_data = previous_entry.swap_data;
             }
         }
 
+        // ZFS
+        if let Some(zfs) = &harvested_data.zfs {
+            self.eat_zfs(zfs, &mut new_entry);
+        } else {
+            new_entry.arc_data = previous_entry.arc_data;
+        }
+
         // CPU
         if let Some(cpu) = &harvested_data.cpu {
             self.eat_cpu(cpu, &mut new_entry);
+        } else {
+            new_entry.cpu_data = previous_entry.cpu_data;
+            new_entry.cpu_iowait_per_core_data = previous_entry.cpu_iowait_per_core_data;
+        }
+
+        // Load average
+        if let Some(load_avg) = &harvested_data.load_avg {
+            self.load_avg_harvest = Some(load_avg.clone());
+        }
+
+        // CPU breakdown
+        if let Some(cpu_breakdown) = &harvested_data.cpu_breakdown {
+            new_entry.cpu_system_data = cpu_breakdown.system_percent
+                + cpu_breakdown.irq_percent
+                + cpu_breakdown.softirq_percent;
+            new_entry.cpu_iowait_data = cpu_breakdown.iowait_percent;
+            new_entry.cpu_stacked_data = [
+                cpu_breakdown.user_percent,
+                cpu_breakdown.system_percent,
+                cpu_breakdown.irq_percent,
+                cpu_breakdown.softirq_percent,
+                cpu_breakdown.iowait_percent,
+                cpu_breakdown.steal_percent,
+            ]
+            .iter()
+            .scan(0.0, |total, percent| {
+                *total += percent;
+                Some(*total)
+            })
+            .collect();
+            self.cpu_breakdown_harvest = Some(cpu_breakdown.clone());
+        } else {
+            new_entry.cpu_system_data = previous_entry.cpu_system_data;
+            new_entry.cpu_iowait_data = previous_entry.cpu_iowait_data;
+            new_entry.cpu_stacked_data = previous_entry.cpu_stacked_data;
+        }
+
+        // GPU
+        if let Some(gpu) = &harvested_data.gpu {
+            self.eat_gpu(gpu, &mut new_entry);
+        } else {
+            new_entry.gpu_data = previous_entry.gpu_data;
         }
 
         // Temp
@@ -141,6 +235,11 @@ impl DataCollection {
             self.eat_temp(temperature_sensors);
         }
 
+        // Fans
+        if let Some(fan_data) = &harvested_data.fan_data {
+            self.eat_fans(fan_data);
+        }
+
         // Disks
         if let Some(disks) = &harvested_data.disks {
             if let Some(io) = &harvested_data.io {
@@ -158,6 +257,11 @@ impl DataCollection {
             self.eat_battery(list_of_batteries);
         }
 
+        // Sockets
+        if let Some(sockets) = &harvested_data.sockets {
+            self.socket_harvest = Some(sockets.clone());
+        }
+
         // And we're done eating.  Update time and push the new entry!
         self.current_instant = harvested_time;
         self.timed_data_vec.push((harvested_time, new_entry));
@@ -187,6 +291,15 @@ impl DataCollection {
         self.swap_harvest = swap.clone();
     }
 
+    fn eat_zfs(&mut self, zfs: &zfs::ZfsHarvest, new_entry: &mut TimedData) {
+        new_entry.arc_data = match zfs.arc_max_bytes {
+            0 => 0f64,
+            max => (zfs.arc_size_bytes as f64) / (max as f64) * 100.0,
+        };
+
+        self.zfs_harvest = Some(zfs.clone());
+    }
+
     fn eat_network(&mut self, network: &network::NetworkHarvest, new_entry: &mut TimedData) {
         // FIXME [NETWORKING; CONFIG]: The ability to config this?
         // FIXME [NETWORKING]: Support bits, support switching between decimal and binary units (move the log part to conversion and switch on the fly)
@@ -212,27 +325,42 @@ impl DataCollection {
         // Note this only pre-calculates the data points - the names will be
         // within the local copy of cpu_harvest.  Since it's all sequential
         // it probably doesn't matter anyways.
-        cpu.iter()
-            .for_each(|cpu| new_entry.cpu_data.push(cpu.cpu_usage));
+        cpu.iter().for_each(|cpu| {
+            new_entry.cpu_data.push(cpu.cpu_usage);
+            new_entry.cpu_iowait_per_core_data.push(cpu.iowait_percent);
+        });
 
         self.cpu_harvest = cpu.to_vec();
     }
 
+    fn eat_gpu(&mut self, gpu: &[gpu::GpuHarvest], new_entry: &mut TimedData) {
+        gpu.iter()
+            .for_each(|gpu| new_entry.gpu_data.push(gpu.utilization_percent));
+
+        self.gpu_harvest = gpu.to_vec();
+    }
+
     fn eat_temp(&mut self, temperature_sensors: &[temperature::TempHarvest]) {
         // TODO: [PO] To implement
         self.temp_harvest = temperature_sensors.to_vec();
     }
 
+    fn eat_fans(&mut self, fan_data: &[fan::FanHarvest]) {
+        self.fan_harvest = fan_data.to_vec();
+    }
+
     fn eat_disks(
         &mut self, disks: &[disks::DiskHarvest], io: &disks::IOHarvest, harvested_time: Instant,
     ) {
         // TODO: [PO] To implement
 
         let time_since_last_harvest = harvested_time
-            .duration_since(self.current_instant)
+            .duration_since(self.last_disk_instant)
             .as_secs_f64();
+        self.last_disk_instant = harvested_time;
 
-        for (itx, device) in disks.iter().enumerate() {
+        let mut disks = disks.to_vec();
+        for (itx, device) in disks.iter_mut().enumerate() {
             if let Some(trim) = device.name.split('/').last() {
                 let io_device = if cfg!(target_os = "macos") {
                     // Must trim one level further!
@@ -273,13 +401,13 @@ impl DataCollection {
 
                         *io_curr = (r_rate, w_rate);
                         *io_prev = (io_r_pt, io_w_pt);
+                        device.read_bytes_per_sec = r_rate as f64;
+                        device.write_bytes_per_sec = w_rate as f64;
 
                         if let Some(io_labels) = self.io_labels.get_mut(itx) {
-                            let converted_read = get_simple_byte_values(r_rate, false);
-                            let converted_write = get_simple_byte_values(w_rate, false);
                             *io_labels = (
-                                format!("{:.*}{}/s", 0, converted_read.0, converted_read.1),
-                                format!("{:.*}{}/s", 0, converted_write.0, converted_write.1),
+                                format!("{}/s", humanize_bytes(r_rate)),
+                                format!("{}/s", humanize_bytes(w_rate)),
                             );
                         }
                     }
@@ -295,7 +423,7 @@ impl DataCollection {
             }
         }
 
-        self.disk_harvest = disks.to_vec();
+        self.disk_harvest = disks;
         self.io_harvest = io.clone();
     }
 
@@ -1,10 +1,8 @@
 //! This is the main file to house data collection functions.
 
-use std::time::Instant;
-
-#[cfg(target_os = "linux")]
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 
+use serde::Serialize;
 use sysinfo::{System, SystemExt};
 
 use battery::{Battery, Manager};
@@ -16,23 +14,72 @@ use futures::join;
 pub mod battery_harvester;
 pub mod cpu;
 pub mod disks;
+pub mod docker;
+pub mod fan;
+pub mod gpu;
 pub mod mem;
 pub mod network;
 pub mod processes;
+pub mod sockets;
 pub mod temperature;
+pub mod zfs;
+
+/// How often each kind of data is collected, in milliseconds.  Anything without a rate is
+/// collected on every update.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CollectionRates {
+    pub cpu: Option<u64>,
+    pub memory: Option<u64>,
+    pub disk: Option<u64>,
+    pub processes: Option<u64>,
+    pub network: Option<u64>,
+    pub temperature: Option<u64>,
+}
 
-#[derive(Clone, Debug)]
+/// Whether enough time has passed since something was last collected to collect it again.
+fn is_due(last_update: Option<Instant>, rate_in_ms: Option<u64>, current_instant: Instant) -> bool {
+    match (last_update, rate_in_ms) {
+        (Some(last_update), Some(rate_in_ms)) => {
+            current_instant.duration_since(last_update).as_millis() >= u128::from(rate_in_ms)
+        }
+        _ => true,
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub struct Data {
+    #[serde(skip)]
     pub last_collection_time: Instant,
+    #[serde(rename = "cpu")]
     pub cpu: Option<cpu::CpuHarvest>,
+    #[serde(rename = "memory")]
     pub memory: Option<mem::MemHarvest>,
+    #[serde(rename = "swap")]
     pub swap: Option<mem::MemHarvest>,
+    #[serde(rename = "temperature_sensors")]
     pub temperature_sensors: Option<Vec<temperature::TempHarvest>>,
+    #[serde(rename = "fan_data")]
+    pub fan_data: Option<Vec<fan::FanHarvest>>,
+    #[serde(rename = "network")]
     pub network: Option<network::NetworkHarvest>,
+    #[serde(rename = "list_of_processes")]
     pub list_of_processes: Option<Vec<processes::ProcessHarvest>>,
+    #[serde(rename = "disks")]
     pub disks: Option<Vec<disks::DiskHarvest>>,
+    #[serde(rename = "io")]
     pub io: Option<disks::IOHarvest>,
+    #[serde(rename = "list_of_batteries")]
     pub list_of_batteries: Option<Vec<battery_harvester::BatteryHarvest>>,
+    #[serde(rename = "gpu")]
+    pub gpu: Option<Vec<gpu::GpuHarvest>>,
+    #[serde(rename = "zfs")]
+    pub zfs: Option<zfs::ZfsHarvest>,
+    #[serde(rename = "load_avg")]
+    pub load_avg: Option<cpu::LoadAvgHarvest>,
+    #[serde(rename = "cpu_breakdown")]
+    pub cpu_breakdown: Option<cpu::CpuBreakdownHarvest>,
+    #[serde(rename = "sockets")]
+    pub sockets: Option<sockets::SocketHarvest>,
 }
 
 impl Default for Data {
@@ -43,11 +90,17 @@ impl Default for Data {
             memory: None,
             swap: None,
             temperature_sensors: None,
+            fan_data: None,
             list_of_processes: None,
             disks: None,
             io: None,
             network: None,
             list_of_batteries: None,
+            gpu: None,
+            zfs: None,
+            load_avg: None,
+            cpu_breakdown: None,
+            sockets: None,
         }
     }
 }
@@ -56,11 +109,13 @@ impl Data {
     pub fn first_run_cleanup(&mut self) {
         self.io = None;
         self.temperature_sensors = None;
+        self.fan_data = None;
         self.list_of_processes = None;
         self.disks = None;
         self.memory = None;
         self.swap = None;
         self.cpu = None;
+        self.cpu_breakdown = None;
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
@@ -78,17 +133,43 @@ pub struct DataCollector {
     #[cfg(target_os = "linux")]
     prev_non_idle: f64,
     mem_total_kb: u64,
-    temperature_type: temperature::TemperatureType,
     use_current_cpu_total: bool,
     last_collection_time: Instant,
     total_rx: u64,
     total_tx: u64,
+    total_packets: (u64, u64),
     show_average_cpu: bool,
+    show_fans: bool,
+    show_zfs: bool,
+    show_zram: bool,
+    /// The NUMA node of each core, read once at startup.
+    numa_nodes: HashMap<usize, u32>,
+    /// The total ARC hits and misses as of the last collection.
+    prev_arc_counts: Option<(u64, u64)>,
+    /// The times of each line of `/proc/stat` as of the last CPU collection.
+    prev_cpu_times: HashMap<String, cpu::CpuTimes>,
+    /// The total pages swapped in and out as of the last memory collection.
+    #[cfg(target_os = "linux")]
+    prev_swap_counts: Option<(u64, u64)>,
+    hide_kernel_threads: bool,
     widgets_to_harvest: UsedWidgets,
+    collection_rates: CollectionRates,
+    net_filter: Option<globset::GlobSet>,
+    docker_socket: Option<String>,
+    last_cpu_update: Option<Instant>,
+    last_mem_update: Option<Instant>,
+    last_disk_update: Option<Instant>,
+    last_proc_update: Option<Instant>,
+    last_net_update: Option<Instant>,
+    last_temp_update: Option<Instant>,
     battery_manager: Option<Manager>,
     battery_list: Option<Vec<Battery>>,
+    #[cfg(feature = "nvidia")]
+    nvml: Option<nvml_wrapper::Nvml>,
     #[cfg(target_os = "linux")]
     page_file_size_kb: u64,
+    #[cfg(target_family = "unix")]
+    user_table: users::UsersCache,
 }
 
 impl Default for DataCollector {
@@ -103,17 +184,39 @@ impl Default for DataCollector {
             #[cfg(target_os = "linux")]
             prev_non_idle: 0_f64,
             mem_total_kb: 0,
-            temperature_type: temperature::TemperatureType::Celsius,
             use_current_cpu_total: false,
             last_collection_time: Instant::now(),
             total_rx: 0,
             total_tx: 0,
+            total_packets: (0, 0),
             show_average_cpu: false,
+            show_fans: false,
+            show_zfs: false,
+            show_zram: false,
+            numa_nodes: HashMap::new(),
+            prev_arc_counts: None,
+            prev_cpu_times: HashMap::new(),
+            #[cfg(target_os = "linux")]
+            prev_swap_counts: None,
+            hide_kernel_threads: false,
             widgets_to_harvest: UsedWidgets::default(),
+            collection_rates: CollectionRates::default(),
+            net_filter: None,
+            docker_socket: None,
+            last_cpu_update: None,
+            last_mem_update: None,
+            last_disk_update: None,
+            last_proc_update: None,
+            last_net_update: None,
+            last_temp_update: None,
             battery_manager: None,
             battery_list: None,
+            #[cfg(feature = "nvidia")]
+            nvml: None,
             #[cfg(target_os = "linux")]
             page_file_size_kb: unsafe { libc::sysconf(libc::_SC_PAGESIZE) as u64 / 1024 },
+            #[cfg(target_family = "unix")]
+            user_table: users::UsersCache::new(),
         }
     }
 }
@@ -122,6 +225,10 @@ impl DataCollector {
     pub fn init(&mut self) {
         self.mem_total_kb = self.sys.get_total_memory();
 
+        if self.widgets_to_harvest.use_cpu {
+            self.numa_nodes = cpu::get_numa_nodes();
+        }
+
         if self.widgets_to_harvest.use_battery {
             if let Ok(battery_manager) = Manager::new() {
                 if let Ok(batteries) = battery_manager.batteries() {
@@ -134,19 +241,30 @@ impl DataCollector {
             }
         }
 
+        #[cfg(feature = "nvidia")]
+        {
+            if self.widgets_to_harvest.use_gpu {
+                self.nvml = nvml_wrapper::Nvml::init().ok();
+            }
+        }
+
         futures::executor::block_on(self.update_data());
         std::thread::sleep(std::time::Duration::from_millis(250));
         self.data.first_run_cleanup();
+
+        // The first collection only sets baselines, so make sure everything is collected again.
+        self.last_cpu_update = None;
+        self.last_mem_update = None;
+        self.last_disk_update = None;
+        self.last_proc_update = None;
+        self.last_net_update = None;
+        self.last_temp_update = None;
     }
 
     pub fn set_collected_data(&mut self, used_widgets: UsedWidgets) {
         self.widgets_to_harvest = used_widgets;
     }
 
-    pub fn set_temperature_type(&mut self, temperature_type: temperature::TemperatureType) {
-        self.temperature_type = temperature_type;
-    }
-
     pub fn set_use_current_cpu_total(&mut self, use_current_cpu_total: bool) {
         self.use_current_cpu_total = use_current_cpu_total;
     }
@@ -155,44 +273,92 @@ impl DataCollector {
         self.show_average_cpu = show_average_cpu;
     }
 
+    pub fn set_show_fans(&mut self, show_fans: bool) {
+        self.show_fans = show_fans;
+    }
+
+    pub fn set_show_zfs(&mut self, show_zfs: bool) {
+        self.show_zfs = show_zfs;
+    }
+
+    pub fn set_show_zram(&mut self, show_zram: bool) {
+        self.show_zram = show_zram;
+    }
+
+    pub fn set_hide_kernel_threads(&mut self, hide_kernel_threads: bool) {
+        self.hide_kernel_threads = hide_kernel_threads;
+    }
+
+    pub fn set_collection_rates(&mut self, collection_rates: CollectionRates) {
+        self.collection_rates = collection_rates;
+    }
+
+    pub fn set_net_filter(&mut self, net_filter: Option<globset::GlobSet>) {
+        self.net_filter = net_filter;
+    }
+
+    pub fn set_docker_socket(&mut self, docker_socket: Option<String>) {
+        self.docker_socket = docker_socket;
+    }
+
     pub async fn update_data(&mut self) {
-        if self.widgets_to_harvest.use_cpu {
+        // Skip anything that was collected more recently than its rate allows.
+        let current_instant = std::time::Instant::now();
+        let rates = self.collection_rates;
+        let use_cpu = self.widgets_to_harvest.use_cpu
+            && is_due(self.last_cpu_update, rates.cpu, current_instant);
+        let use_mem = self.widgets_to_harvest.use_mem
+            && is_due(self.last_mem_update, rates.memory, current_instant);
+        let use_disk = self.widgets_to_harvest.use_disk
+            && is_due(self.last_disk_update, rates.disk, current_instant);
+        let use_proc = self.widgets_to_harvest.use_proc
+            && is_due(self.last_proc_update, rates.processes, current_instant);
+        let use_net = self.widgets_to_harvest.use_net
+            && is_due(self.last_net_update, rates.network, current_instant);
+        let use_temp = self.widgets_to_harvest.use_temp
+            && is_due(self.last_temp_update, rates.temperature, current_instant);
+
+        if use_cpu {
             self.sys.refresh_cpu();
         }
 
         if cfg!(any(target_arch = "arm", target_arch = "aarch64")) {
             // ARM stuff
-            if self.widgets_to_harvest.use_proc {
+            if use_proc {
                 self.sys.refresh_processes();
             }
-            if self.widgets_to_harvest.use_temp {
+            if use_temp {
                 self.sys.refresh_components();
             }
-            if self.widgets_to_harvest.use_net {
+            if use_net {
                 self.sys.refresh_networks();
             }
-            if self.widgets_to_harvest.use_mem {
+            if use_mem {
                 self.sys.refresh_memory();
             }
         } else {
             if cfg!(not(target_os = "linux")) {
-                if self.widgets_to_harvest.use_proc {
+                if use_proc {
                     self.sys.refresh_processes();
                 }
-                if self.widgets_to_harvest.use_temp {
+                if use_temp {
                     self.sys.refresh_components();
                 }
             }
-            if cfg!(target_os = "windows") && self.widgets_to_harvest.use_net {
+            if cfg!(target_os = "windows") && use_net {
                 self.sys.refresh_networks();
             }
         }
 
-        let current_instant = std::time::Instant::now();
-
         // CPU
-        if self.widgets_to_harvest.use_cpu {
-            self.data.cpu = Some(cpu::get_cpu_data_list(&self.sys, self.show_average_cpu));
+        if use_cpu {
+            let mut cpu_harvest =
+                cpu::get_cpu_data_list(&self.sys, self.show_average_cpu, &self.numa_nodes);
+            let cpu_breakdowns = cpu::get_cpu_breakdowns(&mut self.prev_cpu_times);
+            cpu::set_core_breakdowns(&mut cpu_harvest, &cpu_breakdowns);
+            self.data.cpu = Some(cpu_harvest);
+            self.data.load_avg = cpu::get_load_avg(&self.sys);
+            self.data.cpu_breakdown = cpu_breakdowns.get("cpu").cloned();
         }
 
         // Batteries
@@ -205,11 +371,42 @@ impl DataCollector {
             }
         }
 
-        if self.widgets_to_harvest.use_proc {
+        // Fans
+        self.data.fan_data = fan::get_fan_data(use_temp && self.show_fans);
+
+        // ZFS
+        self.data.zfs = zfs::get_zfs_data(use_mem && self.show_zfs, &mut self.prev_arc_counts);
+
+        // GPUs
+        if self.widgets_to_harvest.use_gpu {
+            #[allow(unused_mut)]
+            let mut gpu_vec: Vec<gpu::GpuHarvest> = Vec::new();
+
+            #[cfg(target_os = "linux")]
+            {
+                gpu_vec.extend(gpu::amd_gpu_data());
+            }
+
+            #[cfg(feature = "nvidia")]
+            {
+                if let Some(nvml) = &self.nvml {
+                    gpu_vec.extend(gpu::nvidia_gpu_data(nvml));
+                }
+            }
+
+            self.data.gpu = Some(gpu_vec);
+        }
+
+        // Sockets
+        if self.widgets_to_harvest.use_sockets {
+            self.data.sockets = sockets::get_socket_data();
+        }
+
+        if use_proc {
             // Processes.  This is the longest part of the harvesting process... changing this might be
             // good in the future.  What was tried already:
             // * Splitting the internal part into multiple scoped threads (dropped by ~.01 seconds, but upped usage)
-            if let Ok(process_list) = if cfg!(target_os = "linux") {
+            if let Ok(mut process_list) = if cfg!(target_os = "linux") {
                 #[cfg(target_os = "linux")]
                 {
                     processes::linux_processes(
@@ -218,10 +415,14 @@ impl DataCollector {
                         &mut self.pid_mapping,
                         self.use_current_cpu_total,
                         current_instant
-                            .duration_since(self.last_collection_time)
+                            .duration_since(
+                                self.last_proc_update.unwrap_or(self.last_collection_time),
+                            )
                             .as_secs(),
                         self.mem_total_kb,
                         self.page_file_size_kb,
+                        &self.user_table,
+                        self.hide_kernel_threads,
                     )
                 }
                 #[cfg(not(target_os = "linux"))]
@@ -235,6 +436,8 @@ impl DataCollector {
                         &self.sys,
                         self.use_current_cpu_total,
                         self.mem_total_kb,
+                        #[cfg(target_family = "unix")]
+                        &self.user_table,
                     )
                 }
                 #[cfg(target_os = "linux")]
@@ -242,6 +445,11 @@ impl DataCollector {
                     Ok(Vec::new())
                 }
             } {
+                if let Some(docker_socket) = &self.docker_socket {
+                    if let Some(containers) = docker::get_containers(docker_socket) {
+                        docker::associate_containers(&mut process_list, &containers);
+                    }
+                }
                 self.data.list_of_processes = Some(process_list);
             }
         }
@@ -252,76 +460,76 @@ impl DataCollector {
             {
                 network::arm_or_windows_network_data(
                     &self.sys,
-                    self.last_collection_time,
+                    self.last_net_update.unwrap_or(self.last_collection_time),
                     &mut self.total_rx,
                     &mut self.total_tx,
+                    &mut self.total_packets,
                     current_instant,
-                    self.widgets_to_harvest.use_net,
+                    use_net,
+                    &self.net_filter,
                 )
             }
             #[cfg(not(any(target_os = "windows", target_arch = "aarch64", target_arch = "arm")))]
             {
                 network::non_arm_or_windows_network_data(
-                    self.last_collection_time,
+                    self.last_net_update.unwrap_or(self.last_collection_time),
                     &mut self.total_rx,
                     &mut self.total_tx,
+                    &mut self.total_packets,
                     current_instant,
-                    self.widgets_to_harvest.use_net,
+                    use_net,
+                    &self.net_filter,
                 )
             }
         };
         let mem_data_fut = {
             #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
             {
-                mem::arm_mem_data(&self.sys, self.widgets_to_harvest.use_mem)
+                mem::arm_mem_data(&self.sys, use_mem)
             }
 
             #[cfg(not(any(target_arch = "aarch64", target_arch = "arm")))]
             {
-                mem::non_arm_mem_data(self.widgets_to_harvest.use_mem)
+                mem::non_arm_mem_data(use_mem)
             }
         };
         let swap_data_fut = {
             #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
             {
-                mem::arm_swap_data(&self.sys, self.widgets_to_harvest.use_mem)
+                mem::arm_swap_data(&self.sys, use_mem)
             }
 
             #[cfg(not(any(target_arch = "aarch64", target_arch = "arm")))]
             {
-                mem::non_arm_swap_data(self.widgets_to_harvest.use_mem)
+                mem::non_arm_swap_data(use_mem)
             }
         };
         let disk_data_fut = {
             #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
             {
-                disks::arm_disk_usage(&self.sys, self.widgets_to_harvest.use_disk)
+                disks::arm_disk_usage(&self.sys, use_disk)
             }
 
             #[cfg(not(any(target_arch = "aarch64", target_arch = "arm")))]
             {
-                disks::non_arm_disk_usage(self.widgets_to_harvest.use_disk)
+                disks::non_arm_disk_usage(use_disk)
             }
         };
         let disk_io_usage_fut = {
             #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
             {
-                disks::arm_io_usage(&self.sys, self.widgets_to_harvest.use_disk)
+                disks::arm_io_usage(&self.sys, use_disk)
             }
 
             #[cfg(not(any(target_arch = "aarch64", target_arch = "arm")))]
             {
-                disks::non_arm_io_usage(false, self.widgets_to_harvest.use_disk)
+                disks::non_arm_io_usage(false, use_disk)
             }
         };
         let temp_data_fut = {
             #[cfg(any(not(target_os = "linux"), target_arch = "aarch64", target_arch = "arm"))]
             {
-                temperature::arm_and_non_linux_temperature_data(
-                    &self.sys,
-                    &self.temperature_type,
-                    self.widgets_to_harvest.use_temp,
-                )
+                temperature::arm_and_non_linux_temperature_data(&self.sys, use_temp)
             }
 
             #[cfg(not(any(
@@ -330,10 +538,7 @@ impl DataCollector {
                 target_arch = "arm"
             )))]
             {
-                temperature::linux_temperature_data(
-                    &self.temperature_type,
-                    self.widgets_to_harvest.use_temp,
-                )
+                temperature::linux_temperature_data(use_temp)
             }
         };
 
@@ -352,12 +557,35 @@ impl DataCollector {
             self.data.network = Some(net_data);
         }
 
-        if let Ok(memory) = mem_res {
+        if let Ok(mut memory) = mem_res {
+            // The kernel counts the ARC as used, even though ZFS gives it up under pressure.
+            if let (Some(memory), Some(zfs)) = (&mut memory, &self.data.zfs) {
+                memory.mem_used_in_mb = memory
+                    .mem_used_in_mb
+                    .saturating_sub(zfs.arc_size_bytes / 1024 / 1024);
+            }
             self.data.memory = memory;
         }
 
         if let Ok(swap) = swap_res {
             self.data.swap = swap;
+            if self.show_zram {
+                if let Some(swap) = &mut self.data.swap {
+                    mem::add_zram_data(swap);
+                }
+            }
+            #[cfg(target_os = "linux")]
+            {
+                if let Some(swap) = &mut self.data.swap {
+                    mem::add_swap_io_rates(
+                        swap,
+                        &mut self.prev_swap_counts,
+                        self.page_file_size_kb,
+                        self.last_mem_update.unwrap_or(self.last_collection_time),
+                        current_instant,
+                    );
+                }
+            }
         }
 
         if let Ok(disks) = disk_res {
@@ -372,7 +600,19 @@ impl DataCollector {
             self.data.temperature_sensors = temp;
         }
 
-        // Update time
+        // Update times
+        for (was_updated, last_update) in &mut [
+            (use_cpu, &mut self.last_cpu_update),
+            (use_mem, &mut self.last_mem_update),
+            (use_disk, &mut self.last_disk_update),
+            (use_proc, &mut self.last_proc_update),
+            (use_net, &mut self.last_net_update),
+            (use_temp, &mut self.last_temp_update),
+        ] {
+            if *was_updated {
+                **last_update = Some(current_instant);
+            }
+        }
         self.data.last_collection_time = current_instant;
         self.last_collection_time = current_instant;
     }
@@ -1,15 +1,27 @@
 use battery::{
     units::{power::watt, ratio::percent, time::second, Time},
-    Battery, Manager,
+    Battery, Manager, State,
 };
+use serde::{Serialize, Serializer};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BatteryHarvest {
+    #[serde(rename = "charge_percent")]
     pub charge_percent: f64,
+    #[serde(rename = "secs_until_full")]
     pub secs_until_full: Option<i64>,
+    #[serde(rename = "secs_until_empty")]
     pub secs_until_empty: Option<i64>,
+    #[serde(rename = "power_consumption_rate_watts")]
     pub power_consumption_rate_watts: f64,
+    #[serde(rename = "health_percent")]
     pub health_percent: f64,
+    #[serde(rename = "state", serialize_with = "serialize_state")]
+    pub state: State,
+}
+
+fn serialize_state<S: Serializer>(state: &State, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&state.to_string())
 }
 
 fn convert_optional_time_to_optional_seconds(optional_time: Option<Time>) -> Option<i64> {
@@ -35,6 +47,7 @@ pub fn refresh_batteries(manager: &Manager, batteries: &mut [Battery]) -> Vec<Ba
                     charge_percent: f64::from(battery.state_of_charge().get::<percent>()),
                     power_consumption_rate_watts: f64::from(battery.energy_rate().get::<watt>()),
                     health_percent: f64::from(battery.state_of_health().get::<percent>()),
+                    state: battery.state(),
                 })
             } else {
                 None
@@ -1,34 +1,344 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
 use sysinfo::{ProcessorExt, System, SystemExt};
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize)]
 pub struct CpuData {
+    #[serde(rename = "cpu_prefix")]
     pub cpu_prefix: String,
+    #[serde(rename = "cpu_count")]
     pub cpu_count: Option<usize>,
+    #[serde(rename = "cpu_usage")]
     pub cpu_usage: f64,
+    /// The current frequency, if the platform reports one.
+    #[serde(rename = "frequency_mhz")]
+    pub frequency_mhz: Option<u64>,
+    /// The NUMA node the core belongs to, if known.
+    #[serde(rename = "numa_node")]
+    pub numa_node: Option<u32>,
+    /// The share of the core's time spent waiting on I/O since the last collection, in percent.
+    #[serde(rename = "iowait_percent")]
+    pub iowait_percent: f64,
+    /// The share of the core's time taken by the hypervisor since the last collection, in percent.
+    #[serde(rename = "steal_percent")]
+    pub steal_percent: f64,
+    /// The share of the core's time spent servicing hardware interrupts, in percent.
+    #[serde(rename = "irq_percent")]
+    pub irq_percent: f64,
+    /// The share of the core's time spent servicing software interrupts, in percent.
+    #[serde(rename = "softirq_percent")]
+    pub softirq_percent: f64,
 }
 
 pub type CpuHarvest = Vec<CpuData>;
 
-pub fn get_cpu_data_list(sys: &System, show_average_cpu: bool) -> CpuHarvest {
+/// How the time of all CPUs was split since the last collection, in percent.
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct CpuBreakdownHarvest {
+    #[serde(rename = "user_percent")]
+    pub user_percent: f64,
+    #[serde(rename = "system_percent")]
+    pub system_percent: f64,
+    #[serde(rename = "irq_percent")]
+    pub irq_percent: f64,
+    #[serde(rename = "softirq_percent")]
+    pub softirq_percent: f64,
+    #[serde(rename = "iowait_percent")]
+    pub iowait_percent: f64,
+    #[serde(rename = "steal_percent")]
+    pub steal_percent: f64,
+    #[serde(rename = "idle_percent")]
+    pub idle_percent: f64,
+}
+
+/// The cumulative times of a CPU in each state, in clock ticks.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CpuTimes {
+    user: u64,
+    system: u64,
+    irq: u64,
+    softirq: u64,
+    iowait: u64,
+    steal: u64,
+    idle: u64,
+    total: u64,
+}
+
+/// Reads how CPU time was split from the "cpu" lines of `/proc/stat`, which look like
+/// "cpu0 user nice system idle iowait irq softirq steal guest guest_nice".  The first line, "cpu",
+/// is the total of all CPUs.  Splits are keyed by the name at the start of each line.
+/// `prev_times` holds the times from the last read, and is updated; if there are none yet, no
+/// split is given, as it would be taken over all time since boot.
+pub fn get_cpu_breakdowns(
+    prev_times: &mut HashMap<String, CpuTimes>,
+) -> HashMap<String, CpuBreakdownHarvest> {
+    let mut breakdowns = HashMap::new();
+    if !cfg!(target_os = "linux") {
+        return breakdowns;
+    }
+
+    let stat = match std::fs::read_to_string("/proc/stat") {
+        Ok(stat) => stat,
+        Err(_) => return breakdowns,
+    };
+
+    for line in stat.lines().filter(|line| line.starts_with("cpu")) {
+        let mut fields = line.split_whitespace();
+        let name = match fields.next() {
+            Some(name) => name,
+            None => continue,
+        };
+        let fields = match fields
+            .map(|field| field.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(fields) if fields.len() >= 8 => fields,
+            _ => continue,
+        };
+
+        // Guest time is already counted as user time, so it's left out of the total.
+        let times = CpuTimes {
+            user: fields[0] + fields[1],
+            system: fields[2],
+            irq: fields[5],
+            softirq: fields[6],
+            iowait: fields[4],
+            steal: fields[7],
+            idle: fields[3],
+            total: fields[..8].iter().sum(),
+        };
+        let prev = match prev_times.insert(name.to_string(), times) {
+            Some(prev) => prev,
+            None => continue,
+        };
+
+        let total = times.total.saturating_sub(prev.total);
+        let breakdown = if total == 0 {
+            CpuBreakdownHarvest::default()
+        } else {
+            let to_percent =
+                |now: u64, before: u64| now.saturating_sub(before) as f64 / total as f64 * 100.0;
+            CpuBreakdownHarvest {
+                user_percent: to_percent(times.user, prev.user),
+                system_percent: to_percent(times.system, prev.system),
+                irq_percent: to_percent(times.irq, prev.irq),
+                softirq_percent: to_percent(times.softirq, prev.softirq),
+                iowait_percent: to_percent(times.iowait, prev.iowait),
+                steal_percent: to_percent(times.steal, prev.steal),
+                idle_percent: to_percent(times.idle, prev.idle),
+            }
+        };
+        breakdowns.insert(name.to_string(), breakdown);
+    }
+
+    breakdowns
+}
+
+/// Fills in the I/O wait, steal, and interrupt time of each entry from the splits read by
+/// [`get_cpu_breakdowns`].  The average uses the split of all CPUs.
+pub fn set_core_breakdowns(
+    cpu_harvest: &mut [CpuData], breakdowns: &HashMap<String, CpuBreakdownHarvest>,
+) {
+    for cpu in cpu_harvest {
+        let name = match cpu.cpu_count {
+            Some(cpu_count) => format!("cpu{}", cpu_count),
+            None => "cpu".to_string(),
+        };
+        if let Some(breakdown) = breakdowns.get(&name) {
+            cpu.iowait_percent = breakdown.iowait_percent;
+            cpu.steal_percent = breakdown.steal_percent;
+            cpu.irq_percent = breakdown.irq_percent;
+            cpu.softirq_percent = breakdown.softirq_percent;
+        }
+    }
+}
+
+/// The 1, 5, and 15-minute load averages.
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct LoadAvgHarvest {
+    #[serde(rename = "one")]
+    pub one: f64,
+    #[serde(rename = "five")]
+    pub five: f64,
+    #[serde(rename = "fifteen")]
+    pub fifteen: f64,
+}
+
+/// Reads the load averages from `/proc/loadavg`, which starts with "0.52 0.58 0.59".
+#[cfg(target_os = "linux")]
+pub fn get_load_avg(_sys: &System) -> Option<LoadAvgHarvest> {
+    let loadavg = std::fs::read_to_string("/proc/loadavg").ok()?;
+    let mut fields = loadavg
+        .split_whitespace()
+        .map(|field| field.parse::<f64>().ok());
+
+    Some(LoadAvgHarvest {
+        one: fields.next()??,
+        five: fields.next()??,
+        fifteen: fields.next()??,
+    })
+}
+
+/// Windows has no load average (sysinfo just reports zeros), so there is nothing to show.
+#[cfg(target_os = "windows")]
+pub fn get_load_avg(_sys: &System) -> Option<LoadAvgHarvest> {
+    None
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn get_load_avg(sys: &System) -> Option<LoadAvgHarvest> {
+    let load_avg = sys.get_load_average();
+    Some(LoadAvgHarvest {
+        one: load_avg.one,
+        five: load_avg.five,
+        fifteen: load_avg.fifteen,
+    })
+}
+
+/// Reads the current frequency of each core.  These are read from sysfs, which reports them in
+/// kHz; if cpufreq isn't available (as in many VMs), the "cpu MHz" lines of `/proc/cpuinfo` are
+/// used instead.
+#[cfg(target_os = "linux")]
+fn get_frequencies_mhz(cpus: &[sysinfo::Processor]) -> Vec<Option<u64>> {
+    let mut cpuinfo_frequencies: Option<Vec<u64>> = None;
+
+    (0..cpus.len())
+        .map(|itx| {
+            std::fs::read_to_string(format!(
+                "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_cur_freq",
+                itx
+            ))
+            .ok()
+            .and_then(|frequency_khz| frequency_khz.trim().parse::<u64>().ok())
+            .map(|frequency_khz| frequency_khz / 1000)
+            .or_else(|| {
+                cpuinfo_frequencies
+                    .get_or_insert_with(get_cpuinfo_frequencies_mhz)
+                    .get(itx)
+                    .copied()
+            })
+        })
+        .collect()
+}
+
+/// Reads the frequency of each core from `/proc/cpuinfo`, which lists cores in order.
+#[cfg(target_os = "linux")]
+fn get_cpuinfo_frequencies_mhz() -> Vec<u64> {
+    std::fs::read_to_string("/proc/cpuinfo")
+        .map(|cpuinfo| {
+            cpuinfo
+                .lines()
+                .filter(|line| line.starts_with("cpu MHz"))
+                .filter_map(|line| line.split(':').nth(1)?.trim().parse::<f64>().ok())
+                .map(|frequency_mhz| frequency_mhz.round() as u64)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_frequencies_mhz(cpus: &[sysinfo::Processor]) -> Vec<Option<u64>> {
+    cpus.iter()
+        .map(|cpu| match cpu.get_frequency() {
+            0 => None,
+            frequency_mhz => Some(frequency_mhz),
+        })
+        .collect()
+}
+
+/// Maps each core to its NUMA node, as listed in `/sys/devices/system/node/node<N>/cpulist`.
+#[cfg(target_os = "linux")]
+pub fn get_numa_nodes() -> HashMap<usize, u32> {
+    let mut numa_nodes = HashMap::new();
+
+    if let Ok(entries) = std::fs::read_dir("/sys/devices/system/node") {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let node = match entry
+                .file_name()
+                .to_string_lossy()
+                .strip_prefix("node")
+                .and_then(|node| node.parse::<u32>().ok())
+            {
+                Some(node) => node,
+                None => continue,
+            };
+
+            // The list looks like "0-11,24-35".
+            if let Ok(cpulist) = std::fs::read_to_string(entry.path().join("cpulist")) {
+                for range in cpulist.trim().split(',').filter(|range| !range.is_empty()) {
+                    let mut bounds = range.splitn(2, '-').map(|bound| bound.parse::<usize>());
+                    if let Some(Ok(start)) = bounds.next() {
+                        let end = match bounds.next() {
+                            Some(Ok(end)) => end,
+                            Some(Err(_)) => continue,
+                            None => start,
+                        };
+                        for cpu in start..=end {
+                            numa_nodes.insert(cpu, node);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    numa_nodes
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_numa_nodes() -> HashMap<usize, u32> {
+    HashMap::new()
+}
+
+pub fn get_cpu_data_list(
+    sys: &System, show_average_cpu: bool, numa_nodes: &HashMap<usize, u32>,
+) -> CpuHarvest {
     let cpu_data = sys.get_processors();
     let avg_cpu_usage = sys.get_global_processor_info().get_cpu_usage();
+    let frequencies_mhz = get_frequencies_mhz(cpu_data);
     let mut cpu_vec = vec![];
 
-    if show_average_cpu {
-        cpu_vec.push(CpuData {
-            cpu_prefix: "AVG".to_string(),
-            cpu_count: None,
-            cpu_usage: avg_cpu_usage as f64,
-        });
-    }
-
-    for (itx, cpu) in cpu_data.iter().enumerate() {
+    for (itx, (cpu, frequency_mhz)) in cpu_data.iter().zip(frequencies_mhz).enumerate() {
         cpu_vec.push(CpuData {
             cpu_prefix: "CPU".to_string(),
             cpu_count: Some(itx),
             cpu_usage: f64::from(cpu.get_cpu_usage()),
+            frequency_mhz,
+            numa_node: numa_nodes.get(&itx).copied(),
+            iowait_percent: 0.0,
+            steal_percent: 0.0,
+            irq_percent: 0.0,
+            softirq_percent: 0.0,
         });
     }
 
+    if show_average_cpu {
+        let frequencies = cpu_vec
+            .iter()
+            .filter_map(|cpu| cpu.frequency_mhz)
+            .collect::<Vec<_>>();
+        let avg_frequency_mhz = if frequencies.is_empty() {
+            None
+        } else {
+            Some(frequencies.iter().sum::<u64>() / frequencies.len() as u64)
+        };
+
+        cpu_vec.insert(
+            0,
+            CpuData {
+                cpu_prefix: "AVG".to_string(),
+                cpu_count: None,
+                cpu_usage: avg_cpu_usage as f64,
+                frequency_mhz: avg_frequency_mhz,
+                numa_node: None,
+                iowait_percent: 0.0,
+                steal_percent: 0.0,
+                irq_percent: 0.0,
+                softirq_percent: 0.0,
+            },
+        );
+    }
+
     cpu_vec
 }
@@ -1,15 +1,38 @@
-#[derive(Debug, Clone, Default)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct DiskHarvest {
+    #[serde(rename = "name")]
     pub name: String,
+    #[serde(rename = "mount_point")]
     pub mount_point: String,
+    #[serde(rename = "free_space")]
     pub free_space: u64,
+    #[serde(rename = "used_space")]
     pub used_space: u64,
+    #[serde(rename = "total_space")]
     pub total_space: u64,
+    /// The read rate since the last collection, filled in when the data is stored.
+    #[serde(rename = "read_bytes_per_sec")]
+    pub read_bytes_per_sec: f64,
+    /// The write rate since the last collection, filled in when the data is stored.
+    #[serde(rename = "write_bytes_per_sec")]
+    pub write_bytes_per_sec: f64,
+}
+
+/// How the disk widget is sorted.  By default, disks are left in name order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiskSorting {
+    Name,
+    ReadRate,
+    WriteRate,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct IOData {
+    #[serde(rename = "read_bytes")]
     pub read_bytes: u64,
+    #[serde(rename = "write_bytes")]
     pub write_bytes: u64,
 }
 
@@ -50,6 +73,7 @@ pub async fn arm_disk_usage(
                 .get_total_space()
                 .saturating_sub(disk.get_available_space()),
             total_space: disk.get_total_space(),
+            ..DiskHarvest::default()
         })
         .collect::<Vec<DiskHarvest>>();
     vec_disks.sort_by(|a, b| a.name.cmp(&b.name));
@@ -135,6 +159,7 @@ pub async fn non_arm_disk_usage(
                     .to_str()
                     .unwrap_or("Name Unavailable"))
                 .to_string(),
+                ..DiskHarvest::default()
             });
         }
     }
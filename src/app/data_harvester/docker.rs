@@ -0,0 +1,105 @@
+//! Associates processes with Docker containers.  Containers are listed via the Docker API over
+//! its Unix socket, and processes are matched to them via their cgroups, so processes will only
+//! be associated on Linux.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::processes::ProcessHarvest;
+use crate::Pid;
+
+#[derive(Deserialize)]
+struct DockerContainer {
+    #[serde(rename = "Id")]
+    id: String,
+    #[serde(rename = "Names")]
+    names: Vec<String>,
+}
+
+/// Lists all containers as a mapping of container IDs to names, or `None` if the Docker API
+/// could not be reached.
+#[cfg(target_family = "unix")]
+pub fn get_containers(socket_path: &str) -> Option<HashMap<String, String>> {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::UnixStream,
+        time::Duration,
+    };
+
+    let mut stream = UnixStream::connect(socket_path).ok()?;
+    stream.set_read_timeout(Some(Duration::from_secs(1))).ok()?;
+    stream
+        .set_write_timeout(Some(Duration::from_secs(1)))
+        .ok()?;
+
+    // Use HTTP/1.0 so that the response isn't chunked, and the connection closes when it's done.
+    stream
+        .write_all(b"GET /containers/json?all=true HTTP/1.0\r\nHost: docker\r\n\r\n")
+        .ok()?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+
+    let body_start = response.find("\r\n\r\n")?;
+    let status_code = response
+        .lines()
+        .next()
+        .and_then(|status_line| status_line.split_whitespace().nth(1));
+    if status_code != Some("200") {
+        return None;
+    }
+
+    let containers: Vec<DockerContainer> =
+        serde_json::from_str(&response[body_start + 4..]).ok()?;
+    Some(
+        containers
+            .into_iter()
+            .map(|container| {
+                // Names are reported with a leading slash, like "/my_container".
+                let name = container
+                    .names
+                    .first()
+                    .map(|name| name.trim_start_matches('/').to_string())
+                    .unwrap_or_else(|| container.id.chars().take(12).collect());
+                (container.id, name)
+            })
+            .collect(),
+    )
+}
+
+#[cfg(not(target_family = "unix"))]
+pub fn get_containers(_socket_path: &str) -> Option<HashMap<String, String>> {
+    None
+}
+
+/// Fills in the container of each process that belongs to one of the given containers.
+pub fn associate_containers(
+    processes: &mut [ProcessHarvest], containers: &HashMap<String, String>,
+) {
+    if containers.is_empty() {
+        return;
+    }
+
+    for process in processes {
+        if let Some(container_id) = get_container_id(process.pid, containers) {
+            process.container_name = containers.get(&container_id).cloned();
+            process.container_id = Some(container_id);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn get_container_id(pid: Pid, containers: &HashMap<String, String>) -> Option<String> {
+    // Container IDs show up in cgroup paths as 64 hex characters, like "/docker/<id>" or
+    // "/system.slice/docker-<id>.scope".
+    let cgroups = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    cgroups
+        .split(|c: char| !c.is_ascii_hexdigit())
+        .find(|part| part.len() == 64 && containers.contains_key(*part))
+        .map(|container_id| container_id.to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn get_container_id(_pid: Pid, _containers: &HashMap<String, String>) -> Option<String> {
+    None
+}
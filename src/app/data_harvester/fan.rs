@@ -0,0 +1,79 @@
+//! Data collection for fans.  Fans are read from hwmon on Linux; sysinfo doesn't expose fan
+//! speeds, so other platforms report none for now.
+
+use serde::Serialize;
+
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct FanHarvest {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "rpm")]
+    pub rpm: u32,
+}
+
+/// Reads fans from `/sys/class/hwmon`.  Each fan is named after its chip and either its label or,
+/// if there is none, its index.
+#[cfg(target_os = "linux")]
+pub fn get_fan_data(actually_get: bool) -> Option<Vec<FanHarvest>> {
+    use std::fs;
+
+    if !actually_get {
+        return None;
+    }
+
+    let mut fan_vec: Vec<FanHarvest> = Vec::new();
+
+    if let Ok(hwmons) = fs::read_dir("/sys/class/hwmon") {
+        for hwmon in hwmons.filter_map(|hwmon| hwmon.ok()) {
+            let hwmon_path = hwmon.path();
+            let chip_name = fs::read_to_string(hwmon_path.join("name"))
+                .map(|name| name.trim().to_string())
+                .unwrap_or_else(|_| hwmon.file_name().to_string_lossy().to_string());
+
+            let entries = match fs::read_dir(&hwmon_path) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let file_name = entry.file_name();
+                let file_name = file_name.to_string_lossy();
+
+                // We're looking for files like "fan1_input".
+                let fan_prefix = match file_name.strip_suffix("_input") {
+                    Some(fan_prefix) if fan_prefix.starts_with("fan") => fan_prefix.to_string(),
+                    _ => continue,
+                };
+
+                let rpm = match fs::read_to_string(entry.path())
+                    .ok()
+                    .and_then(|rpm| rpm.trim().parse::<u32>().ok())
+                {
+                    Some(rpm) => rpm,
+                    None => continue,
+                };
+
+                let label = fs::read_to_string(hwmon_path.join(format!("{}_label", fan_prefix)))
+                    .map(|label| label.trim().to_string())
+                    .unwrap_or(fan_prefix);
+
+                fan_vec.push(FanHarvest {
+                    name: format!("{}: {}", chip_name, label),
+                    rpm,
+                });
+            }
+        }
+    }
+
+    fan_vec.sort_by(|a, b| a.name.cmp(&b.name));
+    Some(fan_vec)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_fan_data(actually_get: bool) -> Option<Vec<FanHarvest>> {
+    if actually_get {
+        Some(Vec::new())
+    } else {
+        None
+    }
+}
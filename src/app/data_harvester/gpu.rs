@@ -0,0 +1,119 @@
+//! Data collection for GPUs.  AMD GPUs are read via sysfs on Linux, while NVIDIA GPUs are read
+//! via NVML if bottom was built with the `nvidia` feature.
+
+use serde::Serialize;
+
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct GpuHarvest {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "utilization_percent")]
+    pub utilization_percent: f64,
+    #[serde(rename = "vram_used_bytes")]
+    pub vram_used_bytes: u64,
+    #[serde(rename = "vram_total_bytes")]
+    pub vram_total_bytes: u64,
+    /// Always in Celsius, like the sensors' temperatures.
+    #[serde(rename = "temperature")]
+    pub temperature: Option<f32>,
+}
+
+/// Reads AMD GPUs from `/sys/class/drm`.  Only cards that expose `gpu_busy_percent` (that is,
+/// those using the amdgpu driver) are returned.
+#[cfg(target_os = "linux")]
+pub fn amd_gpu_data() -> Vec<GpuHarvest> {
+    use std::{fs, path::Path};
+
+    fn read_value<T: std::str::FromStr>(path: &Path) -> Option<T> {
+        fs::read_to_string(path).ok()?.trim().parse::<T>().ok()
+    }
+
+    let mut gpu_vec: Vec<GpuHarvest> = Vec::new();
+
+    let entries = match fs::read_dir("/sys/class/drm") {
+        Ok(entries) => entries,
+        Err(_) => return gpu_vec,
+    };
+
+    let mut cards = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            // Skip connectors like "card0-DP-1".
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            file_name.starts_with("card") && file_name[4..].chars().all(|c| c.is_ascii_digit())
+        })
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    cards.sort();
+
+    for card in cards {
+        let device = card.join("device");
+        let utilization_percent = match read_value::<f64>(&device.join("gpu_busy_percent")) {
+            Some(utilization_percent) => utilization_percent,
+            None => continue,
+        };
+
+        let name = fs::read_to_string(device.join("product_name"))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| {
+                card.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            });
+
+        let temperature = fs::read_dir(device.join("hwmon"))
+            .ok()
+            .and_then(|mut hwmons| {
+                hwmons.find_map(|hwmon| read_value::<f32>(&hwmon.ok()?.path().join("temp1_input")))
+            })
+            .map(|millidegrees| millidegrees / 1000.0);
+
+        gpu_vec.push(GpuHarvest {
+            name,
+            utilization_percent,
+            vram_used_bytes: read_value(&device.join("mem_info_vram_used")).unwrap_or(0),
+            vram_total_bytes: read_value(&device.join("mem_info_vram_total")).unwrap_or(0),
+            temperature,
+        });
+    }
+
+    gpu_vec
+}
+
+/// Reads NVIDIA GPUs via NVML.
+#[cfg(feature = "nvidia")]
+pub fn nvidia_gpu_data(nvml: &nvml_wrapper::Nvml) -> Vec<GpuHarvest> {
+    use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+
+    let mut gpu_vec: Vec<GpuHarvest> = Vec::new();
+
+    if let Ok(device_count) = nvml.device_count() {
+        for index in 0..device_count {
+            if let Ok(device) = nvml.device_by_index(index) {
+                let (vram_used_bytes, vram_total_bytes) = match device.memory_info() {
+                    Ok(memory_info) => (memory_info.used, memory_info.total),
+                    Err(_) => (0, 0),
+                };
+
+                gpu_vec.push(GpuHarvest {
+                    name: device.name().unwrap_or_else(|_| format!("GPU {}", index)),
+                    utilization_percent: device
+                        .utilization_rates()
+                        .map(|utilization| utilization.gpu as f64)
+                        .unwrap_or(0.0),
+                    vram_used_bytes,
+                    vram_total_bytes,
+                    temperature: device
+                        .temperature(TemperatureSensor::Gpu)
+                        .ok()
+                        .map(|celsius| celsius as f32),
+                });
+            }
+        }
+    }
+
+    gpu_vec
+}
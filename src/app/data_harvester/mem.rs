@@ -1,7 +1,45 @@
-#[derive(Debug, Clone)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
 pub struct MemHarvest {
+    /// Like the other sizes here, in mebibytes.
+    #[serde(rename = "mem_total_in_mb")]
     pub mem_total_in_mb: u64,
+    /// On Linux, this is the total minus `MemAvailable`, so that reclaimable cache isn't counted.
+    #[serde(rename = "mem_used_in_mb")]
     pub mem_used_in_mb: u64,
+    /// Memory that isn't used for anything at all.  Only read on Linux.
+    #[serde(rename = "mem_free_in_mb")]
+    pub mem_free_in_mb: Option<u64>,
+    /// The kernel's estimate of how much memory could be used without swapping.  Only read on
+    /// Linux 3.14 and later.
+    #[serde(rename = "mem_available_in_mb")]
+    pub mem_available_in_mb: Option<u64>,
+    /// Memory used for buffers and the page cache, which the kernel can reclaim, so it isn't
+    /// counted as used.  Only read on Linux.
+    #[serde(rename = "cache_in_mb")]
+    pub cache_in_mb: Option<u64>,
+    /// For swap, how much data zram devices hold, and how much memory it takes up once
+    /// compressed.  Only read on Linux, if there are zram devices.
+    #[serde(rename = "zram_data_in_mb")]
+    pub zram_data_in_mb: Option<u64>,
+    #[serde(rename = "zram_compressed_in_mb")]
+    pub zram_compressed_in_mb: Option<u64>,
+    /// The number of pages in the huge page pool.  Only read on Linux.
+    #[serde(rename = "huge_pages_total")]
+    pub huge_pages_total: Option<u64>,
+    #[serde(rename = "huge_pages_free")]
+    pub huge_pages_free: Option<u64>,
+    #[serde(rename = "huge_page_size_kb")]
+    pub huge_page_size_kb: Option<u64>,
+    /// Anonymous memory backed by transparent huge pages.
+    #[serde(rename = "anon_huge_pages_kb")]
+    pub anon_huge_pages_kb: Option<u64>,
+    /// How fast pages are swapped in, in bytes per second.  Only read on Linux.
+    #[serde(rename = "swap_in_per_sec")]
+    pub swap_in_per_sec: f64,
+    #[serde(rename = "swap_out_per_sec")]
+    pub swap_out_per_sec: f64,
 }
 
 impl Default for MemHarvest {
@@ -9,10 +47,165 @@ impl Default for MemHarvest {
         MemHarvest {
             mem_total_in_mb: 0,
             mem_used_in_mb: 0,
+            mem_free_in_mb: None,
+            mem_available_in_mb: None,
+            cache_in_mb: None,
+            zram_data_in_mb: None,
+            zram_compressed_in_mb: None,
+            huge_pages_total: None,
+            huge_pages_free: None,
+            huge_page_size_kb: None,
+            anon_huge_pages_kb: None,
+            swap_in_per_sec: 0.0,
+            swap_out_per_sec: 0.0,
         }
     }
 }
 
+/// Fills in the used memory, cache, and huge page statistics from `/proc/meminfo`.
+#[cfg(target_os = "linux")]
+fn add_meminfo_data(mem_harvest: &mut MemHarvest) {
+    if let Ok(meminfo) = std::fs::read_to_string("/proc/meminfo") {
+        parse_meminfo(&meminfo, mem_harvest);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn add_meminfo_data(_mem_harvest: &mut MemHarvest) {}
+
+/// Fills in `mem_harvest` from the contents of a `/proc/meminfo` file.  Used memory is the total
+/// minus `MemAvailable`, or on kernels too old to report that, the total minus free memory and
+/// cache.  Fields that are missing are left as they were.
+pub fn parse_meminfo(meminfo: &str, mem_harvest: &mut MemHarvest) {
+    // Lines look like "HugePages_Total:       0" or "Hugepagesize:       2048 kB".
+    let get_value = |key: &str| {
+        meminfo.lines().find_map(|line| {
+            let mut fields = line.split_whitespace();
+            if fields.next()?.strip_suffix(':')? == key {
+                fields.next()?.parse::<u64>().ok()
+            } else {
+                None
+            }
+        })
+    };
+
+    // Like free(1), count reclaimable slab memory as cache too.
+    mem_harvest.cache_in_mb = match (get_value("Buffers"), get_value("Cached")) {
+        (Some(buffers), Some(cached)) => {
+            Some((buffers + cached + get_value("SReclaimable").unwrap_or(0)) / 1024)
+        }
+        _ => None,
+    };
+    mem_harvest.mem_free_in_mb = get_value("MemFree").map(|free| free / 1024);
+    mem_harvest.mem_available_in_mb = get_value("MemAvailable").map(|available| available / 1024);
+
+    if let Some(total_kb) = get_value("MemTotal") {
+        let unused_kb = match (get_value("MemAvailable"), get_value("MemFree")) {
+            (Some(available), _) => Some(available),
+            (None, Some(free)) => Some(free + mem_harvest.cache_in_mb.unwrap_or(0) * 1024),
+            (None, None) => None,
+        };
+        if let Some(unused_kb) = unused_kb {
+            mem_harvest.mem_total_in_mb = total_kb / 1024;
+            mem_harvest.mem_used_in_mb = total_kb.saturating_sub(unused_kb) / 1024;
+        }
+    }
+
+    mem_harvest.huge_pages_total = get_value("HugePages_Total");
+    mem_harvest.huge_pages_free = get_value("HugePages_Free");
+    mem_harvest.huge_page_size_kb = get_value("Hugepagesize");
+    mem_harvest.anon_huge_pages_kb = get_value("AnonHugePages");
+}
+
+/// Whether there are any zram devices.
+pub fn is_zram_present() -> bool {
+    cfg!(target_os = "linux")
+        && std::fs::read_dir("/sys/block")
+            .map(|devices| {
+                devices
+                    .flatten()
+                    .any(|device| device.file_name().to_string_lossy().starts_with("zram"))
+            })
+            .unwrap_or(false)
+}
+
+/// Fills in the zram statistics from each device's `mm_stat`, summed over all devices.  Devices
+/// that aren't used for swap are counted too.
+#[cfg(target_os = "linux")]
+pub fn add_zram_data(swap_harvest: &mut MemHarvest) {
+    let devices = match std::fs::read_dir("/sys/block") {
+        Ok(devices) => devices,
+        Err(_) => return,
+    };
+
+    let mut sizes: Option<(u64, u64)> = None;
+    for device in devices.flatten() {
+        if !device.file_name().to_string_lossy().starts_with("zram") {
+            continue;
+        }
+
+        // The first two columns are the original and compressed sizes of the data, in bytes.
+        if let Ok(mm_stat) = std::fs::read_to_string(device.path().join("mm_stat")) {
+            let mut fields = mm_stat
+                .split_whitespace()
+                .map(|field| field.parse::<u64>().ok());
+            if let (Some(Some(data)), Some(Some(compressed))) = (fields.next(), fields.next()) {
+                let (total_data, total_compressed) = sizes.unwrap_or((0, 0));
+                sizes = Some((total_data + data, total_compressed + compressed));
+            }
+        }
+    }
+
+    if let Some((data, compressed)) = sizes {
+        swap_harvest.zram_data_in_mb = Some(data / 1024 / 1024);
+        swap_harvest.zram_compressed_in_mb = Some(compressed / 1024 / 1024);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn add_zram_data(_swap_harvest: &mut MemHarvest) {}
+
+/// Fills in the swap-in and swap-out rates from the page counts in `/proc/vmstat`.
+/// `prev_swap_counts` holds the pages swapped in and out as of `last_update`, and is updated; if
+/// there are none yet, the rates are left at zero.
+#[cfg(target_os = "linux")]
+pub fn add_swap_io_rates(
+    swap_harvest: &mut MemHarvest, prev_swap_counts: &mut Option<(u64, u64)>, page_size_kb: u64,
+    last_update: std::time::Instant, current_instant: std::time::Instant,
+) {
+    let vmstat = match std::fs::read_to_string("/proc/vmstat") {
+        Ok(vmstat) => vmstat,
+        Err(_) => return,
+    };
+
+    // Lines look like "pswpin 1234".
+    let get_value = |key: &str| {
+        vmstat.lines().find_map(|line| {
+            let mut fields = line.split_whitespace();
+            if fields.next()? == key {
+                fields.next()?.parse::<u64>().ok()
+            } else {
+                None
+            }
+        })
+    };
+
+    if let (Some(pages_in), Some(pages_out)) = (get_value("pswpin"), get_value("pswpout")) {
+        let elapsed_secs = current_instant.duration_since(last_update).as_secs_f64();
+        if let Some((prev_pages_in, prev_pages_out)) = *prev_swap_counts {
+            if elapsed_secs > 0.0 {
+                let page_size_bytes = (page_size_kb * 1024) as f64;
+                swap_harvest.swap_in_per_sec =
+                    pages_in.saturating_sub(prev_pages_in) as f64 * page_size_bytes / elapsed_secs;
+                swap_harvest.swap_out_per_sec = pages_out.saturating_sub(prev_pages_out) as f64
+                    * page_size_bytes
+                    / elapsed_secs;
+            }
+        }
+        *prev_swap_counts = Some((pages_in, pages_out));
+    }
+}
+
 /// Meant for ARM use.
 #[cfg(any(target_arch = "aarch64", target_arch = "arm"))]
 pub async fn arm_mem_data(
@@ -23,10 +216,14 @@ pub async fn arm_mem_data(
         return Ok(None);
     }
 
-    Ok(Some(MemHarvest {
+    let mut mem_harvest = MemHarvest {
         mem_total_in_mb: sys.get_total_memory() / 1024,
         mem_used_in_mb: sys.get_used_memory() / 1024,
-    }))
+        ..MemHarvest::default()
+    };
+    add_meminfo_data(&mut mem_harvest);
+
+    Ok(Some(mem_harvest))
 }
 
 /// Meant for ARM use.
@@ -42,6 +239,7 @@ pub async fn arm_swap_data(
     Ok(Some(MemHarvest {
         mem_total_in_mb: sys.get_total_swap() / 1024,
         mem_used_in_mb: sys.get_used_swap() / 1024,
+        ..MemHarvest::default()
     }))
 }
 
@@ -55,13 +253,17 @@ pub async fn non_arm_mem_data(
 
     let memory = heim::memory::memory().await?;
 
-    Ok(Some(MemHarvest {
-        mem_total_in_mb: memory.total().get::<heim::units::information::megabyte>(),
-        mem_used_in_mb: memory.total().get::<heim::units::information::megabyte>()
+    let mut mem_harvest = MemHarvest {
+        mem_total_in_mb: memory.total().get::<heim::units::information::mebibyte>(),
+        mem_used_in_mb: memory.total().get::<heim::units::information::mebibyte>()
             - memory
                 .available()
-                .get::<heim::units::information::megabyte>(),
-    }))
+                .get::<heim::units::information::mebibyte>(),
+        ..MemHarvest::default()
+    };
+    add_meminfo_data(&mut mem_harvest);
+
+    Ok(Some(mem_harvest))
 }
 
 #[cfg(not(any(target_arch = "aarch64", target_arch = "arm")))]
@@ -75,7 +277,8 @@ pub async fn non_arm_swap_data(
     let memory = heim::memory::swap().await?;
 
     Ok(Some(MemHarvest {
-        mem_total_in_mb: memory.total().get::<heim::units::information::megabyte>(),
-        mem_used_in_mb: memory.used().get::<heim::units::information::megabyte>(),
+        mem_total_in_mb: memory.total().get::<heim::units::information::mebibyte>(),
+        mem_used_in_mb: memory.used().get::<heim::units::information::mebibyte>(),
+        ..MemHarvest::default()
     }))
 }
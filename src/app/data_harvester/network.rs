@@ -1,17 +1,56 @@
+use globset::GlobSet;
+use serde::Serialize;
 use std::time::Instant;
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, Serialize)]
 pub struct NetworkHarvest {
+    #[serde(rename = "rx")]
     pub rx: u64,
+    #[serde(rename = "tx")]
     pub tx: u64,
+    #[serde(rename = "total_rx")]
     pub total_rx: u64,
+    #[serde(rename = "total_tx")]
     pub total_tx: u64,
+    #[serde(rename = "rx_packets_per_sec")]
+    pub rx_packets_per_sec: f64,
+    #[serde(rename = "tx_packets_per_sec")]
+    pub tx_packets_per_sec: f64,
 }
 
 impl NetworkHarvest {
     pub fn first_run_cleanup(&mut self) {
         self.rx = 0;
         self.tx = 0;
+        self.rx_packets_per_sec = 0.0;
+        self.tx_packets_per_sec = 0.0;
+    }
+}
+
+/// Returns the received and transmitted packets per second since the last collection, given the
+/// current packet totals.  `prev_packets` holds the totals from the last collection, and is
+/// updated.
+fn get_packet_rates(
+    total_packets: (u64, u64), prev_packets: &mut (u64, u64), elapsed_time: f64,
+) -> (f64, f64) {
+    let rates = if elapsed_time == 0.0 {
+        (0.0, 0.0)
+    } else {
+        (
+            total_packets.0.saturating_sub(prev_packets.0) as f64 / elapsed_time,
+            total_packets.1.saturating_sub(prev_packets.1) as f64 / elapsed_time,
+        )
+    };
+
+    *prev_packets = total_packets;
+    rates
+}
+
+/// Whether an interface should be skipped due to the network filter.
+fn is_filtered_out(interface: &str, filter: &Option<GlobSet>) -> bool {
+    match filter {
+        Some(filter) => filter.is_match(interface),
+        None => false,
     }
 }
 
@@ -19,7 +58,8 @@ impl NetworkHarvest {
 #[cfg(any(target_os = "windows", target_arch = "aarch64", target_arch = "arm"))]
 pub async fn arm_or_windows_network_data(
     sys: &sysinfo::System, prev_net_access_time: Instant, prev_net_rx: &mut u64,
-    prev_net_tx: &mut u64, curr_time: Instant, actually_get: bool,
+    prev_net_tx: &mut u64, prev_net_packets: &mut (u64, u64), curr_time: Instant,
+    actually_get: bool, filter: &Option<GlobSet>,
 ) -> Option<NetworkHarvest> {
     use sysinfo::{NetworkExt, SystemExt};
 
@@ -29,11 +69,18 @@ pub async fn arm_or_windows_network_data(
 
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut total_rx_packets: u64 = 0;
+    let mut total_tx_packets: u64 = 0;
 
     let networks = sys.get_networks();
-    for (_, network) in networks {
+    for (name, network) in networks {
+        if is_filtered_out(name, filter) {
+            continue;
+        }
         total_rx += network.get_total_received();
         total_tx += network.get_total_transmitted();
+        total_rx_packets += network.get_total_packets_received();
+        total_tx_packets += network.get_total_packets_transmitted();
     }
 
     let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
@@ -47,6 +94,12 @@ pub async fn arm_or_windows_network_data(
         )
     };
 
+    let (rx_packets_per_sec, tx_packets_per_sec) = get_packet_rates(
+        (total_rx_packets, total_tx_packets),
+        prev_net_packets,
+        elapsed_time,
+    );
+
     *prev_net_rx = total_rx;
     *prev_net_tx = total_tx;
     Some(NetworkHarvest {
@@ -54,13 +107,16 @@ pub async fn arm_or_windows_network_data(
         tx,
         total_rx,
         total_tx,
+        rx_packets_per_sec,
+        tx_packets_per_sec,
     })
 }
 
 #[cfg(not(any(target_os = "windows", target_arch = "aarch64", target_arch = "arm")))]
 pub async fn non_arm_or_windows_network_data(
     prev_net_access_time: Instant, prev_net_rx: &mut u64, prev_net_tx: &mut u64,
-    curr_time: Instant, actually_get: bool,
+    prev_net_packets: &mut (u64, u64), curr_time: Instant, actually_get: bool,
+    filter: &Option<GlobSet>,
 ) -> Option<NetworkHarvest> {
     use futures::StreamExt;
 
@@ -71,11 +127,18 @@ pub async fn non_arm_or_windows_network_data(
     let mut io_data = heim::net::io_counters();
     let mut total_rx: u64 = 0;
     let mut total_tx: u64 = 0;
+    let mut total_rx_packets: u64 = 0;
+    let mut total_tx_packets: u64 = 0;
 
     while let Some(io) = io_data.next().await {
         if let Ok(io) = io {
+            if is_filtered_out(io.interface(), filter) {
+                continue;
+            }
             total_rx += io.bytes_recv().get::<heim::units::information::byte>();
             total_tx += io.bytes_sent().get::<heim::units::information::byte>();
+            total_rx_packets += io.packets_recv();
+            total_tx_packets += io.packets_sent();
         }
     }
 
@@ -90,6 +153,12 @@ pub async fn non_arm_or_windows_network_data(
         )
     };
 
+    let (rx_packets_per_sec, tx_packets_per_sec) = get_packet_rates(
+        (total_rx_packets, total_tx_packets),
+        prev_net_packets,
+        elapsed_time,
+    );
+
     *prev_net_rx = total_rx;
     *prev_net_tx = total_tx;
     Some(NetworkHarvest {
@@ -97,5 +166,7 @@ pub async fn non_arm_or_windows_network_data(
         tx,
         total_rx,
         total_tx,
+        rx_packets_per_sec,
+        tx_packets_per_sec,
     })
 }
@@ -1,4 +1,5 @@
 use crate::Pid;
+use serde::Serialize;
 use std::path::PathBuf;
 use sysinfo::ProcessStatus;
 
@@ -17,6 +18,9 @@ pub enum ProcessSorting {
     CpuPercent,
     Mem,
     MemPercent,
+    CpuTime,
+    StartTime,
+    Age,
     Pid,
     ProcessName,
     Command,
@@ -24,7 +28,12 @@ pub enum ProcessSorting {
     WritePerSecond,
     TotalRead,
     TotalWrite,
+    FdCount,
+    ThreadCount,
+    Nice,
+    User,
     State,
+    Container,
     Count,
 }
 
@@ -38,11 +47,19 @@ impl std::fmt::Display for ProcessSorting {
                 CpuPercent => "CPU%",
                 MemPercent => "Mem%",
                 Mem => "Mem",
+                CpuTime => "CPU Time",
+                StartTime => "Started",
+                Age => "Age",
                 ReadPerSecond => "R/s",
                 WritePerSecond => "W/s",
                 TotalRead => "T.Read",
                 TotalWrite => "T.Write",
+                FdCount => "FDs",
+                ThreadCount => "Thrds",
+                Nice => "Nice",
+                User => "User",
                 State => "State",
+                Container => "Container",
                 ProcessName => "Name",
                 Command => "Command",
                 Pid => "PID",
@@ -58,23 +75,67 @@ impl Default for ProcessSorting {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ProcessHarvest {
+    #[serde(rename = "pid")]
     pub pid: Pid,
+    #[serde(rename = "parent_pid")]
     pub parent_pid: Option<Pid>, // Remember, parent_pid 0 is root...
+    #[serde(rename = "cpu_usage_percent")]
     pub cpu_usage_percent: f64,
+    #[serde(rename = "mem_usage_percent")]
     pub mem_usage_percent: f64,
+    #[serde(rename = "mem_usage_bytes")]
     pub mem_usage_bytes: u64,
+    /// The total CPU time used by the process in both user and kernel mode, in seconds, if it
+    /// could be read.
+    #[serde(rename = "total_cpu_time")]
+    pub total_cpu_time: Option<f64>,
+    /// When the process started, in seconds since the Unix epoch, if it could be read.
+    #[serde(rename = "start_time_secs")]
+    pub start_time_secs: Option<u64>,
     // pub rss_kb: u64,
     // pub virt_kb: u64,
+    #[serde(rename = "name")]
     pub name: String,
+    #[serde(rename = "command")]
     pub command: String,
+    /// The full command line, which is only read when it is looked at.
+    #[serde(rename = "cmdline")]
+    pub cmdline: Option<String>,
+    /// The environment variables, which are only read when they are looked at.
+    #[serde(rename = "envs")]
+    pub envs: Option<Vec<(String, String)>>,
+    #[serde(rename = "read_bytes_per_sec")]
     pub read_bytes_per_sec: u64,
+    #[serde(rename = "write_bytes_per_sec")]
     pub write_bytes_per_sec: u64,
+    #[serde(rename = "total_read_bytes")]
     pub total_read_bytes: u64,
+    #[serde(rename = "total_write_bytes")]
     pub total_write_bytes: u64,
+    /// Whether the I/O usage could be read at all; this may fail if permission is denied.
+    #[serde(rename = "is_io_readable")]
+    pub is_io_readable: bool,
+    /// The number of open file descriptors, if they could be counted.
+    #[serde(rename = "fd_count")]
+    pub fd_count: Option<u32>,
+    /// The number of threads, if it could be read.
+    #[serde(rename = "thread_count")]
+    pub thread_count: Option<u64>,
+    /// The nice value, if it could be read.
+    #[serde(rename = "nice")]
+    pub nice: Option<i32>,
+    #[serde(rename = "process_state")]
     pub process_state: String,
+    #[serde(rename = "process_state_char")]
     pub process_state_char: char,
+    #[serde(rename = "user")]
+    pub user: String,
+    #[serde(rename = "container_id")]
+    pub container_id: Option<String>,
+    #[serde(rename = "container_name")]
+    pub container_name: Option<String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -82,11 +143,13 @@ pub struct PrevProcDetails {
     pub total_read_bytes: u64,
     pub total_write_bytes: u64,
     pub cpu_time: f64,
+    pub proc_dir_path: PathBuf,
     pub proc_stat_path: PathBuf,
     // pub proc_statm_path: PathBuf,
     pub proc_exe_path: PathBuf,
     pub proc_io_path: PathBuf,
     pub proc_cmdline_path: PathBuf,
+    pub proc_fd_path: PathBuf,
     pub just_read: bool,
 }
 
@@ -95,9 +158,11 @@ impl PrevProcDetails {
         PrevProcDetails {
             proc_io_path: PathBuf::from(format!("/proc/{}/io", pid)),
             proc_exe_path: PathBuf::from(format!("/proc/{}/exe", pid)),
+            proc_dir_path: PathBuf::from(format!("/proc/{}", pid)),
             proc_stat_path: PathBuf::from(format!("/proc/{}/stat", pid)),
             // proc_statm_path: PathBuf::from(format!("/proc/{}/statm", pid)),
             proc_cmdline_path: PathBuf::from(format!("/proc/{}/cmdline", pid)),
+            proc_fd_path: PathBuf::from(format!("/proc/{}/fd", pid)),
             ..PrevProcDetails::default()
         }
     }
@@ -172,6 +237,16 @@ fn cpu_usage_calculation(
     Ok((result, cpu_percentage))
 }
 
+/// Reads when the system booted, in seconds since the Unix epoch.
+#[cfg(target_os = "linux")]
+fn get_boot_time() -> Option<u64> {
+    std::fs::read_to_string("/proc/stat")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))
+        .and_then(|btime| btime.trim().parse::<u64>().ok())
+}
+
 #[cfg(target_os = "linux")]
 fn get_process_io(path: &PathBuf) -> std::io::Result<String> {
     Ok(std::fs::read_to_string(path)?)
@@ -245,7 +320,8 @@ fn get_linux_cpu_usage(
 fn read_proc<S: core::hash::BuildHasher>(
     pid: Pid, cpu_usage: f64, cpu_fraction: f64,
     pid_mapping: &mut HashMap<Pid, PrevProcDetails, S>, use_current_cpu_total: bool,
-    time_difference_in_secs: u64, mem_total_kb: u64, page_file_kb: u64,
+    time_difference_in_secs: u64, mem_total_kb: u64, page_file_kb: u64, clock_ticks_per_sec: f64,
+    boot_time: Option<u64>, user_table: &users::UsersCache, hide_kernel_threads: bool,
 ) -> error::Result<ProcessHarvest> {
     let pid_stat = pid_mapping
         .entry(pid)
@@ -261,13 +337,12 @@ fn read_proc<S: core::hash::BuildHasher>(
         .last()
         .ok_or(BottomError::MinorError)?
         .to_string();
-    let command = {
-        let cmd = read_path_contents(&pid_stat.proc_cmdline_path)?;
-        if cmd.trim().is_empty() {
-            format!("[{}]", name)
-        } else {
-            cmd
-        }
+    let cmd = read_path_contents(&pid_stat.proc_cmdline_path)?;
+    let is_cmd_empty = cmd.trim().is_empty();
+    let command = if is_cmd_empty {
+        format!("[{}]", name)
+    } else {
+        cmd
     };
     let stat = stat_results
         .split(')')
@@ -276,6 +351,11 @@ fn read_proc<S: core::hash::BuildHasher>(
         .ok_or(BottomError::MinorError)?
         .split_whitespace()
         .collect::<Vec<&str>>();
+    // The process may have exited while we were reading it; skip it rather than indexing past
+    // the end of whatever was left.
+    if stat.len() < 22 {
+        return Err(BottomError::MinorError);
+    }
     let (process_state_char, process_state) = get_linux_process_state(&stat);
     let cpu_usage_percent = get_linux_cpu_usage(
         &stat,
@@ -284,69 +364,122 @@ fn read_proc<S: core::hash::BuildHasher>(
         &mut pid_stat.cpu_time,
         use_current_cpu_total,
     )?;
+    // utime + stime, in clock ticks.
+    let total_cpu_time = if clock_ticks_per_sec > 0.0 {
+        Some(
+            (stat[11].parse::<f64>().unwrap_or(0.0) + stat[12].parse::<f64>().unwrap_or(0.0))
+                / clock_ticks_per_sec,
+        )
+    } else {
+        None
+    };
+    // The start time is in clock ticks since boot.
+    let start_time_secs = match (boot_time, stat[19].parse::<f64>()) {
+        (Some(boot_time), Ok(start_ticks)) if clock_ticks_per_sec > 0.0 => {
+            Some(boot_time + (start_ticks / clock_ticks_per_sec) as u64)
+        }
+        _ => None,
+    };
     let parent_pid = stat[1].parse::<Pid>().ok();
+    let thread_count = stat[17].parse::<u64>().ok();
+    let nice = stat[16].parse::<i32>().ok();
+
+    // Kernel threads are all children of kthreadd (PID 2), and have no command line.
+    if hide_kernel_threads && (pid == 2 || parent_pid == Some(2) || is_cmd_empty) {
+        return Err(BottomError::MinorError);
+    }
+
     let (_vsize, rss) = get_linux_process_vsize_rss(&stat);
     let mem_usage_kb = rss * page_file_kb;
     let mem_usage_percent = mem_usage_kb as f64 / mem_total_kb as f64 * 100.0;
     let mem_usage_bytes = mem_usage_kb * 1024;
+    // This can also fail if permission is denied.
+    let fd_count = std::fs::read_dir(&pid_stat.proc_fd_path)
+        .ok()
+        .map(|fds| fds.count() as u32);
+    let user = {
+        use std::os::unix::fs::MetadataExt;
+        let uid = std::fs::metadata(&pid_stat.proc_dir_path)?.uid();
+        get_username(uid, user_table)
+    };
 
     // This can fail if permission is denied!
-    let (total_read_bytes, total_write_bytes, read_bytes_per_sec, write_bytes_per_sec) =
-        if let Ok(io_results) = get_process_io(&pid_stat.proc_io_path) {
-            let io_stats = io_results.split_whitespace().collect::<Vec<&str>>();
-
-            let (total_read_bytes, total_write_bytes) = get_linux_process_io_usage(&io_stats);
-            let read_bytes_per_sec = if time_difference_in_secs == 0 {
-                0
-            } else {
-                total_read_bytes.saturating_sub(pid_stat.total_read_bytes) / time_difference_in_secs
-            };
-            let write_bytes_per_sec = if time_difference_in_secs == 0 {
-                0
-            } else {
-                total_write_bytes.saturating_sub(pid_stat.total_write_bytes)
-                    / time_difference_in_secs
-            };
-
-            pid_stat.total_read_bytes = total_read_bytes;
-            pid_stat.total_write_bytes = total_write_bytes;
+    let (
+        total_read_bytes,
+        total_write_bytes,
+        read_bytes_per_sec,
+        write_bytes_per_sec,
+        is_io_readable,
+    ) = if let Ok(io_results) = get_process_io(&pid_stat.proc_io_path) {
+        let io_stats = io_results.split_whitespace().collect::<Vec<&str>>();
 
-            (
-                total_read_bytes,
-                total_write_bytes,
-                read_bytes_per_sec,
-                write_bytes_per_sec,
-            )
+        let (total_read_bytes, total_write_bytes) = get_linux_process_io_usage(&io_stats);
+        let read_bytes_per_sec = if time_difference_in_secs == 0 {
+            0
         } else {
-            (0, 0, 0, 0)
+            total_read_bytes.saturating_sub(pid_stat.total_read_bytes) / time_difference_in_secs
         };
+        let write_bytes_per_sec = if time_difference_in_secs == 0 {
+            0
+        } else {
+            total_write_bytes.saturating_sub(pid_stat.total_write_bytes) / time_difference_in_secs
+        };
+
+        pid_stat.total_read_bytes = total_read_bytes;
+        pid_stat.total_write_bytes = total_write_bytes;
+
+        (
+            total_read_bytes,
+            total_write_bytes,
+            read_bytes_per_sec,
+            write_bytes_per_sec,
+            true,
+        )
+    } else {
+        (0, 0, 0, 0, false)
+    };
 
     Ok(ProcessHarvest {
         pid,
         parent_pid,
         name,
         command,
+        cmdline: None,
+        envs: None,
         mem_usage_percent,
         mem_usage_bytes,
+        total_cpu_time,
+        start_time_secs,
         cpu_usage_percent,
         total_read_bytes,
         total_write_bytes,
         read_bytes_per_sec,
         write_bytes_per_sec,
+        is_io_readable,
+        fd_count,
+        thread_count,
+        nice,
         process_state,
         process_state_char,
+        user,
+        container_id: None,
+        container_name: None,
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 #[cfg(target_os = "linux")]
 pub fn linux_processes(
     prev_idle: &mut f64, prev_non_idle: &mut f64,
     pid_mapping: &mut HashMap<Pid, PrevProcDetails, RandomState>, use_current_cpu_total: bool,
     time_difference_in_secs: u64, mem_total_kb: u64, page_file_kb: u64,
+    user_table: &users::UsersCache, hide_kernel_threads: bool,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     // TODO: [PROC THREADS] Add threads
 
     if let Ok((cpu_usage, cpu_fraction)) = cpu_usage_calculation(prev_idle, prev_non_idle) {
+        let clock_ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) } as f64;
+        let boot_time = get_boot_time();
         let process_vector: Vec<ProcessHarvest> = std::fs::read_dir("/proc")?
             .filter_map(|dir| {
                 if let Ok(dir) = dir {
@@ -362,6 +495,10 @@ pub fn linux_processes(
                             time_difference_in_secs,
                             mem_total_kb,
                             page_file_kb,
+                            clock_ticks_per_sec,
+                            boot_time,
+                            user_table,
+                            hide_kernel_threads,
                         ) {
                             return Some(process_object);
                         }
@@ -381,6 +518,7 @@ pub fn linux_processes(
 #[cfg(not(target_os = "linux"))]
 pub fn windows_macos_processes(
     sys: &System, use_current_cpu_total: bool, mem_total_kb: u64,
+    #[cfg(target_family = "unix")] user_table: &users::UsersCache,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     let mut process_vector: Vec<ProcessHarvest> = Vec::new();
     let process_hashmap = sys.get_processes();
@@ -434,25 +572,489 @@ pub fn windows_macos_processes(
             parent_pid: process_val.parent(),
             name,
             command,
+            cmdline: None,
+            envs: None,
             mem_usage_percent: if mem_total_kb > 0 {
                 process_val.memory() as f64 * 100.0 / mem_total_kb as f64
             } else {
                 0.0
             },
             mem_usage_bytes: process_val.memory() * 1024,
+            total_cpu_time: None,
+            start_time_secs: Some(process_val.start_time()),
             cpu_usage_percent: process_cpu_usage,
             read_bytes_per_sec: disk_usage.read_bytes,
             write_bytes_per_sec: disk_usage.written_bytes,
             total_read_bytes: disk_usage.total_read_bytes,
             total_write_bytes: disk_usage.total_written_bytes,
+            is_io_readable: true,
+            fd_count: None,
+            thread_count: None,
+            nice: None,
             process_state: process_val.status().to_string().to_string(),
             process_state_char: convert_process_status_to_char(process_val.status()),
+            #[cfg(target_family = "unix")]
+            user: get_username(process_val.uid, user_table),
+            #[cfg(not(target_family = "unix"))]
+            user: String::default(),
+            container_id: None,
+            container_name: None,
         });
     }
 
     Ok(process_vector)
 }
 
+/// Reads the environment variables of a process.  This is only done on demand, as it is far too
+/// expensive to do for every process on every update.
+pub fn get_process_environment(pid: Pid) -> crate::utils::error::Result<Vec<(String, String)>> {
+    #[cfg(target_os = "linux")]
+    {
+        let environ = std::fs::read(format!("/proc/{}/environ", pid))?;
+
+        // Each variable is a null-terminated "KEY=value" pair.
+        Ok(environ
+            .split(|byte| *byte == 0)
+            .filter(|variable| !variable.is_empty())
+            .map(|variable| {
+                let variable = String::from_utf8_lossy(variable);
+                let mut split = variable.splitn(2, '=');
+                (
+                    split.next().unwrap_or_default().to_string(),
+                    split.next().unwrap_or_default().to_string(),
+                )
+            })
+            .collect())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err(crate::utils::error::BottomError::GenericError(format!(
+            "reading the environment of PID {} is only supported on Linux",
+            pid
+        )))
+    }
+}
+
+/// Reads the full command line of a process, with its arguments separated by spaces.
+pub fn get_process_cmdline(pid: Pid) -> crate::utils::error::Result<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let cmdline = std::fs::read(format!("/proc/{}/cmdline", pid))?;
+
+        // Arguments are null-separated.
+        Ok(cmdline
+            .split(|byte| *byte == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).to_string())
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let mut sys = System::new();
+        if !sys.refresh_process(pid) {
+            return Err(crate::utils::error::BottomError::GenericError(format!(
+                "PID {} does not exist",
+                pid
+            )));
+        }
+
+        sys.get_process(pid)
+            .map(|process| process.cmd().join(" "))
+            .ok_or_else(|| {
+                crate::utils::error::BottomError::GenericError(format!(
+                    "PID {} does not exist",
+                    pid
+                ))
+            })
+    }
+}
+
+/// Details about a single process, shown in the process details dialog.  Anything that could
+/// not be read on this platform is `None`.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessDetails {
+    pub name: String,
+    pub command: String,
+    /// When the process started, in seconds since the Unix epoch.
+    pub start_time: Option<u64>,
+    /// The total CPU time used by the process in both user and kernel mode, in seconds.
+    pub cpu_time_secs: Option<f64>,
+    pub rss_bytes: Option<u64>,
+    pub virtual_bytes: Option<u64>,
+    pub thread_count: Option<u64>,
+    pub fd_count: Option<usize>,
+    pub cgroup: Option<String>,
+    /// The cores the process is allowed to run on.
+    pub affinity: Option<Vec<usize>>,
+}
+
+/// Reads which cores a process is allowed to run on.
+#[cfg(target_os = "linux")]
+fn get_affinity(pid: Pid) -> Option<Vec<usize>> {
+    let cpu_set_size = std::mem::size_of::<libc::cpu_set_t>();
+    let mut cpu_set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    if unsafe { libc::sched_getaffinity(pid as libc::pid_t, cpu_set_size, &mut cpu_set) } != 0 {
+        return None;
+    }
+
+    Some(
+        (0..cpu_set_size * 8)
+            .filter(|cpu| unsafe { libc::CPU_ISSET(*cpu, &cpu_set) })
+            .collect(),
+    )
+}
+
+/// Reads the details of a single process.  Like the environment, this is only done on demand for
+/// the one process being looked at.
+pub fn get_process_details(pid: Pid) -> crate::utils::error::Result<ProcessDetails> {
+    #[cfg(target_os = "linux")]
+    {
+        let stat_results = std::fs::read_to_string(format!("/proc/{}/stat", pid))?;
+
+        // The name is wrapped in brackets, and may itself contain spaces and brackets.
+        let (name, stat) = match (stat_results.find('('), stat_results.rfind(')')) {
+            (Some(name_start), Some(name_end)) if name_start < name_end => (
+                stat_results[name_start + 1..name_end].to_string(),
+                stat_results[name_end + 1..]
+                    .split_whitespace()
+                    .collect::<Vec<_>>(),
+            ),
+            _ => return Err(BottomError::MinorError),
+        };
+        // Like elsewhere, the offset is because we cut off the PID and name.
+        if stat.len() < 22 {
+            return Err(BottomError::MinorError);
+        }
+
+        let clock_ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+
+        let (cpu_time_secs, start_time) = if clock_ticks_per_sec > 0 {
+            let clock_ticks_per_sec = clock_ticks_per_sec as f64;
+            let cpu_ticks =
+                stat[11].parse::<f64>().unwrap_or(0.0) + stat[12].parse::<f64>().unwrap_or(0.0);
+            let start_time = match (get_boot_time(), stat[19].parse::<f64>()) {
+                (Some(boot_time), Ok(start_ticks)) => {
+                    Some(boot_time + (start_ticks / clock_ticks_per_sec) as u64)
+                }
+                _ => None,
+            };
+
+            (Some(cpu_ticks / clock_ticks_per_sec), start_time)
+        } else {
+            (None, None)
+        };
+
+        // Arguments are null-separated.  Kernel threads have no command line, so just use the name.
+        let command = std::fs::read(format!("/proc/{}/cmdline", pid))
+            .map(|cmdline| {
+                cmdline
+                    .split(|byte| *byte == 0)
+                    .filter(|arg| !arg.is_empty())
+                    .map(|arg| String::from_utf8_lossy(arg).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        let command = if command.is_empty() {
+            format!("[{}]", name)
+        } else {
+            command
+        };
+
+        // Each line is "hierarchy-ID:controllers:path"; with cgroups v2 there is only one.
+        let cgroup = std::fs::read_to_string(format!("/proc/{}/cgroup", pid))
+            .ok()
+            .and_then(|cgroups| {
+                let mut paths = Vec::new();
+                for path in cgroups
+                    .lines()
+                    .filter_map(|line| line.splitn(3, ':').nth(2))
+                {
+                    if !paths.contains(&path) {
+                        paths.push(path);
+                    }
+                }
+                if paths.is_empty() {
+                    None
+                } else {
+                    Some(paths.join(", "))
+                }
+            });
+
+        Ok(ProcessDetails {
+            name,
+            command,
+            start_time,
+            cpu_time_secs,
+            rss_bytes: stat[21]
+                .parse::<u64>()
+                .ok()
+                .filter(|_| page_size > 0)
+                .map(|rss_pages| rss_pages * page_size as u64),
+            virtual_bytes: stat[20].parse::<u64>().ok(),
+            thread_count: stat[17].parse::<u64>().ok(),
+            fd_count: std::fs::read_dir(format!("/proc/{}/fd", pid))
+                .ok()
+                .map(|fds| fds.count()),
+            cgroup,
+            affinity: get_affinity(pid),
+        })
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let mut sys = System::new();
+        if !sys.refresh_process(pid) {
+            return Err(crate::utils::error::BottomError::GenericError(format!(
+                "PID {} does not exist",
+                pid
+            )));
+        }
+
+        let process = sys.get_process(pid).ok_or_else(|| {
+            crate::utils::error::BottomError::GenericError(format!("PID {} does not exist", pid))
+        })?;
+        Ok(ProcessDetails {
+            name: process.name().to_string(),
+            command: process.cmd().join(" "),
+            start_time: Some(process.start_time()),
+            cpu_time_secs: None,
+            rss_bytes: Some(process.memory() * 1024),
+            virtual_bytes: Some(process.virtual_memory() * 1024),
+            thread_count: None,
+            fd_count: None,
+            cgroup: None,
+            affinity: None,
+        })
+    }
+}
+
+/// The kind of file an open file descriptor refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FdType {
+    File,
+    Socket,
+    Pipe,
+    Device,
+    AnonInode,
+}
+
+impl std::fmt::Display for FdType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                FdType::File => "file",
+                FdType::Socket => "socket",
+                FdType::Pipe => "pipe",
+                FdType::Device => "device",
+                FdType::AnonInode => "anon",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FdEntry {
+    pub fd: u32,
+    pub fd_type: FdType,
+    /// The resolved path, or a description like `socket:[1234]`.
+    pub target: String,
+}
+
+/// Lists the open file descriptors of a process.  Like the environment, this is only read on
+/// demand.
+pub fn get_open_fds(pid: Pid) -> crate::utils::error::Result<Vec<FdEntry>> {
+    #[cfg(target_os = "linux")]
+    {
+        let mut fd_entries = std::fs::read_dir(format!("/proc/{}/fd", pid))?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let fd = entry.file_name().to_string_lossy().parse::<u32>().ok()?;
+                // The fd may have been closed since we listed the directory.
+                let target = std::fs::read_link(entry.path())
+                    .ok()?
+                    .to_string_lossy()
+                    .to_string();
+
+                let fd_type = if target.starts_with("socket:") {
+                    FdType::Socket
+                } else if target.starts_with("pipe:") {
+                    FdType::Pipe
+                } else if target.starts_with("anon_inode:") {
+                    FdType::AnonInode
+                } else if target.starts_with("/dev/") {
+                    FdType::Device
+                } else {
+                    FdType::File
+                };
+
+                Some(FdEntry {
+                    fd,
+                    fd_type,
+                    target,
+                })
+            })
+            .collect::<Vec<_>>();
+        fd_entries.sort_by_key(|fd_entry| fd_entry.fd);
+
+        Ok(fd_entries)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err(crate::utils::error::BottomError::GenericError(format!(
+            "listing the open files of PID {} is only supported on Linux",
+            pid
+        )))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConnectionEntry {
+    pub proto: &'static str,
+    pub local_addr: std::net::SocketAddr,
+    pub remote_addr: std::net::SocketAddr,
+    pub state: &'static str,
+}
+
+/// Lists the TCP and UDP connections of a process.  The process' network namespace lists every
+/// socket, so we only keep those whose inode is one of the process' open file descriptors.
+pub fn get_process_connections(pid: Pid) -> crate::utils::error::Result<Vec<ConnectionEntry>> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::{
+            collections::HashSet,
+            net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+        };
+
+        /// Parses an address like `0100007F:0035`.  The kernel prints the address as native
+        /// endian 32-bit words, while the port is a plain number.
+        fn parse_address(address: &str) -> Option<SocketAddr> {
+            let mut split = address.split(':');
+            let ip = split.next()?;
+            let port = u16::from_str_radix(split.next()?, 16).ok()?;
+
+            let mut bytes: Vec<u8> = Vec::with_capacity(16);
+            for word_itx in (0..ip.len()).step_by(8) {
+                let word = u32::from_str_radix(ip.get(word_itx..word_itx + 8)?, 16).ok()?;
+                bytes.extend_from_slice(&word.to_ne_bytes());
+            }
+
+            let ip = match bytes.len() {
+                4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+                16 => {
+                    let mut octets = [0; 16];
+                    octets.copy_from_slice(&bytes);
+                    IpAddr::V6(Ipv6Addr::from(octets))
+                }
+                _ => return None,
+            };
+
+            Some(SocketAddr::new(ip, port))
+        }
+
+        fn tcp_state(state: &str) -> &'static str {
+            match state {
+                "01" => "ESTABLISHED",
+                "02" => "SYN_SENT",
+                "03" => "SYN_RECV",
+                "04" => "FIN_WAIT1",
+                "05" => "FIN_WAIT2",
+                "06" => "TIME_WAIT",
+                "07" => "CLOSE",
+                "08" => "CLOSE_WAIT",
+                "09" => "LAST_ACK",
+                "0A" => "LISTEN",
+                "0B" => "CLOSING",
+                _ => "UNKNOWN",
+            }
+        }
+
+        fn udp_state(state: &str) -> &'static str {
+            match state {
+                "01" => "ESTABLISHED",
+                _ => "UNCONN",
+            }
+        }
+
+        let socket_inodes = std::fs::read_dir(format!("/proc/{}/fd", pid))?
+            .filter_map(|entry| {
+                let target = std::fs::read_link(entry.ok()?.path()).ok()?;
+                let target = target.to_string_lossy();
+                target
+                    .strip_prefix("socket:[")?
+                    .strip_suffix(']')?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .collect::<HashSet<_>>();
+
+        let mut connections: Vec<ConnectionEntry> = Vec::new();
+        let tables: [(&str, &'static str, fn(&str) -> &'static str); 4] = [
+            ("tcp", "TCP", tcp_state),
+            ("tcp6", "TCP6", tcp_state),
+            ("udp", "UDP", udp_state),
+            ("udp6", "UDP6", udp_state),
+        ];
+        for (file_name, proto, get_state) in tables.iter() {
+            // A missing table (ie: IPv6 being disabled) just means there's nothing to list.
+            let table = match std::fs::read_to_string(format!("/proc/{}/net/{}", pid, file_name)) {
+                Ok(table) => table,
+                Err(_) => continue,
+            };
+
+            // Skip the header.
+            for line in table.lines().skip(1) {
+                let fields = line.split_whitespace().collect::<Vec<_>>();
+                if fields.len() < 10 {
+                    continue;
+                }
+
+                let is_own_socket = fields[9]
+                    .parse::<u64>()
+                    .map(|inode| socket_inodes.contains(&inode))
+                    .unwrap_or(false);
+                if !is_own_socket {
+                    continue;
+                }
+
+                if let (Some(local_addr), Some(remote_addr)) =
+                    (parse_address(fields[1]), parse_address(fields[2]))
+                {
+                    connections.push(ConnectionEntry {
+                        proto,
+                        local_addr,
+                        remote_addr,
+                        state: get_state(fields[3]),
+                    });
+                }
+            }
+        }
+
+        Ok(connections)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err(crate::utils::error::BottomError::GenericError(format!(
+            "listing the connections of PID {} is only supported on Linux",
+            pid
+        )))
+    }
+}
+
+/// Resolves a UID to its username, falling back to the UID itself if it has no entry.
+#[cfg(target_family = "unix")]
+fn get_username(uid: libc::uid_t, user_table: &users::UsersCache) -> String {
+    use users::Users;
+
+    user_table
+        .get_user_by_uid(uid)
+        .map(|user| user.name().to_string_lossy().to_string())
+        .unwrap_or_else(|| uid.to_string())
+}
+
 #[allow(unused_variables)]
 #[cfg(not(target_os = "linux"))]
 fn convert_process_status_to_char(status: ProcessStatus) -> char {
@@ -0,0 +1,89 @@
+//! Data collection for socket counts.  Totals are read from `/proc/net/sockstat` and
+//! `/proc/net/sockstat6`, while TCP connection states are counted from `/proc/net/tcp` and
+//! `/proc/net/tcp6`, so this is only available on Linux.
+
+use serde::Serialize;
+
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct SocketHarvest {
+    /// All TCP sockets, including those in TIME_WAIT.
+    #[serde(rename = "tcp")]
+    pub tcp: u64,
+    #[serde(rename = "tcp_established")]
+    pub tcp_established: u64,
+    #[serde(rename = "tcp_time_wait")]
+    pub tcp_time_wait: u64,
+    #[serde(rename = "tcp_close_wait")]
+    pub tcp_close_wait: u64,
+    #[serde(rename = "udp")]
+    pub udp: u64,
+    #[serde(rename = "raw")]
+    pub raw: u64,
+    #[serde(rename = "unix")]
+    pub unix: u64,
+}
+
+/// Reads the number of sockets of each kind, for both IPv4 and IPv6.
+#[cfg(target_os = "linux")]
+pub fn get_socket_data() -> Option<SocketHarvest> {
+    use std::fs;
+
+    // Lines look like "TCP: inuse 12 orphan 0 tw 3 alloc 15 mem 1", or "UDP6: inuse 2".
+    let sockstat = fs::read_to_string("/proc/net/sockstat").ok()?;
+    let sockstat6 = fs::read_to_string("/proc/net/sockstat6").unwrap_or_default();
+    let get_stat = |protocol: &str, name: &str| -> u64 {
+        sockstat
+            .lines()
+            .chain(sockstat6.lines())
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                if fields.next()? != protocol {
+                    return None;
+                }
+                let fields = fields.collect::<Vec<_>>();
+                fields
+                    .chunks(2)
+                    .find(|pair| pair[0] == name)
+                    .and_then(|pair| pair.get(1)?.parse::<u64>().ok())
+            })
+            .sum()
+    };
+
+    // The fourth column of each row is the connection state, in hex.
+    let (mut tcp_established, mut tcp_close_wait) = (0, 0);
+    for path in &["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(table) = fs::read_to_string(path) {
+            for state in table
+                .lines()
+                .skip(1)
+                .filter_map(|line| line.split_whitespace().nth(3))
+            {
+                match state {
+                    "01" => tcp_established += 1,
+                    "08" => tcp_close_wait += 1,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let tcp_time_wait = get_stat("TCP:", "tw");
+
+    Some(SocketHarvest {
+        tcp: get_stat("TCP:", "inuse") + get_stat("TCP6:", "inuse") + tcp_time_wait,
+        tcp_established,
+        tcp_time_wait,
+        tcp_close_wait,
+        udp: get_stat("UDP:", "inuse") + get_stat("UDP6:", "inuse"),
+        raw: get_stat("RAW:", "inuse") + get_stat("RAW6:", "inuse"),
+        // Skip the header.
+        unix: fs::read_to_string("/proc/net/unix")
+            .map(|unix| unix.lines().count().saturating_sub(1) as u64)
+            .unwrap_or(0),
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_socket_data() -> Option<SocketHarvest> {
+    None
+}
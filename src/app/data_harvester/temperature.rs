@@ -1,9 +1,15 @@
+use serde::Serialize;
 use std::cmp::Ordering;
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize)]
 pub struct TempHarvest {
+    #[serde(rename = "component_name")]
     pub component_name: Option<String>,
+    #[serde(rename = "component_label")]
     pub component_label: Option<String>,
+    /// Always in Celsius, regardless of the temperature type; use [`convert_celsius`] to display
+    /// it.
+    #[serde(rename = "temperature")]
     pub temperature: f32,
 }
 
@@ -20,21 +26,22 @@ impl Default for TemperatureType {
     }
 }
 
+/// Converts a temperature in Celsius to the given temperature type.
+pub fn convert_celsius(celsius: f32, temp_type: &TemperatureType) -> f32 {
+    match temp_type {
+        TemperatureType::Celsius => celsius,
+        TemperatureType::Kelvin => celsius + 273.15,
+        TemperatureType::Fahrenheit => (celsius * (9.0 / 5.0)) + 32.0,
+    }
+}
+
 /// Meant for ARM and non-Linux usage.
 #[cfg(any(not(target_os = "linux"), target_arch = "aarch64", target_arch = "arm"))]
 pub async fn arm_and_non_linux_temperature_data(
-    sys: &sysinfo::System, temp_type: &TemperatureType, actually_get: bool,
+    sys: &sysinfo::System, actually_get: bool,
 ) -> crate::utils::error::Result<Option<Vec<TempHarvest>>> {
     use sysinfo::{ComponentExt, SystemExt};
 
-    fn convert_celsius_to_kelvin(celsius: f32) -> f32 {
-        celsius + 273.15
-    }
-
-    fn convert_celsius_to_fahrenheit(celsius: f32) -> f32 {
-        (celsius * (9.0 / 5.0)) + 32.0
-    }
-
     if !actually_get {
         return Ok(None);
     }
@@ -46,13 +53,7 @@ pub async fn arm_and_non_linux_temperature_data(
         temperature_vec.push(TempHarvest {
             component_name: None,
             component_label: Some(component.get_label().to_string()),
-            temperature: match temp_type {
-                TemperatureType::Celsius => component.get_temperature(),
-                TemperatureType::Kelvin => convert_celsius_to_kelvin(component.get_temperature()),
-                TemperatureType::Fahrenheit => {
-                    convert_celsius_to_fahrenheit(component.get_temperature())
-                }
-            },
+            temperature: component.get_temperature(),
         });
     }
 
@@ -62,7 +63,7 @@ pub async fn arm_and_non_linux_temperature_data(
 
 #[cfg(not(any(not(target_os = "linux"), target_arch = "aarch64", target_arch = "arm")))]
 pub async fn linux_temperature_data(
-    temp_type: &TemperatureType, actually_get: bool,
+    actually_get: bool,
 ) -> crate::utils::error::Result<Option<Vec<TempHarvest>>> {
     use futures::StreamExt;
 
@@ -83,18 +84,9 @@ pub async fn linux_temperature_data(
                 } else {
                     None
                 },
-                temperature: match temp_type {
-                    TemperatureType::Celsius => sensor
-                        .current()
-                        .get::<thermodynamic_temperature::degree_celsius>(),
-                    TemperatureType::Kelvin => {
-                        sensor.current().get::<thermodynamic_temperature::kelvin>()
-                    }
-                    TemperatureType::Fahrenheit => sensor
-                        .current()
-                        .get::<thermodynamic_temperature::degree_fahrenheit>(
-                    ),
-                },
+                temperature: sensor
+                    .current()
+                    .get::<thermodynamic_temperature::degree_celsius>(),
             });
         }
     }
@@ -0,0 +1,74 @@
+//! Data collection for the ZFS ARC.  Statistics are read from `/proc/spl/kstat/zfs/arcstats`, so
+//! they are only available on Linux.
+
+use serde::Serialize;
+
+const ARCSTATS_PATH: &str = "/proc/spl/kstat/zfs/arcstats";
+
+#[derive(Default, Debug, Clone, Serialize)]
+pub struct ZfsHarvest {
+    #[serde(rename = "arc_size_bytes")]
+    pub arc_size_bytes: u64,
+    #[serde(rename = "arc_max_bytes")]
+    pub arc_max_bytes: u64,
+    /// The percentage of ARC lookups since the last collection that were hits.
+    #[serde(rename = "arc_hit_rate")]
+    pub arc_hit_rate: f64,
+    /// The percentage of ARC lookups since the last collection that were misses.
+    #[serde(rename = "arc_miss_rate")]
+    pub arc_miss_rate: f64,
+}
+
+/// Whether the ZFS kernel module is loaded.
+pub fn is_zfs_present() -> bool {
+    cfg!(target_os = "linux") && std::path::Path::new(ARCSTATS_PATH).exists()
+}
+
+/// Reads the ARC statistics.  `prev_counts` holds the total hits and misses from the last read,
+/// and is updated; if there are none yet, the rates are taken over all lookups since boot.
+pub fn get_zfs_data(
+    actually_get: bool, prev_counts: &mut Option<(u64, u64)>,
+) -> Option<ZfsHarvest> {
+    if !actually_get || !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    // The first two lines are a header, followed by "name type data" rows.
+    let arcstats = std::fs::read_to_string(ARCSTATS_PATH).ok()?;
+    let get_stat = |name: &str| {
+        arcstats.lines().skip(2).find_map(|line| {
+            let mut fields = line.split_whitespace();
+            if fields.next()? == name {
+                fields.nth(1)?.parse::<u64>().ok()
+            } else {
+                None
+            }
+        })
+    };
+
+    let hits = get_stat("hits")?;
+    let misses = get_stat("misses")?;
+    let (new_hits, new_misses) = match *prev_counts {
+        Some((prev_hits, prev_misses)) => (
+            hits.saturating_sub(prev_hits),
+            misses.saturating_sub(prev_misses),
+        ),
+        None => (hits, misses),
+    };
+    *prev_counts = Some((hits, misses));
+
+    let (arc_hit_rate, arc_miss_rate) = match new_hits + new_misses {
+        0 => (0.0, 0.0),
+        lookups => (
+            new_hits as f64 / lookups as f64 * 100.0,
+            new_misses as f64 / lookups as f64 * 100.0,
+        ),
+    };
+
+    Some(ZfsHarvest {
+        arc_size_bytes: get_stat("size")?,
+        arc_max_bytes: get_stat("c_max")?,
+        arc_hit_rate,
+        arc_miss_rate,
+    })
+}
@@ -0,0 +1,380 @@
+//! Support for user-defined keybindings, set via the `[keybindings]` table in the config file.
+
+use std::collections::{HashMap, HashSet};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::utils::error::{self, BottomError};
+
+/// An action that can be bound to a key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    Quit,
+    Reset,
+    ShowHelp,
+    ToggleFreeze,
+    ExpandWidget,
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    ScrollUp,
+    ScrollDown,
+    SkipToFirst,
+    SkipToLast,
+    PageUp,
+    PageDown,
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+    KillProcess,
+    SendSignal,
+    EnableSearching,
+    ToggleGrouping,
+    ToggleTreeMode,
+    ToggleCollapse,
+    FilterByUser,
+    FilterByState,
+    ToggleTag,
+    ClearTags,
+    TogglePin,
+    FollowProcess,
+    Renice,
+    DecreaseNice,
+    IncreaseNice,
+    ToggleSort,
+    InvertSort,
+    SortPreviousColumn,
+    SortNextColumn,
+    TogglePercentages,
+    ToggleKernelThreads,
+    ToggleCurrentUsage,
+    ToggleIdle,
+    ToggleHugePages,
+    ToggleCommand,
+    ShowDetails,
+    ShowCommandLine,
+    ShowEnvironment,
+    ShowOpenFiles,
+    ShowConnections,
+    ToggleNumaGrouping,
+    ToggleCore,
+    ShowAllCores,
+    ToggleAverageCpu,
+    ToggleCpuBreakdown,
+    ToggleCpuInterrupts,
+    ExportData,
+    ExportProcesses,
+    ToggleIgnoreCase,
+    ToggleWholeWord,
+    ToggleRegex,
+}
+
+impl KeyAction {
+    /// All actions alongside the name used to refer to them in the config file.
+    pub const ALL: [(&'static str, KeyAction); 59] = [
+        ("quit", KeyAction::Quit),
+        ("reset", KeyAction::Reset),
+        ("show_help", KeyAction::ShowHelp),
+        ("toggle_freeze", KeyAction::ToggleFreeze),
+        ("expand_widget", KeyAction::ExpandWidget),
+        ("move_left", KeyAction::MoveLeft),
+        ("move_right", KeyAction::MoveRight),
+        ("move_up", KeyAction::MoveUp),
+        ("move_down", KeyAction::MoveDown),
+        ("scroll_up", KeyAction::ScrollUp),
+        ("scroll_down", KeyAction::ScrollDown),
+        ("skip_to_first", KeyAction::SkipToFirst),
+        ("skip_to_last", KeyAction::SkipToLast),
+        ("page_up", KeyAction::PageUp),
+        ("page_down", KeyAction::PageDown),
+        ("zoom_in", KeyAction::ZoomIn),
+        ("zoom_out", KeyAction::ZoomOut),
+        ("reset_zoom", KeyAction::ResetZoom),
+        ("kill_process", KeyAction::KillProcess),
+        ("send_signal", KeyAction::SendSignal),
+        ("enable_searching", KeyAction::EnableSearching),
+        ("toggle_grouping", KeyAction::ToggleGrouping),
+        ("toggle_tree_mode", KeyAction::ToggleTreeMode),
+        ("toggle_collapse", KeyAction::ToggleCollapse),
+        ("filter_by_user", KeyAction::FilterByUser),
+        ("filter_by_state", KeyAction::FilterByState),
+        ("toggle_tag", KeyAction::ToggleTag),
+        ("clear_tags", KeyAction::ClearTags),
+        ("toggle_pin", KeyAction::TogglePin),
+        ("follow_process", KeyAction::FollowProcess),
+        ("renice", KeyAction::Renice),
+        ("decrease_nice", KeyAction::DecreaseNice),
+        ("increase_nice", KeyAction::IncreaseNice),
+        ("toggle_sort", KeyAction::ToggleSort),
+        ("invert_sort", KeyAction::InvertSort),
+        ("sort_previous_column", KeyAction::SortPreviousColumn),
+        ("sort_next_column", KeyAction::SortNextColumn),
+        ("toggle_percentages", KeyAction::TogglePercentages),
+        ("toggle_kernel_threads", KeyAction::ToggleKernelThreads),
+        ("toggle_current_usage", KeyAction::ToggleCurrentUsage),
+        ("toggle_idle", KeyAction::ToggleIdle),
+        ("toggle_huge_pages", KeyAction::ToggleHugePages),
+        ("toggle_command", KeyAction::ToggleCommand),
+        ("show_details", KeyAction::ShowDetails),
+        ("show_command_line", KeyAction::ShowCommandLine),
+        ("show_environment", KeyAction::ShowEnvironment),
+        ("show_open_files", KeyAction::ShowOpenFiles),
+        ("show_connections", KeyAction::ShowConnections),
+        ("toggle_numa_grouping", KeyAction::ToggleNumaGrouping),
+        ("toggle_core", KeyAction::ToggleCore),
+        ("show_all_cores", KeyAction::ShowAllCores),
+        ("toggle_average_cpu", KeyAction::ToggleAverageCpu),
+        ("toggle_cpu_breakdown", KeyAction::ToggleCpuBreakdown),
+        ("toggle_cpu_interrupts", KeyAction::ToggleCpuInterrupts),
+        ("export_data", KeyAction::ExportData),
+        ("export_processes", KeyAction::ExportProcesses),
+        ("toggle_ignore_case", KeyAction::ToggleIgnoreCase),
+        ("toggle_whole_word", KeyAction::ToggleWholeWord),
+        ("toggle_regex", KeyAction::ToggleRegex),
+    ];
+
+    /// The keys an action is bound to when the config doesn't rebind it.  Multi-key sequences
+    /// like `dd` are listed by their first key.  Enter does different things depending on the
+    /// widget, so it isn't listed for any action.
+    pub fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            KeyAction::Quit => &["q", "ctrl-c"],
+            KeyAction::Reset => &["ctrl-r"],
+            KeyAction::ShowHelp => &["?"],
+            KeyAction::ToggleFreeze => &["f"],
+            KeyAction::ExpandWidget => &["e"],
+            KeyAction::MoveLeft => &["H", "A", "ctrl-left", "shift-left"],
+            KeyAction::MoveRight => &["L", "D", "ctrl-right", "shift-right"],
+            KeyAction::MoveUp => &["K", "W", "ctrl-up", "shift-up"],
+            KeyAction::MoveDown => &["J", "S", "ctrl-down", "shift-down"],
+            KeyAction::ScrollUp => &["k", "up"],
+            KeyAction::ScrollDown => &["j", "down"],
+            KeyAction::SkipToFirst => &["g", "home"],
+            KeyAction::SkipToLast => &["G", "end"],
+            KeyAction::PageUp => &["pageup"],
+            KeyAction::PageDown => &["pagedown"],
+            KeyAction::ZoomIn => &["+"],
+            KeyAction::ZoomOut => &["-"],
+            KeyAction::ResetZoom => &["=", "r"],
+            KeyAction::KillProcess => &["d"],
+            KeyAction::SendSignal => &["f9"],
+            KeyAction::EnableSearching => &["/", "ctrl-f"],
+            KeyAction::ToggleGrouping => &["tab"],
+            KeyAction::ToggleTreeMode => &["t", "f5"],
+            KeyAction::ToggleCollapse | KeyAction::ShowDetails => &[],
+            KeyAction::FilterByUser => &["ctrl-u"],
+            KeyAction::FilterByState => &["ctrl-t"],
+            KeyAction::ToggleTag | KeyAction::ToggleCore => &["space"],
+            KeyAction::ClearTags => &["U"],
+            KeyAction::TogglePin => &["M"],
+            KeyAction::FollowProcess => &["F"],
+            KeyAction::Renice => &["R"],
+            KeyAction::DecreaseNice => &["["],
+            KeyAction::IncreaseNice => &["]"],
+            KeyAction::ToggleSort => &["s", "f6"],
+            KeyAction::InvertSort => &["I"],
+            KeyAction::SortPreviousColumn => &["<"],
+            KeyAction::SortNextColumn => &[">"],
+            KeyAction::TogglePercentages => &["%"],
+            KeyAction::ToggleKernelThreads => &["T"],
+            KeyAction::ToggleCurrentUsage => &["u"],
+            KeyAction::ToggleIdle | KeyAction::ToggleCpuInterrupts => &["i"],
+            KeyAction::ToggleHugePages => &["h"],
+            KeyAction::ToggleCommand => &["P"],
+            KeyAction::ShowCommandLine => &["C"],
+            KeyAction::ShowEnvironment => &["E"],
+            KeyAction::ShowOpenFiles => &["o"],
+            KeyAction::ShowConnections | KeyAction::ToggleNumaGrouping => &["N"],
+            KeyAction::ShowAllCores => &["a"],
+            KeyAction::ToggleAverageCpu => &["v"],
+            KeyAction::ToggleCpuBreakdown => &["b"],
+            KeyAction::ExportData => &["ctrl-e"],
+            KeyAction::ExportProcesses => &["ctrl-o"],
+            KeyAction::ToggleIgnoreCase => &["f1", "alt-c", "alt-C"],
+            KeyAction::ToggleWholeWord => &["f2", "alt-w", "alt-W", "ctrl-w"],
+            KeyAction::ToggleRegex => &["f3", "alt-r", "alt-R"],
+        }
+    }
+}
+
+impl std::str::FromStr for KeyAction {
+    type Err = BottomError;
+
+    fn from_str(s: &str) -> error::Result<Self> {
+        KeyAction::ALL
+            .iter()
+            .find(|(name, _)| *name == s.to_lowercase())
+            .map(|(_, action)| *action)
+            .ok_or_else(|| BottomError::ConfigError(format!("\"{}\" is an invalid action name", s)))
+    }
+}
+
+/// A mapping of keys to actions, covering the keys whose meaning the config changes.  Binding
+/// an action replaces its default keys, so those keys map to whichever of their default actions
+/// are left, which may be none.  Keys that aren't in the map keep their built-in handling, which
+/// also takes care of widget-specific keys and multi-key sequences like `dd`.
+#[derive(Debug, Default)]
+pub struct KeybindingMap {
+    bindings: HashMap<KeyEvent, Vec<KeyAction>>,
+}
+
+impl KeybindingMap {
+    /// Builds a keybinding map from a table of action names to key descriptors.  All unknown
+    /// actions, invalid keys, and keys bound to more than one action are reported together.
+    pub fn from_table(table: &HashMap<String, String>) -> error::Result<Self> {
+        let mut user_bindings: HashMap<KeyEvent, KeyAction> = HashMap::new();
+        let mut key_names: HashMap<KeyEvent, &str> = HashMap::new();
+        let mut errors: Vec<String> = Vec::new();
+
+        // Sort so that error messages are stable across runs.
+        let mut entries = table.iter().collect::<Vec<_>>();
+        entries.sort();
+
+        for (action_name, key_name) in entries {
+            let action = action_name.parse::<KeyAction>();
+            let key = parse_key(key_name);
+
+            match (action, key) {
+                (Ok(action), Ok(key)) => {
+                    if let Some(other_action) = key_names.get(&key) {
+                        errors.push(format!(
+                            "\"{}\" is bound to both \"{}\" and \"{}\"",
+                            key_name, other_action, action_name
+                        ));
+                    } else {
+                        key_names.insert(key, action_name);
+                        user_bindings.insert(key, action);
+                    }
+                }
+                (action, key) => {
+                    if let Err(err) = action {
+                        errors.push(error_message(err));
+                    }
+                    if let Err(err) = key {
+                        errors.push(error_message(err));
+                    }
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(BottomError::ConfigError(errors.join("; ")));
+        }
+
+        let rebound_actions = user_bindings.values().copied().collect::<HashSet<_>>();
+        let mut bindings: HashMap<KeyEvent, Vec<KeyAction>> = HashMap::new();
+        for (key, actions) in default_bindings() {
+            if actions
+                .iter()
+                .any(|action| rebound_actions.contains(action))
+            {
+                bindings.insert(
+                    key,
+                    actions
+                        .into_iter()
+                        .filter(|action| !rebound_actions.contains(action))
+                        .collect(),
+                );
+            }
+        }
+        for (key, action) in user_bindings {
+            bindings.insert(key, vec![action]);
+        }
+
+        Ok(KeybindingMap { bindings })
+    }
+
+    /// Returns the actions a key event now performs, if the config changed what it does.  An
+    /// empty list means the key was freed by rebinding its default action.
+    pub fn get(&self, event: &KeyEvent) -> Option<&[KeyAction]> {
+        self.bindings
+            .get(&normalize_key(*event))
+            .map(|actions| actions.as_slice())
+    }
+}
+
+/// Maps each default key to the actions it performs.  A few keys, like space, do different
+/// things depending on the widget, so they have more than one action.
+fn default_bindings() -> HashMap<KeyEvent, Vec<KeyAction>> {
+    let mut bindings: HashMap<KeyEvent, Vec<KeyAction>> = HashMap::new();
+    for (_, action) in KeyAction::ALL.iter() {
+        for key in action.default_keys() {
+            if let Ok(key) = parse_key(key) {
+                bindings.entry(key).or_default().push(*action);
+            }
+        }
+    }
+    bindings
+}
+
+fn error_message(err: BottomError) -> String {
+    match err {
+        BottomError::ConfigError(message) => message,
+        err => err.to_string(),
+    }
+}
+
+/// Shift is reported inconsistently for characters between terminals, so we fold it into the
+/// character itself.
+fn normalize_key(event: KeyEvent) -> KeyEvent {
+    match event.code {
+        KeyCode::Char(c) if event.modifiers.contains(KeyModifiers::SHIFT) => KeyEvent {
+            code: KeyCode::Char(c.to_ascii_uppercase()),
+            modifiers: event.modifiers - KeyModifiers::SHIFT,
+        },
+        _ => event,
+    }
+}
+
+/// Parses a key descriptor like `"ctrl-h"`, `"alt-f"`, `"shift-left"`, `"f5"`, or `"x"`.
+pub fn parse_key(descriptor: &str) -> error::Result<KeyEvent> {
+    let invalid_key = || BottomError::ConfigError(format!("\"{}\" is an invalid key", descriptor));
+
+    let mut parts = descriptor.split('-').collect::<Vec<_>>();
+    // A lone "-" (or a trailing one, like "ctrl--") refers to the minus key itself.
+    if descriptor.ends_with("--") || descriptor == "-" {
+        parts.truncate(parts.len() - 2);
+        parts.push("-");
+    }
+    let key_name = parts.pop().ok_or_else(invalid_key)?;
+
+    let mut modifiers = KeyModifiers::empty();
+    for modifier in parts {
+        modifiers |= match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return Err(invalid_key()),
+        };
+    }
+
+    let code = if key_name.chars().count() == 1 {
+        // Single characters are case-sensitive, unlike named keys.
+        KeyCode::Char(key_name.chars().next().ok_or_else(invalid_key)?)
+    } else {
+        match key_name.to_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "space" => KeyCode::Char(' '),
+            name => match name.strip_prefix('f').map(|num| num.parse::<u8>()) {
+                Some(Ok(num)) if (1..=12).contains(&num) => KeyCode::F(num),
+                _ => return Err(invalid_key()),
+            },
+        }
+    };
+
+    Ok(normalize_key(KeyEvent { code, modifiers }))
+}
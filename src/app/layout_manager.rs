@@ -894,6 +894,8 @@ pub enum BottomWidgetType {
     BasicNet,
     BasicTables,
     Battery,
+    Gpu,
+    Sockets,
 }
 
 impl BottomWidgetType {
@@ -904,7 +906,7 @@ impl BottomWidgetType {
 
     pub fn is_widget_graph(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Cpu | Net | Mem)
+        matches!(self, Cpu | Net | Mem | Gpu)
     }
 
     pub fn get_pretty_name(&self) -> &str {
@@ -917,6 +919,8 @@ impl BottomWidgetType {
             Temp => "Temperature",
             Disk => "Disks",
             Battery => "Battery",
+            Gpu => "GPU",
+            Sockets => "Sockets",
             _ => "",
         }
     }
@@ -942,6 +946,8 @@ impl std::str::FromStr for BottomWidgetType {
             "disk" => Ok(BottomWidgetType::Disk),
             "empty" => Ok(BottomWidgetType::Empty),
             "battery" | "batt" => Ok(BottomWidgetType::Battery),
+            "gpu" => Ok(BottomWidgetType::Gpu),
+            "sockets" | "socket" => Ok(BottomWidgetType::Sockets),
             _ => Err(BottomError::ConfigError(format!(
                 "\"{}\" is an invalid widget name.
 
@@ -960,6 +966,10 @@ Supported widget names:
 |           disk           |
 +--------------------------+
 |       batt, battery      |
++--------------------------+
+|            gpu           |
++--------------------------+
+|      socket, sockets     |
 +--------------------------+
                 ",
                 s
@@ -977,4 +987,23 @@ pub struct UsedWidgets {
     pub use_disk: bool,
     pub use_temp: bool,
     pub use_battery: bool,
+    pub use_gpu: bool,
+    pub use_sockets: bool,
+}
+
+impl UsedWidgets {
+    /// Every widget, for when all data should be collected regardless of the layout.
+    pub fn all() -> Self {
+        UsedWidgets {
+            use_cpu: true,
+            use_mem: true,
+            use_net: true,
+            use_proc: true,
+            use_disk: true,
+            use_temp: true,
+            use_battery: true,
+            use_gpu: true,
+            use_sockets: true,
+        }
+    }
 }
@@ -31,34 +31,138 @@ impl Process {
     }
 }
 
+/// Signals that can be picked from the signal dialog, alongside their names.
+#[cfg(target_family = "unix")]
+pub const SIGNALS: [(&str, i32); 9] = [
+    ("SIGTERM", libc::SIGTERM),
+    ("SIGKILL", libc::SIGKILL),
+    ("SIGINT", libc::SIGINT),
+    ("SIGQUIT", libc::SIGQUIT),
+    ("SIGSTOP", libc::SIGSTOP),
+    ("SIGCONT", libc::SIGCONT),
+    ("SIGHUP", libc::SIGHUP),
+    ("SIGUSR1", libc::SIGUSR1),
+    ("SIGUSR2", libc::SIGUSR2),
+];
+
+/// Sends a signal to a process, given a PID.
+#[cfg(target_family = "unix")]
+pub fn send_signal_given_pid(pid: Pid, signal: i32) -> crate::utils::error::Result<()> {
+    let output = unsafe { libc::kill(pid as i32, signal) };
+    if output != 0 {
+        // We had an error...
+        let err_code = std::io::Error::last_os_error().raw_os_error();
+        let err = match err_code {
+            Some(libc::ESRCH) => "the target process did not exist.",
+            Some(libc::EPERM) => "the calling process does not have the permissions to signal the target process(es).",
+            Some(libc::EINVAL) => "an invalid signal was specified.",
+            _ => "Unknown error occurred."
+        };
+
+        return if let Some(err_code) = err_code {
+            Err(BottomError::GenericError(format!(
+                "Error code {} - {}",
+                err_code, err,
+            )))
+        } else {
+            Err(BottomError::GenericError(format!(
+                "Error code ??? - {}",
+                err,
+            )))
+        };
+    }
+
+    Ok(())
+}
+
+/// Returns the current nice value of a process, given a PID.
+pub fn get_nice_given_pid(pid: Pid) -> Option<i32> {
+    #[cfg(target_family = "unix")]
+    {
+        // -1 is a valid nice value, so errno has to be cleared to tell it apart from an error.
+        unsafe {
+            #[cfg(target_os = "linux")]
+            {
+                *libc::__errno_location() = 0;
+            }
+            #[cfg(not(target_os = "linux"))]
+            {
+                *libc::__error() = 0;
+            }
+        }
+
+        let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t) };
+        if nice == -1 && std::io::Error::last_os_error().raw_os_error() != Some(0) {
+            None
+        } else {
+            Some(nice)
+        }
+    }
+    #[cfg(not(target_family = "unix"))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// Sets the nice value of a process, given a PID.  The error is a short reason, like
+/// "permission denied".
+pub fn set_nice_given_pid(pid: Pid, nice: i32) -> Result<(), &'static str> {
+    #[cfg(target_family = "unix")]
+    {
+        let output = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) };
+        if output != 0 {
+            return Err(match std::io::Error::last_os_error().raw_os_error() {
+                Some(libc::ESRCH) => "the process does not exist",
+                // Only root can lower the nice value, or renice another user's process.
+                Some(libc::EPERM) | Some(libc::EACCES) => "permission denied",
+                _ => "unknown error",
+            });
+        }
+
+        Ok(())
+    }
+    #[cfg(not(target_family = "unix"))]
+    {
+        let _ = (pid, nice);
+        Err("renicing is not supported on this platform")
+    }
+}
+
+/// Whether we are allowed to lower nice values, which needs `CAP_SYS_NICE` on Linux (and root
+/// elsewhere).
+pub fn can_lower_nice() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        // CAP_SYS_NICE is bit 23 of the effective capability set.
+        const CAP_SYS_NICE: u32 = 23;
+        std::fs::read_to_string("/proc/self/status")
+            .ok()
+            .and_then(|status| {
+                status
+                    .lines()
+                    .find_map(|line| line.strip_prefix("CapEff:"))
+                    .and_then(|caps| u64::from_str_radix(caps.trim(), 16).ok())
+            })
+            .map(|caps| caps & (1 << CAP_SYS_NICE) != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(all(target_family = "unix", not(target_os = "linux")))]
+    {
+        unsafe { libc::geteuid() == 0 }
+    }
+    #[cfg(not(target_family = "unix"))]
+    {
+        false
+    }
+}
+
 /// Kills a process, given a PID.
 pub fn kill_process_given_pid(pid: Pid) -> crate::utils::error::Result<()> {
     if cfg!(target_family = "unix") {
         #[cfg(any(target_family = "unix"))]
         {
-            let output = unsafe { libc::kill(pid as i32, libc::SIGTERM) };
-            if output != 0 {
-                // We had an error...
-                let err_code = std::io::Error::last_os_error().raw_os_error();
-                let err = match err_code {
-                Some(libc::ESRCH) => "the target process did not exist.",
-                Some(libc::EPERM) => "the calling process does not have the permissions to terminate the target process(es).",
-                Some(libc::EINVAL) => "an invalid signal was specified.",
-                _ => "Unknown error occurred."
-            };
-
-                return if let Some(err_code) = err_code {
-                    Err(BottomError::GenericError(format!(
-                        "Error code {} - {}",
-                        err_code, err,
-                    )))
-                } else {
-                    Err(BottomError::GenericError(format!(
-                        "Error code ??? - {}",
-                        err,
-                    )))
-                };
-            }
+            send_signal_given_pid(pid, libc::SIGTERM)?;
         }
     } else if cfg!(target_family = "windows") {
         #[cfg(target_family = "windows")]
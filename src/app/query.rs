@@ -5,9 +5,11 @@ use crate::{
         BottomError::{self, QueryError},
         Result,
     },
+    Pid,
 };
 use std::collections::VecDeque;
 use std::fmt::Debug;
+use std::ops::Range;
 
 const DELIMITER_LIST: [char; 6] = ['=', '>', '<', '(', ')', '\"'];
 const COMPARISON_LIST: [&str; 3] = [">", "=", "<"];
@@ -23,10 +25,13 @@ pub trait ProcessQuery {
     /// - Process names: No prefix required, can use regex, match word, or case.
     ///   Enclosing anything, including prefixes, in quotes, means we treat it as an entire process
     ///   rather than a prefix.
-    /// - PIDs: Use prefix `pid`, can use regex or match word (case is irrelevant).
+    /// - PIDs: Use prefix `pid`.  Takes a comma-separated list of PIDs and ranges like
+    ///   `1,1000-2000`, which are matched exactly, unless using regex.
     /// - CPU: Use prefix `cpu`, cannot use r/m/c (regex, match word, case).  Can compare.
     /// - MEM: Use prefix `mem`, cannot use r/m/c.  Can compare.
     /// - STATE: Use prefix `state`, TODO when we update how state looks in 0.5 probably.
+    /// - User: Use prefix `user`, can use regex, match word, or case.  Can also be written as
+    ///   `user:name`.
     /// - Read/s: Use prefix `r`.  Can compare.
     /// - Write/s: Use prefix `w`.  Can compare.
     /// - Total read: Use prefix `read`.  Can compare.
@@ -48,7 +53,10 @@ impl ProcessQuery for ProcWidgetState {
                 list_of_ors.push(process_or(query)?);
             }
 
-            Ok(Query { query: list_of_ors })
+            Ok(Query {
+                query: list_of_ors,
+                is_excluding: false,
+            })
         }
 
         fn process_or(query: &mut VecDeque<String>) -> Result<Or> {
@@ -245,6 +253,43 @@ impl ProcessQuery for ProcWidgetState {
                         return Err(QueryError("Missing closing quotation".into()));
                     }
                 } else {
+                    // String prefixes can also be attached to their value with a colon, like "user:root".
+                    let mut queue_top = queue_top;
+                    if let Some(colon_index) = queue_top.find(':') {
+                        let prefix_type = queue_top[..colon_index].parse::<PrefixType>()?;
+                        let is_comparison_next = colon_index + 1 == queue_top.len()
+                            && matches!(query.front(), Some(next) if COMPARISON_LIST.contains(&next.as_str()));
+
+                        if is_comparison_next
+                            && !matches!(
+                                prefix_type,
+                                PrefixType::Name | PrefixType::State | PrefixType::User
+                            )
+                        {
+                            // Something like "pid:>1000" reads the same as "pid > 1000".  Only
+                            // numeric prefixes can be compared, so "user:>x" is left as it is.
+                            queue_top.truncate(colon_index);
+                        } else if let PrefixType::Pid | PrefixType::State | PrefixType::User =
+                            prefix_type
+                        {
+                            let content = &queue_top[colon_index + 1..];
+                            if content.is_empty() {
+                                return Err(QueryError(
+                                    "Missing argument for search prefix".into(),
+                                ));
+                            }
+
+                            return Ok(Prefix {
+                                or: None,
+                                regex_prefix: Some((
+                                    prefix_type,
+                                    StringQuery::Value(content.to_string()),
+                                )),
+                                compare_prefix: None,
+                            });
+                        }
+                    }
+
                     //  Get prefix type...
                     let prefix_type = queue_top.parse::<PrefixType>()?;
                     let content = if let PrefixType::Name = prefix_type {
@@ -262,7 +307,12 @@ impl ProcessQuery for ProcWidgetState {
                                     compare_prefix: None,
                                 })
                             }
-                            PrefixType::Pid | PrefixType::State => {
+                            // PIDs are matched against a list by default, but can be compared
+                            // numerically too, like "pid > 1000".
+                            PrefixType::Pid | PrefixType::State | PrefixType::User
+                                if !(matches!(prefix_type, PrefixType::Pid)
+                                    && (content == ">" || content == "<")) =>
+                            {
                                 // We have to check if someone put an "="...
                                 if content == "=" {
                                     // Check next string if possible
@@ -413,25 +463,25 @@ impl ProcessQuery for ProcWidgetState {
         }
 
         let mut split_query = VecDeque::new();
-
-        self.get_current_search_query()
-            .split_whitespace()
-            .for_each(|s| {
-                // From https://stackoverflow.com/a/56923739 in order to get a split but include the parentheses
-                let mut last = 0;
-                for (index, matched) in s.match_indices(|x| DELIMITER_LIST.contains(&x)) {
-                    if last != index {
-                        split_query.push_back(s[last..index].to_owned());
-                    }
-                    split_query.push_back(matched.to_owned());
-                    last = index + matched.len();
-                }
-                if last < s.len() {
-                    split_query.push_back(s[last..].to_owned());
+        let (is_excluding, search_query) = split_exclusion(self.get_current_search_query());
+
+        search_query.split_whitespace().for_each(|s| {
+            // From https://stackoverflow.com/a/56923739 in order to get a split but include the parentheses
+            let mut last = 0;
+            for (index, matched) in s.match_indices(|x| DELIMITER_LIST.contains(&x)) {
+                if last != index {
+                    split_query.push_back(s[last..index].to_owned());
                 }
-            });
+                split_query.push_back(matched.to_owned());
+                last = index + matched.len();
+            }
+            if last < s.len() {
+                split_query.push_back(s[last..].to_owned());
+            }
+        });
 
         let mut process_filter = process_string_to_filter(&mut split_query)?;
+        process_filter.is_excluding = is_excluding;
         process_filter.process_regexes(
             self.process_search_state.is_searching_whole_word,
             self.process_search_state.is_ignoring_case,
@@ -442,12 +492,92 @@ impl ProcessQuery for ProcWidgetState {
     }
 }
 
+/// Where a process matched a search, as a byte range into the matched text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchMatch {
+    /// A match in the process name, or in the command if that is shown instead.
+    Name(Range<usize>),
+    Pid(Range<usize>),
+}
+
+/// Splits off a leading `!`, which means that the query hides matching processes instead of
+/// showing them.
+pub fn split_exclusion(search_query: &str) -> (bool, &str) {
+    let search_query = search_query.trim();
+    match search_query.strip_prefix('!') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, search_query),
+    }
+}
+
+/// Builds the regex that a search term is matched with, following the search options.
+pub fn build_search_regex(
+    text: &str, is_searching_whole_word: bool, is_ignoring_case: bool,
+    is_searching_with_regex: bool,
+) -> Result<regex::Regex> {
+    let escaped_regex: String;
+    // Whole words must be bounded by the ends of the text or by a non-word character, so "sh"
+    // matches "/bin/sh -c" but not "bash".  We don't use \b since it never matches next to
+    // punctuation, like in "(sd-pam)".
+    let final_regex_string = &format!(
+        "{}{}({}){}",
+        if is_ignoring_case { "(?i)" } else { "" },
+        if is_searching_whole_word {
+            "(?:^|\\W)"
+        } else {
+            ""
+        },
+        if !is_searching_with_regex {
+            escaped_regex = regex::escape(text);
+            &escaped_regex
+        } else {
+            text
+        },
+        if is_searching_whole_word {
+            "(?:\\W|$)"
+        } else {
+            ""
+        },
+    );
+
+    Ok(regex::Regex::new(final_regex_string)?)
+}
+
 pub struct Query {
     /// Remember, AND > OR, but AND must come after OR when we parse.
     pub query: Vec<Or>,
+    /// Whether processes that match should be hidden rather than shown.
+    pub is_excluding: bool,
 }
 
 impl Query {
+    /// A query that just matches the given text against process names, for when the text
+    /// couldn't be parsed as a proper query.
+    pub fn plain_text(
+        text: &str, is_searching_whole_word: bool, is_ignoring_case: bool,
+    ) -> Result<Self> {
+        let mut query = Query {
+            query: vec![Or {
+                lhs: And {
+                    lhs: Prefix {
+                        or: None,
+                        regex_prefix: Some((
+                            PrefixType::Name,
+                            StringQuery::Value(text.to_string()),
+                        )),
+                        compare_prefix: None,
+                    },
+                    rhs: None,
+                },
+                rhs: None,
+            }],
+            is_excluding: false,
+        };
+        query.process_regexes(is_searching_whole_word, is_ignoring_case, false)?;
+
+        Ok(query)
+    }
+
     pub fn process_regexes(
         &mut self, is_searching_whole_word: bool, is_ignoring_case: bool,
         is_searching_with_regex: bool,
@@ -467,6 +597,20 @@ impl Query {
         self.query
             .iter()
             .all(|ok| ok.check(process, is_using_command))
+            != self.is_excluding
+    }
+
+    /// Returns where a process matched this query's name or PID terms, for highlighting.
+    pub fn find_match(
+        &self, process: &ConvertedProcessData, is_using_command: bool,
+    ) -> Option<SearchMatch> {
+        if self.is_excluding {
+            return None;
+        }
+
+        self.query
+            .iter()
+            .find_map(|or| or.find_match(process, is_using_command))
     }
 }
 
@@ -510,6 +654,24 @@ impl Or {
             self.lhs.check(process, is_using_command)
         }
     }
+
+    pub fn find_match(
+        &self, process: &ConvertedProcessData, is_using_command: bool,
+    ) -> Option<SearchMatch> {
+        // Only look at the sides that actually matched.
+        if self.lhs.check(process, is_using_command) {
+            if let Some(found) = self.lhs.find_match(process, is_using_command) {
+                return Some(found);
+            }
+        }
+
+        match &self.rhs {
+            Some(rhs) if rhs.check(process, is_using_command) => {
+                rhs.find_match(process, is_using_command)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Debug for Or {
@@ -555,6 +717,17 @@ impl And {
             self.lhs.check(process, is_using_command)
         }
     }
+
+    pub fn find_match(
+        &self, process: &ConvertedProcessData, is_using_command: bool,
+    ) -> Option<SearchMatch> {
+        self.lhs
+            .find_match(process, is_using_command)
+            .or_else(|| match &self.rhs {
+                Some(rhs) => rhs.find_match(process, is_using_command),
+                None => None,
+            })
+    }
 }
 
 impl Debug for And {
@@ -578,6 +751,7 @@ pub enum PrefixType {
     TWrite,
     Name,
     State,
+    User,
     __Nonexhaustive,
 }
 
@@ -600,6 +774,7 @@ impl std::str::FromStr for PrefixType {
             "twrite" | "t.write" => Ok(TWrite),
             "pid" => Ok(Pid),
             "state" => Ok(State),
+            "user" => Ok(User),
             _ => Ok(Name),
         }
     }
@@ -626,27 +801,20 @@ impl Prefix {
         } else if let Some((prefix_type, query_content)) = &mut self.regex_prefix {
             if let StringQuery::Value(regex_string) = query_content {
                 match prefix_type {
-                    PrefixType::Pid | PrefixType::Name | PrefixType::State => {
-                        let escaped_regex: String;
-                        let final_regex_string = &format!(
-                            "{}{}{}{}",
-                            if is_searching_whole_word { "^" } else { "" },
-                            if is_ignoring_case { "(?i)" } else { "" },
-                            if !is_searching_with_regex {
-                                escaped_regex = regex::escape(regex_string);
-                                &escaped_regex
-                            } else {
-                                regex_string
-                            },
-                            if is_searching_whole_word { "$" } else { "" },
-                        );
+                    PrefixType::Pid if !is_searching_with_regex => {
+                        *query_content = StringQuery::PidList(parse_pid_list(regex_string)?);
+                    }
+                    PrefixType::Pid | PrefixType::Name | PrefixType::State | PrefixType::User => {
+                        let regex = build_search_regex(
+                            regex_string,
+                            is_searching_whole_word,
+                            is_ignoring_case,
+                            is_searching_with_regex,
+                        )?;
 
                         let taken_pwc = self.regex_prefix.take();
                         if let Some((taken_pt, _)) = taken_pwc {
-                            self.regex_prefix = Some((
-                                taken_pt,
-                                StringQuery::Regex(regex::Regex::new(final_regex_string)?),
-                            ));
+                            self.regex_prefix = Some((taken_pt, StringQuery::Regex(regex)));
                         }
                     }
                     _ => {}
@@ -678,15 +846,39 @@ impl Prefix {
                     } else {
                         process.name.as_str()
                     }),
-                    PrefixType::Pid => r.is_match(process.pid.to_string().as_str()),
-                    PrefixType::State => r.is_match(process.process_state.as_str()),
+                    // Groups match if any of their processes do.
+                    PrefixType::Pid => process
+                        .group_pids
+                        .iter()
+                        .any(|pid| r.is_match(pid.to_string().as_str())),
+                    PrefixType::State => process
+                        .group_states
+                        .iter()
+                        .any(|state| r.is_match(state.as_str())),
+                    PrefixType::User => process
+                        .group_users
+                        .iter()
+                        .any(|user| r.is_match(user.as_str())),
                     _ => true,
                 }
+            } else if let StringQuery::PidList(pid_ranges) = query_content {
+                process.group_pids.iter().any(|pid| {
+                    pid_ranges
+                        .iter()
+                        .any(|(start, end)| (*start..=*end).contains(pid))
+                })
             } else {
                 true
             }
         } else if let Some((prefix_type, numerical_query)) = &self.compare_prefix {
             match prefix_type {
+                PrefixType::Pid => process.group_pids.iter().any(|pid| {
+                    matches_condition(
+                        &numerical_query.condition,
+                        *pid as f64,
+                        numerical_query.value,
+                    )
+                }),
                 PrefixType::PCpu => matches_condition(
                     &numerical_query.condition,
                     process.cpu_percent_usage,
@@ -729,6 +921,41 @@ impl Prefix {
             true
         }
     }
+
+    pub fn find_match(
+        &self, process: &ConvertedProcessData, is_using_command: bool,
+    ) -> Option<SearchMatch> {
+        if let Some(or) = &self.or {
+            or.find_match(process, is_using_command)
+        } else if let Some((prefix_type, StringQuery::Regex(r))) = &self.regex_prefix {
+            // The first group is the search term itself, without any whole word boundaries.
+            let find = |text: &str| {
+                r.captures(text)
+                    .and_then(|captures| captures.get(1))
+                    .map(|found| found.start()..found.end())
+            };
+
+            match prefix_type {
+                PrefixType::Name => find(if is_using_command {
+                    process.command.as_str()
+                } else {
+                    process.name.as_str()
+                })
+                .map(SearchMatch::Name),
+                PrefixType::Pid => find(process.pid.to_string().as_str()).map(SearchMatch::Pid),
+                _ => None,
+            }
+        } else if let Some((_, StringQuery::PidList(_))) = &self.regex_prefix {
+            // PIDs in a list match exactly, so the whole PID is highlighted.
+            if self.check(process, is_using_command) {
+                Some(SearchMatch::Pid(0..process.pid.to_string().len()))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
 }
 
 impl Debug for Prefix {
@@ -758,6 +985,33 @@ pub enum QueryComparison {
 pub enum StringQuery {
     Value(String),
     Regex(regex::Regex),
+    /// Inclusive ranges of PIDs; a single PID is a range of one.
+    PidList(Vec<(Pid, Pid)>),
+}
+
+/// Parses a comma-separated list of PIDs and ranges, like `1,1000-2000`.
+fn parse_pid_list(pid_list: &str) -> Result<Vec<(Pid, Pid)>> {
+    pid_list
+        .split(',')
+        .map(|entry| {
+            let (start, end) = match entry.find('-') {
+                Some(dash_index) => (&entry[..dash_index], &entry[dash_index + 1..]),
+                None => (entry, entry),
+            };
+            let start = start
+                .parse::<Pid>()
+                .map_err(|_| BottomError::InvalidPidList)?;
+            let end = end
+                .parse::<Pid>()
+                .map_err(|_| BottomError::InvalidPidList)?;
+
+            if start <= end {
+                Ok((start, end))
+            } else {
+                Err(BottomError::InvalidPidList)
+            }
+        })
+        .collect()
 }
 
 #[derive(Debug)]
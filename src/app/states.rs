@@ -1,13 +1,22 @@
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    time::Instant,
+};
 
 use unicode_segmentation::GraphemeCursor;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use tui::widgets::TableState;
 
 use crate::{
     app::{layout_manager::BottomWidgetType, query::*},
     constants,
-    data_harvester::processes::{self, ProcessSorting},
+    data_harvester::{
+        disks::DiskSorting,
+        processes::{self, ProcessSorting},
+    },
+    utils::error::BottomError,
+    Pid,
 };
 use ProcessSorting::*;
 
@@ -38,6 +47,8 @@ pub struct AppScrollWidgetState {
     pub previous_scroll_position: usize,
     pub scroll_direction: ScrollDirection,
     pub table_state: TableState,
+    /// How many rows fit in the table when it was last drawn, used to move by a page.
+    pub num_visible_rows: usize,
 }
 
 #[derive(Default)]
@@ -48,6 +59,28 @@ pub struct AppDeleteDialogState {
     pub yes_brc: Option<(u16, u16)>,
     pub no_tlc: Option<(u16, u16)>,
     pub no_brc: Option<(u16, u16)>,
+    /// Whether the dialog is letting the user pick a signal, rather than just killing.
+    pub is_choosing_signal: bool,
+    /// Only signals whose number starts with this are listed.
+    pub signal_filter: String,
+    /// The index of the selected signal, within the filtered signals.
+    pub selected_signal_index: usize,
+    /// Whether the user must press `y` to send the selected signal (ie: SIGKILL).
+    pub is_confirming_signal: bool,
+    /// Whether the dialog is acting on all tagged processes, rather than the selected one.
+    pub is_killing_tagged: bool,
+}
+
+impl AppDeleteDialogState {
+    /// The signals that can be picked, given the current filter.
+    #[cfg(target_family = "unix")]
+    pub fn get_filtered_signals(&self) -> Vec<(&'static str, i32)> {
+        crate::app::process_killer::SIGNALS
+            .iter()
+            .filter(|(_name, signal)| signal.to_string().starts_with(&self.signal_filter))
+            .copied()
+            .collect()
+    }
 }
 
 pub struct AppHelpDialogState {
@@ -111,6 +144,96 @@ impl AppSearchState {
     pub fn is_invalid_or_blank_search(&self) -> bool {
         self.is_blank_search || self.is_invalid_search
     }
+
+    /// The cursor's position in the query, in bytes.
+    pub fn get_cursor_position(&self) -> usize {
+        self.grapheme_cursor.cur_cursor()
+    }
+
+    fn walk_forward(&mut self, start_position: usize) {
+        self.grapheme_cursor
+            .next_boundary(&self.current_search_query[start_position..], start_position)
+            .unwrap();
+    }
+
+    fn walk_back(&mut self, start_position: usize) {
+        self.grapheme_cursor
+            .prev_boundary(&self.current_search_query[..start_position], 0)
+            .unwrap();
+    }
+
+    /// Inserts a character at the cursor, and moves the cursor past it.
+    pub fn insert_char(&mut self, c: char) {
+        let position = self.get_cursor_position();
+        self.current_search_query.insert(position, c);
+        self.grapheme_cursor = GraphemeCursor::new(position, self.current_search_query.len(), true);
+        self.walk_forward(position);
+        self.char_cursor_position += UnicodeWidthChar::width(c).unwrap_or(0);
+        self.cursor_direction = CursorDirection::Right;
+    }
+
+    /// Removes the character before the cursor.  Returns whether there was one.
+    pub fn backspace(&mut self) -> bool {
+        let position = self.get_cursor_position();
+        if position == 0 {
+            return false;
+        }
+
+        self.walk_back(position);
+        let position = self.get_cursor_position();
+        let removed_char = self.current_search_query.remove(position);
+        self.grapheme_cursor = GraphemeCursor::new(position, self.current_search_query.len(), true);
+        self.char_cursor_position -= UnicodeWidthChar::width(removed_char).unwrap_or(0);
+        self.cursor_direction = CursorDirection::Left;
+        true
+    }
+
+    /// Removes the character at the cursor.  Returns whether there was one.
+    pub fn delete(&mut self) -> bool {
+        let position = self.get_cursor_position();
+        if position >= self.current_search_query.len() {
+            return false;
+        }
+
+        self.current_search_query.remove(position);
+        self.grapheme_cursor = GraphemeCursor::new(position, self.current_search_query.len(), true);
+        true
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        let prev_cursor = self.get_cursor_position();
+        self.walk_back(prev_cursor);
+        let position = self.get_cursor_position();
+        if position < prev_cursor {
+            self.char_cursor_position -=
+                UnicodeWidthStr::width(&self.current_search_query[position..prev_cursor]);
+            self.cursor_direction = CursorDirection::Left;
+        }
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        let prev_cursor = self.get_cursor_position();
+        self.walk_forward(prev_cursor);
+        let position = self.get_cursor_position();
+        if position > prev_cursor {
+            self.char_cursor_position +=
+                UnicodeWidthStr::width(&self.current_search_query[prev_cursor..position]);
+            self.cursor_direction = CursorDirection::Right;
+        }
+    }
+
+    pub fn skip_cursor_to_start(&mut self) {
+        self.grapheme_cursor = GraphemeCursor::new(0, self.current_search_query.len(), true);
+        self.char_cursor_position = 0;
+        self.cursor_direction = CursorDirection::Left;
+    }
+
+    pub fn skip_cursor_to_end(&mut self) {
+        let len = self.current_search_query.len();
+        self.grapheme_cursor = GraphemeCursor::new(len, len, true);
+        self.char_cursor_position = UnicodeWidthStr::width(self.current_search_query.as_str());
+        self.cursor_direction = CursorDirection::Right;
+    }
 }
 
 /// Meant for canvas operations involving table column widths.
@@ -126,6 +249,8 @@ pub struct ProcessSearchState {
     pub is_ignoring_case: bool,
     pub is_searching_whole_word: bool,
     pub is_searching_with_regex: bool,
+    /// Set while Tab is cycling through process names.
+    pub completion: Option<SearchCompletionState>,
 }
 
 impl Default for ProcessSearchState {
@@ -135,10 +260,23 @@ impl Default for ProcessSearchState {
             is_ignoring_case: true,
             is_searching_whole_word: false,
             is_searching_with_regex: false,
+            completion: None,
         }
     }
 }
 
+/// Tracks the process names that repeated presses of Tab cycle through.
+pub struct SearchCompletionState {
+    /// The query as the last completion left it.  Any other edit starts a new completion.
+    pub completed_query: String,
+    /// Where the last completion left the cursor, in bytes.  Moving it starts a new completion.
+    pub completed_cursor: usize,
+    /// Where the completed token starts, in bytes.
+    pub token_start: usize,
+    pub candidates: Vec<String>,
+    pub index: usize,
+}
+
 impl ProcessSearchState {
     pub fn search_toggle_ignore_case(&mut self) {
         self.is_ignoring_case = !self.is_ignoring_case;
@@ -179,11 +317,16 @@ impl Default for ProcColumn {
             CpuPercent,
             Mem,
             MemPercent,
+            CpuTime,
+            StartTime,
             ReadPerSecond,
             WritePerSecond,
             TotalRead,
             TotalWrite,
+            FdCount,
+            User,
             State,
+            Container,
         ];
 
         let mut column_mapping = HashMap::new();
@@ -246,6 +389,15 @@ impl Default for ProcColumn {
                     );
                 }
                 Count => {
+                    column_mapping.insert(
+                        column,
+                        ColumnInfo {
+                            enabled: false,
+                            shortcut: Some("p"),
+                        },
+                    );
+                }
+                Container => {
                     column_mapping.insert(
                         column,
                         ColumnInfo {
@@ -265,6 +417,16 @@ impl Default for ProcColumn {
                 }
             }
         }
+        // Optional columns are only drawn if they are listed via `process_columns`.
+        for column in [ThreadCount, Nice, Age].iter() {
+            column_mapping.insert(
+                column.clone(),
+                ColumnInfo {
+                    enabled: true,
+                    shortcut: None,
+                },
+            );
+        }
         let longest_header_len = longest_header_len as u16;
 
         ProcColumn {
@@ -280,8 +442,44 @@ impl Default for ProcColumn {
     }
 }
 
-// TODO: [SORTING] Sort by clicking on column header (ie: click on cpu, sort/invert cpu sort)?
 impl ProcColumn {
+    /// Limits which columns are shown, in the given order.  Columns that are not listed are still
+    /// tracked so that toggling them does nothing harmful, but they are never drawn.
+    pub fn set_shown_columns(&mut self, shown_columns: &[ProcessSorting]) {
+        self.ordered_columns = shown_columns.to_vec();
+        self.longest_header_len = self
+            .ordered_columns
+            .iter()
+            .map(|column| column.to_string().len() as u16)
+            .max()
+            .unwrap_or(0);
+    }
+
+    pub fn is_shown(&self, column: &ProcessSorting) -> bool {
+        self.ordered_columns.contains(column)
+    }
+
+    /// Returns the columns that are currently drawn, in order.
+    pub fn get_enabled_columns(&self) -> Vec<&ProcessSorting> {
+        self.ordered_columns
+            .iter()
+            .filter(|column| self.is_enabled(column))
+            .collect()
+    }
+
+    /// The column to sort by when nothing else was picked: CPU usage if it is shown, otherwise
+    /// the first column.
+    pub fn get_default_sorting(&self) -> ProcessSorting {
+        if self.is_shown(&CpuPercent) {
+            CpuPercent
+        } else {
+            self.get_enabled_columns()
+                .first()
+                .map(|column| (*column).clone())
+                .unwrap_or(CpuPercent)
+        }
+    }
+
     /// Returns its new status.
     pub fn toggle(&mut self, column: &ProcessSorting) -> Option<bool> {
         if let Some(mapping) = self.column_mapping.get_mut(column) {
@@ -292,6 +490,17 @@ impl ProcColumn {
         }
     }
 
+    /// Returns whether this changed anything.
+    pub fn set_enabled(&mut self, column: &ProcessSorting, enabled: bool) -> bool {
+        match self.column_mapping.get_mut(column) {
+            Some(mapping) if mapping.enabled != enabled => {
+                mapping.enabled = enabled;
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn is_enabled(&self, column: &ProcessSorting) -> bool {
         if let Some(mapping) = self.column_mapping.get(column) {
             mapping.enabled
@@ -334,8 +543,10 @@ impl ProcColumn {
         self.backup_prev_scroll_position = self.previous_scroll_position;
     }
 
+    /// Returns the headers of all enabled columns.  If `name_header` is set, it replaces the
+    /// header of the name (or command) column.
     pub fn get_column_headers(
-        &self, proc_sorting_type: &ProcessSorting, sort_reverse: bool,
+        &self, proc_sorting_type: &ProcessSorting, sort_reverse: bool, name_header: Option<&str>,
     ) -> Vec<String> {
         const DOWN_ARROW: char = '▼';
         const UP_ARROW: char = '▲';
@@ -351,9 +562,14 @@ impl ProcColumn {
                 }
 
                 if mapping.enabled {
+                    let column_name = match (column_type, name_header) {
+                        (ProcessSorting::ProcessName, Some(name_header))
+                        | (ProcessSorting::Command, Some(name_header)) => name_header.to_string(),
+                        _ => column_type.to_string(),
+                    };
                     Some(format!(
                         "{}{}{}",
-                        column_type.to_string(),
+                        column_name,
                         command_str.as_str(),
                         if proc_sorting_type == column_type {
                             if sort_reverse {
@@ -373,9 +589,93 @@ impl ProcColumn {
     }
 }
 
+/// A process that the selection sticks to, regardless of sorting.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FollowedProcess {
+    Pid(Pid),
+    /// A group of processes, by the name it is grouped under.
+    Group(String),
+}
+
+/// What grouped processes are grouped by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessGroupingKey {
+    /// The process name, or the command if showing commands.
+    Name,
+    User,
+    Container,
+}
+
+impl ProcessGroupingKey {
+    /// The header to use for the name column, if it should be something other than the usual.
+    pub fn name_column_header(self) -> Option<&'static str> {
+        match self {
+            ProcessGroupingKey::Name => None,
+            ProcessGroupingKey::User => Some("User"),
+            ProcessGroupingKey::Container => Some("Container"),
+        }
+    }
+}
+
+/// Which process states are shown in a process widget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessStateFilter {
+    All,
+    Running,
+    Sleeping,
+    Zombie,
+    Stopped,
+}
+
+impl ProcessStateFilter {
+    /// The filter after this one, wrapping around to showing everything.
+    pub fn next(self) -> Self {
+        use ProcessStateFilter::*;
+        match self {
+            All => Running,
+            Running => Sleeping,
+            Sleeping => Zombie,
+            Zombie => Stopped,
+            Stopped => All,
+        }
+    }
+
+    /// Whether a process with the given state character (as in `/proc/<pid>/stat`) is shown.
+    pub fn matches(self, state_char: char) -> bool {
+        use ProcessStateFilter::*;
+        match self {
+            All => true,
+            Running => state_char == 'R',
+            // Uninterruptible (D) and idle kernel threads (I) are sleeping too.
+            Sleeping => matches!(state_char, 'S' | 'D' | 'I'),
+            Zombie => state_char == 'Z',
+            Stopped => matches!(state_char, 'T' | 't'),
+        }
+    }
+}
+
+impl std::fmt::Display for ProcessStateFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ProcessStateFilter::*;
+        write!(
+            f,
+            "{}",
+            match self {
+                All => "all",
+                Running => "running",
+                Sleeping => "sleeping",
+                Zombie => "zombie",
+                Stopped => "stopped",
+            }
+        )
+    }
+}
+
 pub struct ProcWidgetState {
     pub process_search_state: ProcessSearchState,
     pub is_grouped: bool,
+    /// What processes are grouped by.  Only meaningful if grouped.
+    pub grouping_key: ProcessGroupingKey,
     pub scroll_state: AppScrollWidgetState,
     pub process_sorting_type: processes::ProcessSorting,
     pub is_process_sort_descending: bool,
@@ -386,11 +686,26 @@ pub struct ProcWidgetState {
     pub is_tree_mode: bool,
     pub table_width_state: CanvasTableWidthState,
     pub requires_redraw: bool,
+    /// Where each enabled column header was drawn last frame, used for mouse clicks.  The right
+    /// bound is exclusive.
+    pub header_click_locs: Option<Vec<((u16, u16), (u16, u16))>>,
+    /// PIDs whose children are hidden in tree mode.
+    pub collapsed_pids: HashSet<Pid>,
+    /// Only processes in these states are shown.
+    pub state_filter: ProcessStateFilter,
+    /// Whether processes using no CPU and almost no memory are hidden.
+    pub is_hiding_idle: bool,
+    pub followed_process: Option<FollowedProcess>,
+    /// When the followed process last exited, so that the title can say so for a bit.
+    pub follow_lost_instant: Option<Instant>,
+    /// A short message about the last action, like renicing, and when it was set.
+    pub status_message: Option<(String, Instant)>,
 }
 
 impl ProcWidgetState {
     pub fn init(
         is_case_sensitive: bool, is_match_whole_word: bool, is_use_regex: bool, is_grouped: bool,
+        is_hiding_idle: bool, shown_columns: Option<&[ProcessSorting]>,
     ) -> Self {
         let mut process_search_state = ProcessSearchState::default();
         if is_case_sensitive {
@@ -404,19 +719,22 @@ impl ProcWidgetState {
             process_search_state.search_toggle_regex();
         }
 
-        let process_sorting_type = processes::ProcessSorting::CpuPercent;
-
-        // TODO: If we add customizable columns, this should pull from config
         let mut columns = ProcColumn::default();
-        columns.set_to_sorted_index(&process_sorting_type);
+        if let Some(shown_columns) = shown_columns {
+            columns.set_shown_columns(shown_columns);
+        }
         if is_grouped {
             columns.toggle(&ProcessSorting::Count);
             columns.toggle(&ProcessSorting::Pid);
         }
 
+        let process_sorting_type = columns.get_default_sorting();
+        columns.set_to_sorted_index(&process_sorting_type);
+
         ProcWidgetState {
             process_search_state,
             is_grouped,
+            grouping_key: ProcessGroupingKey::Name,
             scroll_state: AppScrollWidgetState::default(),
             process_sorting_type,
             is_process_sort_descending: true,
@@ -427,6 +745,13 @@ impl ProcWidgetState {
             is_tree_mode: false,
             table_width_state: CanvasTableWidthState::default(),
             requires_redraw: false,
+            header_click_locs: None,
+            collapsed_pids: HashSet::new(),
+            state_filter: ProcessStateFilter::All,
+            is_hiding_idle,
+            followed_process: None,
+            follow_lost_instant: None,
+            status_message: None,
         }
     }
 
@@ -437,9 +762,14 @@ impl ProcWidgetState {
     /// Sorry, future me, you're gonna have to refactor this later.  Too busy getting
     /// the feature to work in the first place!  :)
     pub fn update_sorting_with_columns(&mut self) {
+        self.update_sorting_with_column_index(self.columns.current_scroll_position);
+    }
+
+    /// Updates sorting to the column at the given index, counting only enabled columns.
+    /// Selecting the current sorting column reverses the order instead.
+    pub fn update_sorting_with_column_index(&mut self, target_itx: usize) {
         let mut true_index = 0;
         let mut enabled_index = 0;
-        let target_itx = self.columns.current_scroll_position;
         for column in &self.columns.ordered_columns {
             let enabled = self.columns.column_mapping.get(column).unwrap().enabled;
             if enabled_index == target_itx && enabled {
@@ -459,6 +789,8 @@ impl ProcWidgetState {
                 self.process_sorting_type = new_sort_type.clone();
                 match self.process_sorting_type {
                     ProcessSorting::State
+                    | ProcessSorting::User
+                    | ProcessSorting::Container
                     | ProcessSorting::Pid
                     | ProcessSorting::ProcessName
                     | ProcessSorting::Command => {
@@ -471,6 +803,28 @@ impl ProcWidgetState {
         }
     }
 
+    /// Moves sorting to the next (or previous) enabled column, wrapping around.  Unlike
+    /// selecting a column directly, this keeps the current sort direction.
+    pub fn cycle_sort_column(&mut self, forward: bool) {
+        let enabled_columns = self.columns.get_enabled_columns();
+
+        let num_columns = enabled_columns.len();
+        if num_columns == 0 {
+            return;
+        }
+
+        let new_index = match enabled_columns
+            .iter()
+            .position(|column| **column == self.process_sorting_type)
+        {
+            Some(current_index) if forward => (current_index + 1) % num_columns,
+            Some(current_index) => (current_index + num_columns - 1) % num_columns,
+            None => 0,
+        };
+        self.process_sorting_type = enabled_columns[new_index].clone();
+        self.columns.set_to_sorted_index(&self.process_sorting_type);
+    }
+
     pub fn toggle_command_and_name(&mut self, is_using_command: bool) {
         if let Some(pn) = self
             .columns
@@ -489,10 +843,7 @@ impl ProcWidgetState {
     }
 
     pub fn get_cursor_position(&self) -> usize {
-        self.process_search_state
-            .search_state
-            .grapheme_cursor
-            .cur_cursor()
+        self.process_search_state.search_state.get_cursor_position()
     }
 
     pub fn get_char_cursor_position(&self) -> usize {
@@ -508,17 +859,29 @@ impl ProcWidgetState {
     }
 
     pub fn update_query(&mut self) {
-        if self
-            .process_search_state
-            .search_state
-            .current_search_query
-            .is_empty()
-        {
+        let (is_excluding, search_query) = split_exclusion(self.get_current_search_query());
+        if search_query.is_empty() {
             self.process_search_state.search_state.is_blank_search = true;
             self.process_search_state.search_state.is_invalid_search = false;
             self.process_search_state.search_state.error_message = None;
         } else {
-            let parsed_query = self.parse_query();
+            // Anything that isn't a valid query is just treated as text to match against names,
+            // except for invalid PID lists, which are reported.
+            let parsed_query = self.parse_query().or_else(|err| {
+                if let BottomError::InvalidPidList = err {
+                    return Err(err);
+                }
+
+                Query::plain_text(
+                    search_query,
+                    self.process_search_state.is_searching_whole_word,
+                    self.process_search_state.is_ignoring_case,
+                )
+                .map(|mut query| {
+                    query.is_excluding = is_excluding;
+                    query
+                })
+            });
             // debug!("Parsed query: {:#?}", parsed_query);
 
             if let Ok(parsed_query) = parsed_query {
@@ -540,26 +903,114 @@ impl ProcWidgetState {
         self.process_search_state.search_state.reset();
     }
 
-    pub fn search_walk_forward(&mut self, start_position: usize) {
-        self.process_search_state
-            .search_state
-            .grapheme_cursor
-            .next_boundary(
-                &self.process_search_state.search_state.current_search_query[start_position..],
-                start_position,
-            )
-            .unwrap();
+    /// Replaces the current search query, moving the cursor to the end.
+    pub fn set_search_query(&mut self, query: String) {
+        let search_state = &mut self.process_search_state.search_state;
+        search_state.grapheme_cursor = GraphemeCursor::new(query.len(), query.len(), true);
+        search_state.char_cursor_position = UnicodeWidthStr::width(query.as_str());
+        search_state.cursor_direction = CursorDirection::Right;
+        search_state.current_search_query = query;
+        self.update_query();
     }
 
-    pub fn search_walk_back(&mut self, start_position: usize) {
-        self.process_search_state
-            .search_state
-            .grapheme_cursor
-            .prev_boundary(
-                &self.process_search_state.search_state.current_search_query[..start_position],
-                0,
-            )
-            .unwrap();
+    /// Completes the token at the cursor to the process names starting with what is before the
+    /// cursor: first to their longest common prefix, and then cycling through them on repeated
+    /// calls.
+    pub fn complete_search_token<'a>(&mut self, names: impl Iterator<Item = &'a str>) {
+        let query = self.get_current_search_query().clone();
+        let cursor = self.get_cursor_position();
+
+        if let Some(completion) = &mut self.process_search_state.completion {
+            if completion.completed_query == query && completion.completed_cursor == cursor {
+                completion.index = (completion.index + 1) % completion.candidates.len();
+                let token_start = completion.token_start.min(cursor);
+                let candidate = completion.candidates[completion.index].clone();
+                self.replace_search_token(token_start, cursor, &candidate);
+                let completed_query = self.get_current_search_query().clone();
+                let completed_cursor = self.get_cursor_position();
+                if let Some(completion) = &mut self.process_search_state.completion {
+                    completion.completed_query = completed_query;
+                    completion.completed_cursor = completed_cursor;
+                }
+                return;
+            }
+        }
+        self.process_search_state.completion = None;
+
+        // Tokens are split by whitespace and parentheses, like the query itself.
+        let is_token_end = |c: char| c.is_whitespace() || c == '(' || c == ')';
+        let token_start = query[..cursor]
+            .char_indices()
+            .rev()
+            .find(|(_, c)| is_token_end(*c))
+            .map(|(index, c)| index + c.len_utf8())
+            .unwrap_or(0);
+        let token_end = query[cursor..]
+            .find(is_token_end)
+            .map_or(query.len(), |index| cursor + index);
+        let token = &query[token_start..cursor];
+        if token.is_empty() {
+            return;
+        }
+
+        let is_ignoring_case = self.process_search_state.is_ignoring_case;
+        let lowercase_token = token.to_lowercase();
+        let candidates = names
+            .filter(|name| {
+                if is_ignoring_case {
+                    name.to_lowercase().starts_with(&lowercase_token)
+                } else {
+                    name.starts_with(token)
+                }
+            })
+            .map(|name| name.to_string())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let common_prefix = match candidates.split_first() {
+            Some((first, rest)) => rest.iter().fold(first.as_str(), |prefix, candidate| {
+                let len = prefix
+                    .char_indices()
+                    .zip(candidate.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map(|((index, _), _)| index)
+                    .unwrap_or_else(|| std::cmp::min(prefix.len(), candidate.len()));
+                &prefix[..len]
+            }),
+            None => return,
+        };
+
+        if candidates.len() == 1 || common_prefix.chars().count() > token.chars().count() {
+            let common_prefix = common_prefix.to_string();
+            self.replace_search_token(token_start, token_end, &common_prefix);
+        } else {
+            // The prefix is as complete as it gets, so start cycling through the candidates.
+            let candidate = candidates[0].clone();
+            self.replace_search_token(token_start, token_end, &candidate);
+            self.process_search_state.completion = Some(SearchCompletionState {
+                completed_query: self.get_current_search_query().clone(),
+                completed_cursor: self.get_cursor_position(),
+                token_start,
+                candidates,
+                index: 0,
+            });
+        }
+    }
+
+    /// Replaces the query between two byte positions, leaving the cursor after the replacement.
+    fn replace_search_token(&mut self, start: usize, end: usize, replacement: &str) {
+        let search_state = &mut self.process_search_state.search_state;
+        search_state
+            .current_search_query
+            .replace_range(start..end, replacement);
+        let cursor = start + replacement.len();
+        search_state.grapheme_cursor =
+            GraphemeCursor::new(cursor, search_state.current_search_query.len(), true);
+        search_state.char_cursor_position =
+            UnicodeWidthStr::width(&search_state.current_search_query[..cursor]);
+        search_state.cursor_direction = CursorDirection::Right;
+        self.update_query();
     }
 }
 
@@ -629,6 +1080,20 @@ pub struct CpuWidgetState {
     pub autohide_timer: Option<Instant>,
     pub scroll_state: AppScrollWidgetState,
     pub is_multi_graph_mode: bool,
+    /// Whether cores are grouped by NUMA node in the legend.
+    pub is_numa_grouped: bool,
+    /// The rows of the legend that held NUMA node separators last frame, used for mouse clicks.
+    pub numa_separator_rows: Vec<usize>,
+    /// The indices into the CPU data of the cores that are hidden from the chart.
+    pub hidden_cores: HashSet<usize>,
+    /// Whether the system and I/O wait shares of CPU usage are drawn alongside the cores.
+    pub is_showing_breakdown: bool,
+    /// Whether the chart shows the stacked split of CPU time, including interrupts, instead of
+    /// the usage of each core.
+    pub is_showing_interrupts: bool,
+    /// The index into the CPU data of the core that stays emphasized on the chart even when the
+    /// widget isn't focused.
+    pub locked_core: Option<usize>,
     pub table_width_state: CanvasTableWidthState,
 }
 
@@ -640,9 +1105,38 @@ impl CpuWidgetState {
             autohide_timer,
             scroll_state: AppScrollWidgetState::default(),
             is_multi_graph_mode: false,
+            is_numa_grouped: false,
+            numa_separator_rows: Vec::new(),
+            hidden_cores: HashSet::new(),
+            is_showing_breakdown: false,
+            is_showing_interrupts: false,
+            locked_core: None,
             table_width_state: CanvasTableWidthState::default(),
         }
     }
+
+    /// Converts a row of the legend into an offset from the selected entry, skipping over NUMA
+    /// node separators.  Returns `None` if the row is a separator.
+    pub fn get_legend_entry_offset(&self, clicked_row: usize, selected_row: usize) -> Option<i64> {
+        if self.numa_separator_rows.contains(&clicked_row) {
+            return None;
+        }
+
+        let separators_between = self
+            .numa_separator_rows
+            .iter()
+            .filter(|row| {
+                (selected_row < **row && **row < clicked_row)
+                    || (clicked_row < **row && **row < selected_row)
+            })
+            .count() as i64;
+        let offset = clicked_row as i64 - selected_row as i64;
+        Some(if offset < 0 {
+            offset + separators_between
+        } else {
+            offset - separators_between
+        })
+    }
 }
 
 pub struct CpuState {
@@ -670,6 +1164,8 @@ impl CpuState {
 pub struct MemWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    /// Whether huge page statistics are listed in the legend.
+    pub is_showing_huge_pages: bool,
 }
 
 impl MemWidgetState {
@@ -677,6 +1173,7 @@ impl MemWidgetState {
         MemWidgetState {
             current_display_time,
             autohide_timer,
+            is_showing_huge_pages: false,
         }
     }
 }
@@ -702,6 +1199,42 @@ impl MemState {
     }
 }
 
+pub struct GpuWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl GpuWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        GpuWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
+
+pub struct GpuState {
+    pub force_update: Option<u64>,
+    pub widget_states: HashMap<u64, GpuWidgetState>,
+}
+
+impl GpuState {
+    pub fn init(widget_states: HashMap<u64, GpuWidgetState>) -> Self {
+        GpuState {
+            force_update: None,
+            widget_states,
+        }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut GpuWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&GpuWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 pub struct TempWidgetState {
     pub scroll_state: AppScrollWidgetState,
     pub table_width_state: CanvasTableWidthState,
@@ -749,12 +1282,73 @@ impl DiskWidgetState {
 }
 
 pub struct DiskState {
+    pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, DiskWidgetState>,
+    /// The sort is shared by all disk widgets, as they all display the same rows.
+    pub sort_type: DiskSorting,
+    pub is_sort_descending: bool,
 }
 
 impl DiskState {
     pub fn init(widget_states: HashMap<u64, DiskWidgetState>) -> Self {
-        DiskState { widget_states }
+        DiskState {
+            force_update: None,
+            widget_states,
+            sort_type: DiskSorting::Name,
+            is_sort_descending: false,
+        }
+    }
+
+    /// Moves the sort to the next or previous sortable column.  Rates are sorted highest first.
+    pub fn cycle_sort_column(&mut self, forward: bool) {
+        const SORTABLE: [DiskSorting; 3] = [
+            DiskSorting::Name,
+            DiskSorting::ReadRate,
+            DiskSorting::WriteRate,
+        ];
+
+        let current_index = SORTABLE
+            .iter()
+            .position(|sort_type| *sort_type == self.sort_type)
+            .unwrap_or(0);
+        let new_index = if forward {
+            (current_index + 1) % SORTABLE.len()
+        } else {
+            (current_index + SORTABLE.len() - 1) % SORTABLE.len()
+        };
+
+        self.sort_type = SORTABLE[new_index];
+        self.is_sort_descending = self.sort_type != DiskSorting::Name;
+    }
+
+    /// Returns the header of each column.  Sortable columns are followed by an arrow if they are
+    /// being sorted by, or a space otherwise, so that their widths don't change.
+    pub fn get_column_headers(&self) -> Vec<String> {
+        const DOWN_ARROW: char = '▼';
+        const UP_ARROW: char = '▲';
+
+        ["Disk", "Mount", "Used", "", "Free", "Total", "R/s", "W/s"]
+            .iter()
+            .map(|header| {
+                let sort_type = match *header {
+                    "Disk" => DiskSorting::Name,
+                    "R/s" => DiskSorting::ReadRate,
+                    "W/s" => DiskSorting::WriteRate,
+                    _ => return header.to_string(),
+                };
+                format!(
+                    "{}{}",
+                    header,
+                    if sort_type != self.sort_type {
+                        ' '
+                    } else if self.is_sort_descending {
+                        DOWN_ARROW
+                    } else {
+                        UP_ARROW
+                    }
+                )
+            })
+            .collect()
     }
 
     pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut DiskWidgetState> {
@@ -802,6 +1396,232 @@ impl BatteryState {
     }
 }
 
+/// AppReniceDialogState deals with the dialog for setting the nice value of the selected process
+/// (or every process in the selected group).
+#[derive(Default)]
+pub struct AppReniceDialogState {
+    pub is_showing_renice: bool,
+    /// The name of the process (or group) being reniced.
+    pub name: String,
+    pub pids: Vec<Pid>,
+    /// What the processes are grouped by, if a group is being reniced.
+    pub grouping_key: Option<ProcessGroupingKey>,
+    /// Whether the tagged processes are being reniced, rather than the selected one.
+    pub is_renicing_tagged: bool,
+    /// Whether negative nice values are allowed, which is checked once when the dialog opens.
+    pub can_lower_nice: bool,
+    /// The nice value typed in so far.
+    pub input: String,
+    /// Set if the last attempt to renice failed; the dialog then only shows the error.
+    pub error: Option<String>,
+}
+
+impl AppReniceDialogState {
+    pub fn open(
+        &mut self, name: String, pids: Vec<Pid>, nice: Option<i32>,
+        grouping_key: Option<ProcessGroupingKey>,
+    ) {
+        *self = AppReniceDialogState {
+            is_showing_renice: true,
+            name,
+            pids,
+            grouping_key,
+            is_renicing_tagged: false,
+            can_lower_nice: crate::app::process_killer::can_lower_nice(),
+            input: nice.map(|nice| nice.to_string()).unwrap_or_default(),
+            error: None,
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = AppReniceDialogState::default();
+    }
+
+    /// The typed nice value, if it is a valid one (from -20 to 19).
+    pub fn get_nice(&self) -> Option<i32> {
+        self.input
+            .parse::<i32>()
+            .ok()
+            .filter(|nice| (-20..=19).contains(nice))
+    }
+}
+
+/// AppUserFilterState deals with the input box for only showing processes owned by a user.
+#[derive(Default)]
+pub struct AppUserFilterState {
+    pub is_showing_input: bool,
+    /// The username typed in so far.
+    pub input: String,
+}
+
+impl AppUserFilterState {
+    pub fn open(&mut self, input: String) {
+        *self = AppUserFilterState {
+            is_showing_input: true,
+            input,
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = AppUserFilterState::default();
+    }
+}
+
+/// AppProcessInfoDialogState deals with the dialog showing extra information about a single
+/// process, like its environment variables.
+#[derive(Default)]
+pub struct AppProcessInfoDialogState {
+    pub is_showing_info: bool,
+    pub title: String,
+    /// An optional line drawn above the entries, like column names.  It is never filtered.
+    pub header: Option<String>,
+    pub entries: Vec<String>,
+    /// Entries to draw highlighted, like the affinity of a process pinned to a single core.
+    pub highlighted_entries: Vec<String>,
+    /// Only entries matching this search are shown.  It uses the same options as the process
+    /// search, but matches whole entries rather than processes.
+    pub filter: ProcessSearchState,
+    filter_regex: Option<regex::Regex>,
+    pub scroll_state: ParagraphScrollState,
+    /// How far the entries are scrolled to the right, for long lines like command lines.
+    pub horizontal_scroll_state: ParagraphScrollState,
+    /// If the dialog is showing process details, the PID and start time of that process, so that
+    /// the details can be refreshed.  The start time tells apart a reused PID.
+    pub details_of: Option<(Pid, Option<u64>)>,
+}
+
+impl AppProcessInfoDialogState {
+    pub fn open(&mut self, title: String, entries: Vec<String>) {
+        self.open_with_header(title, None, entries);
+    }
+
+    pub fn open_with_header(
+        &mut self, title: String, header: Option<String>, entries: Vec<String>,
+    ) {
+        *self = AppProcessInfoDialogState {
+            is_showing_info: true,
+            title,
+            header,
+            entries,
+            ..AppProcessInfoDialogState::default()
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = AppProcessInfoDialogState::default();
+    }
+
+    /// Rebuilds the filter after its text or options changed.  Until the filter is fixed, an
+    /// invalid one shows every entry, like a blank one.
+    pub fn update_filter(&mut self) {
+        let search_state = &mut self.filter.search_state;
+        search_state.is_blank_search = search_state.current_search_query.is_empty();
+        let filter_regex = if search_state.is_blank_search {
+            Ok(None)
+        } else {
+            build_search_regex(
+                &search_state.current_search_query,
+                self.filter.is_searching_whole_word,
+                self.filter.is_ignoring_case,
+                self.filter.is_searching_with_regex,
+            )
+            .map(Some)
+        };
+
+        match filter_regex {
+            Ok(filter_regex) => {
+                self.filter_regex = filter_regex;
+                search_state.is_invalid_search = false;
+                search_state.error_message = None;
+            }
+            Err(err) => {
+                self.filter_regex = None;
+                search_state.is_invalid_search = true;
+                search_state.error_message = Some(err.to_string());
+            }
+        }
+        self.scroll_state.current_scroll_index = 0;
+    }
+
+    pub fn get_filtered_entries(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|entry| match &self.filter_regex {
+                Some(filter_regex) => filter_regex.is_match(entry),
+                None => true,
+            })
+            .map(|entry| entry.as_str())
+            .collect()
+    }
+}
+
+/// Previously submitted process searches, shared between all process widgets.
+#[derive(Default)]
+pub struct SearchHistory {
+    entries: VecDeque<String>,
+    /// How far back from the newest entry we currently are, if browsing the history.
+    position: Option<usize>,
+    /// Whatever was typed before browsing the history started.
+    draft: String,
+}
+
+impl SearchHistory {
+    /// Adds a query to the history, skipping blank queries and consecutive duplicates.
+    pub fn push(&mut self, query: &str) {
+        self.position = None;
+        self.draft.clear();
+
+        if query.trim().is_empty() || self.entries.back().map(String::as_str) == Some(query) {
+            return;
+        }
+
+        self.entries.push_back(query.to_string());
+        if self.entries.len() > constants::MAX_SEARCH_HISTORY_LENGTH {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Moves to an older entry, remembering the current query if we weren't already browsing.
+    pub fn older(&mut self, current_query: &str) -> Option<&str> {
+        let position = match self.position {
+            Some(position) if position + 1 < self.entries.len() => position + 1,
+            Some(_) => return None,
+            None if !self.entries.is_empty() => {
+                self.draft = current_query.to_string();
+                0
+            }
+            None => return None,
+        };
+
+        self.position = Some(position);
+        self.entries
+            .get(self.entries.len() - 1 - position)
+            .map(String::as_str)
+    }
+
+    /// Moves to a newer entry, or back to the draft if we were on the newest one.
+    pub fn newer(&mut self) -> Option<&str> {
+        match self.position? {
+            0 => {
+                self.position = None;
+                Some(self.draft.as_str())
+            }
+            position => {
+                self.position = Some(position - 1);
+                self.entries
+                    .get(self.entries.len() - position)
+                    .map(String::as_str)
+            }
+        }
+    }
+
+    /// Stops browsing, returning what was typed before browsing started.
+    pub fn stop_browsing(&mut self) -> Option<String> {
+        self.position.take()?;
+        Some(std::mem::take(&mut self.draft))
+    }
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
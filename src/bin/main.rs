@@ -3,15 +3,23 @@
 #[macro_use]
 extern crate log;
 
-use bottom::{canvas, constants::*, data_conversion::*, options::*, *};
+use bottom::{
+    canvas,
+    constants::*,
+    data_conversion::*,
+    options::*,
+    utils::exporters::{influx::InfluxExporter, prometheus::PrometheusExporter},
+    *,
+};
 
 use std::{
     boxed::Box,
     io::{stdout, Write},
+    net::{IpAddr, SocketAddr},
     panic,
     sync::{
         atomic::{AtomicBool, Ordering},
-        mpsc, Arc,
+        mpsc, Arc, RwLock,
     },
     thread,
     time::Duration,
@@ -52,12 +60,22 @@ fn main() -> Result<()> {
         config_path,
     )?;
 
+    if matches.is_present("json") {
+        print_json_snapshot(&app.app_config_fields)?;
+        return Ok(());
+    } else if matches.is_present("server") {
+        let address = get_server_address(&matches, &config)?;
+        let port = get_server_port(&matches)?;
+        return run_server(&app.app_config_fields, address, port);
+    }
+
     // Create painter and set colours.
     let mut painter = canvas::Painter::init(
         widget_layout,
         app.app_config_fields.table_gap,
         app.app_config_fields.use_basic_mode,
         &config,
+        get_palette(&matches),
     )?;
 
     // Set up input handling
@@ -77,12 +95,30 @@ fn main() -> Result<()> {
         });
     }
 
+    let influx_exporter = app
+        .app_config_fields
+        .influx_target
+        .clone()
+        .map(InfluxExporter::spawn);
+    let prometheus_exporter = match get_prometheus_port(&matches)? {
+        Some(port) => Some(PrometheusExporter::spawn(
+            get_server_address(&matches, &config)?,
+            port,
+        )?),
+        None => None,
+    };
+
     // Event loop
     let (reset_sender, reset_receiver) = mpsc::channel();
+    // The average CPU can be toggled at runtime, so always collect it and leave it to the
+    // conversion to drop it.
     create_collection_thread(
         sender,
         reset_receiver,
-        &app.app_config_fields,
+        &app::AppConfigFields {
+            show_average_cpu: true,
+            ..app.app_config_fields.clone()
+        },
         app.used_widgets.clone(),
     );
 
@@ -121,7 +157,18 @@ fn main() -> Result<()> {
                     handle_force_redraws(&mut app);
                 }
                 BottomEvent::Update(data) => {
+                    if let Some(influx_exporter) = &influx_exporter {
+                        influx_exporter.export(&data);
+                    }
                     app.data_collection.eat_data(&data);
+                    if let Some(prometheus_exporter) = &prometheus_exporter {
+                        prometheus_exporter.update(&app.data_collection);
+                    }
+                    app.prune_exited_processes();
+                    if !app.is_frozen {
+                        app.update_process_details();
+                    }
+                    app.update_export_status();
 
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
@@ -140,11 +187,15 @@ fn main() -> Result<()> {
                                 false,
                                 app.app_config_fields.use_basic_mode
                                     || app.app_config_fields.use_old_network_legend,
+                                app.app_config_fields.network_thresholds,
                             );
                             app.canvas_data.network_data_rx = network_data.rx;
                             app.canvas_data.network_data_tx = network_data.tx;
+                            app.canvas_data.rx_threshold = network_data.rx_threshold;
+                            app.canvas_data.tx_threshold = network_data.tx_threshold;
                             app.canvas_data.rx_display = network_data.rx_display;
                             app.canvas_data.tx_display = network_data.tx_display;
+                            app.canvas_data.packets_display = network_data.packets_display;
                             if let Some(total_rx_display) = network_data.total_rx_display {
                                 app.canvas_data.total_rx_display = total_rx_display;
                             }
@@ -155,8 +206,18 @@ fn main() -> Result<()> {
 
                         // Disk
                         if app.used_widgets.use_disk {
-                            app.canvas_data.disk_data =
-                                convert_disk_row(&app.data_collection, &app.filters.disk_filter);
+                            app.canvas_data.disk_data = convert_disk_row(
+                                &app.data_collection,
+                                &app.filters.disk_filter,
+                                app.disk_state.sort_type,
+                                app.disk_state.is_sort_descending,
+                                app.app_config_fields.disk_io_threshold,
+                                app.app_config_fields.disk_usage_thresholds,
+                            );
+                            app.canvas_data.disk_io_total_display = convert_disk_io_total(
+                                &app.data_collection,
+                                &app.filters.disk_filter,
+                            );
                         }
 
                         // Temperatures
@@ -173,14 +234,45 @@ fn main() -> Result<()> {
                             let memory_and_swap_labels = convert_mem_labels(&app.data_collection);
                             app.canvas_data.mem_label_percent = memory_and_swap_labels.0;
                             app.canvas_data.mem_label_frac = memory_and_swap_labels.1;
-                            app.canvas_data.swap_label_percent = memory_and_swap_labels.2;
-                            app.canvas_data.swap_label_frac = memory_and_swap_labels.3;
+                            app.canvas_data.swap_labels = memory_and_swap_labels.2;
+                            app.canvas_data.arc_data =
+                                convert_arc_data_points(&app.data_collection, false);
+                            app.canvas_data.arc_labels = convert_arc_labels(&app.data_collection);
+                            app.canvas_data.huge_page_labels =
+                                convert_huge_page_labels(&app.data_collection);
+                            app.canvas_data.thp_labels = convert_thp_labels(&app.data_collection);
+                            app.canvas_data.swap_io_label =
+                                convert_swap_io_label(&app.data_collection);
                         }
 
                         if app.used_widgets.use_cpu {
                             // CPU
-                            app.canvas_data.cpu_data =
-                                convert_cpu_data_points(&app.data_collection, false);
+                            app.canvas_data.cpu_data = convert_cpu_data_points(
+                                &app.data_collection,
+                                false,
+                                app.app_config_fields.show_average_cpu,
+                            );
+                            app.canvas_data.load_avg_label =
+                                convert_load_avg_label(&app.data_collection);
+                            let (system, iowait) =
+                                convert_cpu_breakdown_points(&app.data_collection, false);
+                            app.canvas_data.cpu_system_data = system;
+                            app.canvas_data.cpu_iowait_data = iowait;
+                            app.canvas_data.cpu_breakdown_label =
+                                convert_cpu_breakdown_label(&app.data_collection);
+                            app.canvas_data.cpu_stacked_data =
+                                convert_cpu_stacked_points(&app.data_collection, false);
+                            app.canvas_data.cpu_interrupt_label =
+                                convert_cpu_interrupt_label(&app.data_collection);
+                        }
+
+                        // GPU
+                        if app.used_widgets.use_gpu {
+                            app.canvas_data.gpu_data = convert_gpu_data_points(
+                                &app.data_collection,
+                                false,
+                                &app.app_config_fields.temperature_type,
+                            );
                         }
 
                         // Processes
@@ -193,6 +285,11 @@ fn main() -> Result<()> {
                             app.canvas_data.battery_data =
                                 convert_battery_harvest(&app.data_collection);
                         }
+
+                        // Sockets
+                        if app.used_widgets.use_sockets {
+                            app.canvas_data.socket_data = convert_socket_data(&app.data_collection);
+                        }
                     }
                 }
                 BottomEvent::Clean => {
@@ -209,3 +306,83 @@ fn main() -> Result<()> {
     cleanup_terminal(&mut terminal)?;
     Ok(())
 }
+
+/// Runs bottom headlessly, serving the latest collected data over HTTP rather than drawing it.
+fn run_server(app_config_fields: &app::AppConfigFields, address: IpAddr, port: u16) -> Result<()> {
+    const NUM_SERVER_THREADS: usize = 4;
+
+    let socket_address = SocketAddr::new(address, port);
+    let server = Arc::new(tiny_http::Server::http(socket_address).map_err(|err| {
+        anyhow::anyhow!("Unable to start the server on {}: {}", socket_address, err)
+    })?);
+
+    // The collector swaps in a freshly serialized snapshot each update, so readers only hold the
+    // lock long enough to clone the pointer and never block the collector on a slow client.
+    let metrics = Arc::new(RwLock::new(Arc::new(String::from("{}"))));
+
+    for _ in 0..NUM_SERVER_THREADS {
+        let server = server.clone();
+        let metrics = metrics.clone();
+        thread::spawn(move || {
+            let json_header =
+                tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                    .unwrap();
+
+            for request in server.incoming_requests() {
+                let body = match (request.method(), request.url()) {
+                    (tiny_http::Method::Get, "/metrics") => Some(match metrics.read() {
+                        Ok(metrics) => metrics.clone(),
+                        Err(_) => continue,
+                    }),
+                    (tiny_http::Method::Get, "/health") => {
+                        Some(Arc::new(String::from(r#"{"status":"ok"}"#)))
+                    }
+                    _ => None,
+                };
+
+                let result = match body {
+                    Some(body) => request.respond(
+                        tiny_http::Response::from_string(body.as_str())
+                            .with_header(json_header.clone()),
+                    ),
+                    None => request.respond(
+                        tiny_http::Response::from_string("Not found").with_status_code(404),
+                    ),
+                };
+                if let Err(err) = result {
+                    debug!("Failed to respond to a request: {}", err);
+                }
+            }
+        });
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let (_reset_sender, reset_receiver) = mpsc::channel();
+    create_collection_thread(
+        sender,
+        reset_receiver,
+        app_config_fields,
+        app::layout_manager::UsedWidgets::all(),
+    );
+
+    let influx_exporter = app_config_fields
+        .influx_target
+        .clone()
+        .map(InfluxExporter::spawn);
+
+    println!("Serving metrics on http://{}/metrics", socket_address);
+
+    while let Ok(event) = receiver.recv() {
+        if let BottomEvent::Update(data) = event {
+            if let Some(influx_exporter) = &influx_exporter {
+                influx_exporter.export(&data);
+            }
+            let snapshot = Arc::new(serde_json::to_string(&*data)?);
+            if let Ok(mut metrics) = metrics.write() {
+                *metrics = snapshot;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -6,8 +6,10 @@ use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
     text::{Span, Spans},
+    widgets::Paragraph,
     Frame, Terminal,
 };
+use unicode_width::UnicodeWidthStr;
 
 use canvas_colours::*;
 use dialogs::*;
@@ -21,7 +23,10 @@ use crate::{
         App,
     },
     constants::*,
-    data_conversion::{ConvertedBatteryData, ConvertedCpuData, ConvertedProcessData},
+    data_conversion::{
+        ConvertedBatteryData, ConvertedCpuData, ConvertedGpuData, ConvertedProcessData,
+        DiskUsageLevel, NetworkThresholdLine, ProcessCount, TemperatureLevel,
+    },
     options::Config,
     utils::error,
 };
@@ -43,19 +48,43 @@ pub struct DisplayableData {
     pub total_tx_display: String,
     pub network_data_rx: Vec<Point>,
     pub network_data_tx: Vec<Point>,
-    pub disk_data: Vec<Vec<String>>,
-    pub temp_sensor_data: Vec<Vec<String>>,
-    pub single_process_data: Vec<ConvertedProcessData>, // Contains single process data
+    pub rx_threshold: Option<NetworkThresholdLine>,
+    pub tx_threshold: Option<NetworkThresholdLine>,
+    pub packets_display: String,
+    pub disk_data: Vec<(Vec<String>, bool, DiskUsageLevel)>, // Represents the row, whether it is above the I/O threshold, and how full it is
+    pub disk_io_total_display: String,
+    pub temp_sensor_data: Vec<(Vec<String>, TemperatureLevel)>, // Represents the row and how hot it is
+    pub single_process_data: Vec<ConvertedProcessData>,         // Contains single process data
     pub finalized_process_data_map: HashMap<u64, Vec<ConvertedProcessData>>, // What's actually displayed
     pub stringified_process_data_map: HashMap<u64, Vec<(Vec<(String, Option<String>)>, bool)>>, // Represents the row and whether it is disabled
+    pub process_count_map: HashMap<u64, ProcessCount>,
     pub mem_label_percent: String,
-    pub swap_label_percent: String,
     pub mem_label_frac: String,
-    pub swap_label_frac: String,
+    /// The percentage and fraction labels of swap, if the system has any.
+    pub swap_labels: Option<(String, String)>,
+    /// The percentage and fraction labels of the ZFS ARC, if there is one.
+    pub arc_labels: Option<(String, String)>,
+    pub huge_page_labels: Option<(String, String)>,
+    pub thp_labels: Option<(String, String)>,
+    pub swap_io_label: Option<String>,
     pub mem_data: Vec<Point>,
     pub swap_data: Vec<Point>,
+    pub arc_data: Vec<Point>,
     pub cpu_data: Vec<ConvertedCpuData>,
+    /// The load averages, if the platform has them.
+    pub load_avg_label: Option<String>,
+    /// The system and I/O wait CPU usage, and a label breaking down CPU usage, if the platform
+    /// reports them.
+    pub cpu_system_data: Vec<Point>,
+    pub cpu_iowait_data: Vec<Point>,
+    pub cpu_breakdown_label: Option<String>,
+    /// The layers of the stacked split of CPU time, and a label with every state including
+    /// interrupts, if the platform reports them.
+    pub cpu_stacked_data: Vec<Vec<Point>>,
+    pub cpu_interrupt_label: Option<String>,
     pub battery_data: Vec<ConvertedBatteryData>,
+    pub gpu_data: Vec<ConvertedGpuData>,
+    pub socket_data: Vec<String>,
 }
 
 /// Handles the canvas' state.  TODO: [OPT] implement this.
@@ -77,6 +106,7 @@ pub struct Painter {
 impl Painter {
     pub fn init(
         widget_layout: BottomLayout, table_gap: u16, is_basic_mode: bool, config: &Config,
+        palette: Option<Vec<String>>,
     ) -> anyhow::Result<Self> {
         // Now for modularity; we have to also initialize the base layouts!
         // We want to do this ONCE and reuse; after this we can just construct
@@ -161,6 +191,12 @@ impl Painter {
         };
 
         painter.generate_config_colours(config)?;
+        if let Some(palette) = &palette {
+            painter
+                .colours
+                .set_palette(palette)
+                .context("Update '--colors'.")?;
+        }
         painter.colours.generate_remaining_cpu_colours();
         painter.complete_painter_init();
 
@@ -168,7 +204,20 @@ impl Painter {
     }
 
     pub fn generate_config_colours(&mut self, config: &Config) -> anyhow::Result<()> {
-        if let Some(colours) = &config.colors {
+        // Colours set directly take priority over the theme.
+        let colours = match &config.theme {
+            Some(theme) => {
+                let theme_colours =
+                    get_theme_colours(theme).context("Update 'theme' in your config file.")?;
+                Some(match &config.colors {
+                    Some(colours) => colours.clone().or(theme_colours),
+                    None => theme_colours,
+                })
+            }
+            None => config.colors.clone(),
+        };
+
+        if let Some(colours) = &colours {
             if let Some(border_color) = &colours.border_color {
                 self.colours
                     .set_border_colour(border_color)
@@ -217,6 +266,12 @@ impl Painter {
                     .context("Update 'swap_color' in your config file..")?;
             }
 
+            if let Some(arc_color) = &colours.arc_color {
+                self.colours
+                    .set_arc_colour(arc_color)
+                    .context("Update 'arc_color' in your config file..")?;
+            }
+
             if let Some(rx_color) = &colours.rx_color {
                 self.colours
                     .set_rx_colour(rx_color)
@@ -267,6 +322,22 @@ impl Painter {
                     .context("Update 'graph_color' in your config file..")?;
             }
 
+            if colours.high_battery_color.is_some()
+                || colours.medium_battery_color.is_some()
+                || colours.low_battery_color.is_some()
+            {
+                self.colours
+                    .set_battery_level_colours(
+                        colours.high_battery_color.as_deref(),
+                        colours.medium_battery_color.as_deref(),
+                        colours.low_battery_color.as_deref(),
+                    )
+                    .context(
+                        "Update 'high_battery_color', 'medium_battery_color', or 'low_battery_color' in your config file.",
+                    )?;
+            }
+
+            // An explicit list of battery colours overrides the per-level ones.
             if let Some(battery_colors) = &colours.battery_colors {
                 self.colours
                     .set_battery_colors(battery_colors)
@@ -350,6 +421,11 @@ impl Painter {
                 for battery_widget in app_state.battery_state.widget_states.values_mut() {
                     battery_widget.tab_click_locs = None;
                 }
+
+                // And process headers...
+                for proc_widget in app_state.proc_state.widget_states.values_mut() {
+                    proc_widget.header_click_locs = None;
+                }
             }
 
             if app_state.help_dialog_state.is_showing_help {
@@ -389,6 +465,77 @@ impl Painter {
                     .split(vertical_dialog_chunk[1]);
 
                 self.draw_help_dialog(&mut f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.process_info_dialog_state.is_showing_info {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(
+                        [
+                            Constraint::Percentage(10),
+                            Constraint::Percentage(80),
+                            Constraint::Percentage(10),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(terminal_size);
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(
+                        if terminal_width < 100 {
+                            [
+                                Constraint::Percentage(0),
+                                Constraint::Percentage(100),
+                                Constraint::Percentage(0),
+                            ]
+                        } else {
+                            [
+                                Constraint::Percentage(15),
+                                Constraint::Percentage(70),
+                                Constraint::Percentage(15),
+                            ]
+                        }
+                        .as_ref(),
+                    )
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_process_info_dialog(f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.renice_dialog_state.is_showing_renice {
+                let (text_width, text_height) = (
+                    if terminal_width < 100 {
+                        terminal_width * 90 / 100
+                    } else {
+                        terminal_width * 50 / 100
+                    },
+                    7,
+                );
+
+                let vertical_bordering = terminal_height.saturating_sub(text_height) / 2;
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(
+                        [
+                            Constraint::Length(vertical_bordering),
+                            Constraint::Length(text_height),
+                            Constraint::Length(vertical_bordering),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(terminal_size);
+
+                let horizontal_bordering = terminal_width.saturating_sub(text_width) / 2;
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(
+                        [
+                            Constraint::Length(horizontal_bordering),
+                            Constraint::Length(text_width),
+                            Constraint::Length(horizontal_bordering),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_renice_dialog(f, app_state, middle_dialog_chunk[1]);
             } else if app_state.delete_dialog_state.is_showing_dd {
                 // TODO: This needs the paragraph wrap feature from tui-rs to be pushed to complete... but for now it's pretty close!
                 // The main problem right now is that I cannot properly calculate the height offset since
@@ -404,7 +551,18 @@ impl Painter {
                     } else {
                         terminal_width * 50 / 100
                     },
-                    7,
+                    if app_state.delete_dialog_state.is_choosing_signal {
+                        // Leave room for the list of signals.
+                        std::cmp::max(
+                            dd_text
+                                .as_ref()
+                                .map(|dd_text| dd_text.height() as u16 + 2)
+                                .unwrap_or(7),
+                            7,
+                        )
+                    } else {
+                        7
+                    },
                 );
                 // let (text_width, text_height) = if let Some(dd_text) = &dd_text {
                 //     let width = if current_width < 100 {
@@ -522,12 +680,24 @@ impl Painter {
                         self.draw_process_features(&mut f, app_state, rect[0], true, widget_id);
                     }
                     Battery => self.draw_battery_display(
-                        &mut f,
+                        f,
                         app_state,
                         rect[0],
                         true,
                         app_state.current_widget.widget_id,
                     ),
+                    Gpu => self.draw_gpu_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
+                    Sockets => self.draw_socket_summary(
+                        &mut f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     _ => {}
                 }
             } else if app_state.is_config_open {
@@ -546,12 +716,11 @@ impl Painter {
                     .constraints(
                         [
                             Constraint::Length(
-                                (app_state.canvas_data.cpu_data.len() / 4) as u16
-                                    + (if app_state.canvas_data.cpu_data.len() % 4 == 0 {
-                                        0
-                                    } else {
-                                        1
-                                    }),
+                                get_basic_cpu_layout(
+                                    app_state.canvas_data.cpu_data.len().saturating_sub(1),
+                                    terminal_size.width,
+                                )
+                                .1 as u16,
                             ),
                             Constraint::Length(1),
                             Constraint::Length(2),
@@ -715,6 +884,19 @@ impl Painter {
                         });
                 }
             }
+
+            // Show any export status over the bottom border.
+            if let Some((message, _)) = &app_state.export_toast {
+                let text = format!(" {} ", message);
+                let width =
+                    std::cmp::min(UnicodeWidthStr::width(text.as_str()) as u16, terminal_width);
+                if terminal_height > 0 && width > 0 {
+                    f.render_widget(
+                        Paragraph::new(Span::styled(text, self.colours.highlighted_border_style)),
+                        Rect::new(terminal_width - width, terminal_height - 1, width, 1),
+                    );
+                }
+            }
         })?;
 
         app_state.is_force_redraw = false;
@@ -754,6 +936,10 @@ impl Painter {
                     true,
                     widget.widget_id,
                 ),
+                Gpu => self.draw_gpu_graph(f, app_state, *widget_draw_loc, widget.widget_id),
+                Sockets => {
+                    self.draw_socket_summary(f, app_state, *widget_draw_loc, widget.widget_id)
+                }
                 _ => {}
             }
         }
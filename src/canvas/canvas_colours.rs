@@ -1,10 +1,12 @@
-use tui::style::{Color, Style};
+use tui::style::{Color, Modifier, Style};
 
 use colour_utils::*;
+pub use themes::get_theme_colours;
 
 use crate::{constants::*, utils::error};
 
 mod colour_utils;
+mod themes;
 
 pub struct CanvasColours {
     pub currently_selected_text_colour: Color,
@@ -13,6 +15,7 @@ pub struct CanvasColours {
     pub table_header_style: Style,
     pub ram_style: Style,
     pub swap_style: Style,
+    pub arc_style: Style,
     pub rx_style: Style,
     pub tx_style: Style,
     pub total_rx_style: Style,
@@ -20,6 +23,12 @@ pub struct CanvasColours {
     pub all_colour_style: Style,
     pub avg_colour_style: Style,
     pub cpu_colour_styles: Vec<Style>,
+    pub cpu_system_style: Style,
+    pub cpu_iowait_style: Style,
+    pub cpu_user_style: Style,
+    pub cpu_irq_style: Style,
+    pub cpu_softirq_style: Style,
+    pub cpu_steal_style: Style,
     pub border_style: Style,
     pub highlighted_border_style: Style,
     pub text_style: Style,
@@ -29,6 +38,16 @@ pub struct CanvasColours {
     pub battery_bar_styles: Vec<Style>,
     pub invalid_query_style: Style,
     pub disabled_text_style: Style,
+    pub search_match_style: Style,
+    pub tagged_process_style: Style,
+    pub zombie_process_style: Style,
+    pub sleeping_process_style: Style,
+    pub high_disk_io_style: Style,
+    // Normal, High, Critical
+    pub disk_usage_bar_styles: Vec<Style>,
+    pub high_temperature_style: Style,
+    pub critical_temperature_style: Style,
+    pub network_threshold_exceeded_style: Style,
 }
 
 impl Default for CanvasColours {
@@ -44,6 +63,7 @@ impl Default for CanvasColours {
             table_header_style: Style::default().fg(STANDARD_HIGHLIGHT_COLOUR),
             ram_style: Style::default().fg(STANDARD_FIRST_COLOUR),
             swap_style: Style::default().fg(STANDARD_SECOND_COLOUR),
+            arc_style: Style::default().fg(STANDARD_THIRD_COLOUR),
             rx_style: Style::default().fg(STANDARD_FIRST_COLOUR),
             tx_style: Style::default().fg(STANDARD_SECOND_COLOUR),
             total_rx_style: Style::default().fg(STANDARD_THIRD_COLOUR),
@@ -51,6 +71,12 @@ impl Default for CanvasColours {
             all_colour_style: Style::default().fg(ALL_COLOUR),
             avg_colour_style: Style::default().fg(AVG_COLOUR),
             cpu_colour_styles: Vec::new(),
+            cpu_system_style: Style::default().fg(Color::Yellow),
+            cpu_iowait_style: Style::default().fg(Color::Red),
+            cpu_user_style: Style::default().fg(Color::LightGreen),
+            cpu_irq_style: Style::default().fg(Color::Magenta),
+            cpu_softirq_style: Style::default().fg(Color::LightMagenta),
+            cpu_steal_style: Style::default().fg(Color::Cyan),
             border_style: Style::default().fg(text_colour),
             highlighted_border_style: Style::default().fg(STANDARD_HIGHLIGHT_COLOUR),
             text_style: Style::default().fg(text_colour),
@@ -66,6 +92,26 @@ impl Default for CanvasColours {
             ],
             invalid_query_style: Style::default().fg(tui::style::Color::Red),
             disabled_text_style: Style::default().fg(Color::DarkGray),
+            search_match_style: Style::default()
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            tagged_process_style: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            zombie_process_style: Style::default().fg(Color::Red),
+            sleeping_process_style: Style::default().add_modifier(Modifier::DIM),
+            high_disk_io_style: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            disk_usage_bar_styles: vec![
+                Style::default().fg(Color::Green),
+                Style::default().fg(Color::Yellow),
+                Style::default().fg(Color::Red),
+            ],
+            high_temperature_style: Style::default().fg(Color::Yellow),
+            critical_temperature_style: Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+            network_threshold_exceeded_style: Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD),
         }
     }
 }
@@ -103,6 +149,11 @@ impl CanvasColours {
         Ok(())
     }
 
+    pub fn set_arc_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.arc_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
     pub fn set_rx_colour(&mut self, colour: &str) -> error::Result<()> {
         self.rx_style = get_style_from_config(colour)?;
         Ok(())
@@ -134,6 +185,7 @@ impl CanvasColours {
     }
 
     pub fn set_cpu_colours(&mut self, colours: &[String]) -> error::Result<()> {
+        self.cpu_colour_styles.clear();
         let max_amount = std::cmp::min(colours.len(), NUM_COLOURS);
         for (itx, colour) in colours.iter().enumerate() {
             if itx >= max_amount {
@@ -144,10 +196,30 @@ impl CanvasColours {
         Ok(())
     }
 
+    /// Uses the given colours for CPU cores, and their first two for RAM and swap, and RX and TX.
+    /// A single colour is used for both of each pair.
+    pub fn set_palette(&mut self, colours: &[String]) -> error::Result<()> {
+        self.set_cpu_colours(colours)?;
+        if let Some(first_style) = self.cpu_colour_styles.first().copied() {
+            let second_style = self
+                .cpu_colour_styles
+                .get(1)
+                .copied()
+                .unwrap_or(first_style);
+            self.ram_style = first_style;
+            self.swap_style = second_style;
+            self.rx_style = first_style;
+            self.tx_style = second_style;
+        }
+        Ok(())
+    }
+
+    /// Generates the CPU colours if none were set.  Set colours are used as they are, and cycled
+    /// through if there are more cores than colours.
     pub fn generate_remaining_cpu_colours(&mut self) {
-        let remaining_num_colours = NUM_COLOURS.saturating_sub(self.cpu_colour_styles.len());
-        self.cpu_colour_styles
-            .extend(gen_n_styles(remaining_num_colours));
+        if self.cpu_colour_styles.is_empty() {
+            self.cpu_colour_styles.extend(gen_n_styles(NUM_COLOURS));
+        }
     }
 
     pub fn set_scroll_entry_text_color(&mut self, colour: &str) -> error::Result<()> {
@@ -191,4 +263,21 @@ impl CanvasColours {
             Ok(())
         }
     }
+
+    /// Sets the battery bar colours by charge level, falling back to the defaults for any level
+    /// that isn't given.
+    pub fn set_battery_level_colours(
+        &mut self, high: Option<&str>, medium: Option<&str>, low: Option<&str>,
+    ) -> error::Result<()> {
+        let get_style = |colour: Option<&str>, default: Color| match colour {
+            Some(colour) => get_style_from_config(colour),
+            None => Ok(Style::default().fg(default)),
+        };
+        let high = get_style(high, Color::Green)?;
+        let medium = get_style(medium, Color::Yellow)?;
+        let low = get_style(low, Color::Red)?;
+
+        self.battery_bar_styles = vec![low, medium, medium, high, high, high];
+        Ok(())
+    }
 }
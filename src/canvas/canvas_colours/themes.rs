@@ -0,0 +1,98 @@
+//! Built-in colour themes, which can be selected with `theme = "<name>"` in the config file.
+//! These are just preset `[colors]` tables; anything set in `[colors]` takes priority.
+
+use crate::{
+    options::ConfigColours,
+    utils::error::{self, BottomError},
+};
+
+pub const THEME_NAMES: [&str; 4] = ["default", "gruvbox", "nord", "solarized_dark"];
+
+fn colour(colour: &str) -> Option<String> {
+    Some(colour.to_string())
+}
+
+fn colours(colours: &[&str]) -> Option<Vec<String>> {
+    Some(colours.iter().map(|colour| colour.to_string()).collect())
+}
+
+/// Returns the colours of the built-in theme with the given name.
+pub fn get_theme_colours(name: &str) -> error::Result<ConfigColours> {
+    match name.to_lowercase().as_str() {
+        "default" => Ok(ConfigColours::default()),
+        "gruvbox" => Ok(ConfigColours {
+            table_header_color: colour("#83a598"),
+            all_cpu_color: colour("#8ec07c"),
+            avg_cpu_color: colour("#fb4934"),
+            cpu_core_colors: colours(&[
+                "#d3869b", "#fabd2f", "#8ec07c", "#b8bb26", "#83a598", "#fe8019",
+            ]),
+            ram_color: colour("#d3869b"),
+            swap_color: colour("#fabd2f"),
+            rx_color: colour("#d3869b"),
+            tx_color: colour("#fabd2f"),
+            border_color: colour("#928374"),
+            highlighted_border_color: colour("#83a598"),
+            text_color: colour("#ebdbb2"),
+            selected_text_color: colour("#282828"),
+            selected_bg_color: colour("#83a598"),
+            widget_title_color: colour("#ebdbb2"),
+            graph_color: colour("#a89984"),
+            high_battery_color: colour("#b8bb26"),
+            medium_battery_color: colour("#fabd2f"),
+            low_battery_color: colour("#fb4934"),
+            ..ConfigColours::default()
+        }),
+        "nord" => Ok(ConfigColours {
+            table_header_color: colour("#81a1c1"),
+            all_cpu_color: colour("#a3be8c"),
+            avg_cpu_color: colour("#bf616a"),
+            cpu_core_colors: colours(&[
+                "#b48ead", "#ebcb8b", "#88c0d0", "#a3be8c", "#81a1c1", "#d08770",
+            ]),
+            ram_color: colour("#b48ead"),
+            swap_color: colour("#ebcb8b"),
+            rx_color: colour("#88c0d0"),
+            tx_color: colour("#ebcb8b"),
+            border_color: colour("#4c566a"),
+            highlighted_border_color: colour("#88c0d0"),
+            text_color: colour("#d8dee9"),
+            selected_text_color: colour("#2e3440"),
+            selected_bg_color: colour("#88c0d0"),
+            widget_title_color: colour("#e5e9f0"),
+            graph_color: colour("#d8dee9"),
+            high_battery_color: colour("#a3be8c"),
+            medium_battery_color: colour("#ebcb8b"),
+            low_battery_color: colour("#bf616a"),
+            ..ConfigColours::default()
+        }),
+        "solarized_dark" => Ok(ConfigColours {
+            table_header_color: colour("#268bd2"),
+            all_cpu_color: colour("#859900"),
+            avg_cpu_color: colour("#dc322f"),
+            cpu_core_colors: colours(&[
+                "#d33682", "#b58900", "#2aa198", "#859900", "#268bd2", "#cb4b16", "#6c71c4",
+            ]),
+            ram_color: colour("#d33682"),
+            swap_color: colour("#b58900"),
+            rx_color: colour("#2aa198"),
+            tx_color: colour("#b58900"),
+            border_color: colour("#586e75"),
+            highlighted_border_color: colour("#268bd2"),
+            text_color: colour("#839496"),
+            selected_text_color: colour("#002b36"),
+            selected_bg_color: colour("#268bd2"),
+            widget_title_color: colour("#93a1a1"),
+            graph_color: colour("#839496"),
+            high_battery_color: colour("#859900"),
+            medium_battery_color: colour("#b58900"),
+            low_battery_color: colour("#dc322f"),
+            ..ConfigColours::default()
+        }),
+        _ => Err(BottomError::ConfigError(format!(
+            "\"{}\" is an invalid theme, use one of {}",
+            name,
+            THEME_NAMES.join(", ")
+        ))),
+    }
+}
@@ -1,5 +1,9 @@
 pub mod dd_dialog;
 pub mod help_dialog;
+pub mod process_info_dialog;
+pub mod renice_dialog;
 
 pub use dd_dialog::KillDialog;
 pub use help_dialog::HelpDialog;
+pub use process_info_dialog::ProcessInfoDialog;
+pub use renice_dialog::ReniceDialog;
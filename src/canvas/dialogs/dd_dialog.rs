@@ -9,6 +9,7 @@ use tui::{
 use crate::{app::App, canvas::Painter};
 
 const DD_BASE: &str = " Confirm Kill Process ── Esc to close ";
+const DD_SIGNAL_BASE: &str = " Send Signal ── Esc to close ";
 const DD_ERROR_BASE: &str = " Error ── Esc to close ";
 
 pub trait KillDialog {
@@ -24,15 +25,109 @@ impl KillDialog for Painter {
         if let Some(dd_err) = &app_state.dd_err {
             return Some(Text::from(vec![
                 Spans::default(),
-                Spans::from("Failed to kill process."),
+                Spans::from(if app_state.delete_dialog_state.is_choosing_signal {
+                    "Failed to send the signal."
+                } else {
+                    "Failed to kill process."
+                }),
                 Spans::from(dd_err.clone()),
                 Spans::from("Please press ENTER or ESC to close this dialog."),
             ]));
         } else if let Some(to_kill_processes) = app_state.get_to_delete_processes() {
             if let Some(first_pid) = to_kill_processes.1.first() {
+                #[cfg(target_family = "unix")]
+                {
+                    if app_state.delete_dialog_state.is_choosing_signal {
+                        let target = if app_state.delete_dialog_state.is_killing_tagged {
+                            format!(
+                                "{} tagged process{}",
+                                to_kill_processes.1.len(),
+                                if to_kill_processes.1.len() == 1 {
+                                    ""
+                                } else {
+                                    "es"
+                                }
+                            )
+                        } else if app_state.is_grouped(app_state.current_widget.widget_id) {
+                            format!(
+                                "{} process{} with the name \"{}\"",
+                                to_kill_processes.1.len(),
+                                if to_kill_processes.1.len() == 1 {
+                                    ""
+                                } else {
+                                    "es"
+                                },
+                                to_kill_processes.0
+                            )
+                        } else {
+                            format!("process \"{}\" with PID {}", to_kill_processes.0, first_pid)
+                        };
+
+                        let filtered_signals = app_state.delete_dialog_state.get_filtered_signals();
+                        let selected_signal = filtered_signals
+                            .get(app_state.delete_dialog_state.selected_signal_index);
+
+                        if app_state.delete_dialog_state.is_confirming_signal {
+                            if let Some((signal_name, _signal)) = selected_signal {
+                                return Some(Text::from(vec![
+                                    Spans::default(),
+                                    Spans::from(format!("Send {} to {}?", signal_name, target)),
+                                    Spans::from("Press 'y' to confirm, or 'n' to go back."),
+                                ]));
+                            }
+                        } else {
+                            let mut signal_spans = vec![
+                                Spans::default(),
+                                Spans::from(format!("Send a signal to {}:", target)),
+                                Spans::default(),
+                            ];
+                            if filtered_signals.is_empty() {
+                                signal_spans.push(Spans::from("No signals match."));
+                            }
+                            signal_spans.extend(filtered_signals.iter().enumerate().map(
+                                |(itx, (signal_name, signal))| {
+                                    let signal_text = format!("{} ({})", signal_name, signal);
+                                    if itx == app_state.delete_dialog_state.selected_signal_index {
+                                        Spans::from(Span::styled(
+                                            signal_text,
+                                            self.colours.currently_selected_text_style,
+                                        ))
+                                    } else {
+                                        Spans::from(signal_text)
+                                    }
+                                },
+                            ));
+                            signal_spans.push(Spans::default());
+                            if !app_state.delete_dialog_state.signal_filter.is_empty() {
+                                signal_spans.push(Spans::from(format!(
+                                    "Filter: {}",
+                                    app_state.delete_dialog_state.signal_filter
+                                )));
+                            }
+                            signal_spans.push(Spans::from(
+                                "Type a number to filter, and press ENTER to send the selected signal.",
+                            ));
+
+                            return Some(Text::from(signal_spans));
+                        }
+                    }
+                }
+
                 return Some(Text::from(vec![
                     Spans::from(""),
-                    if app_state.is_grouped(app_state.current_widget.widget_id) {
+                    if app_state.delete_dialog_state.is_killing_tagged {
+                        if to_kill_processes.1.len() != 1 {
+                            Spans::from(format!(
+                                "Kill {} tagged processes?  Press ENTER to confirm.",
+                                to_kill_processes.1.len()
+                            ))
+                        } else {
+                            Spans::from(format!(
+                                "Kill 1 tagged process with PID {}?  Press ENTER to confirm.",
+                                first_pid
+                            ))
+                        }
+                    } else if app_state.is_grouped(app_state.current_widget.widget_id) {
                         if to_kill_processes.1.len() != 1 {
                             Spans::from(format!(
                                 "Kill {} processes with the name \"{}\"?  Press ENTER to confirm.",
@@ -73,6 +168,17 @@ impl KillDialog for Painter {
                     ),
                     self.colours.border_style,
                 )
+            } else if app_state.delete_dialog_state.is_choosing_signal {
+                Span::styled(
+                    format!(
+                        " Send Signal ─{}─ Esc to close ",
+                        "─".repeat(
+                            usize::from(draw_loc.width)
+                                .saturating_sub(DD_SIGNAL_BASE.chars().count() + 2)
+                        )
+                    ),
+                    self.colours.border_style,
+                )
             } else {
                 Span::styled(
                     format!(
@@ -104,7 +210,9 @@ impl KillDialog for Painter {
             let split_draw_loc = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints(
-                    if app_state.dd_err.is_some() {
+                    if app_state.dd_err.is_some()
+                        || app_state.delete_dialog_state.is_choosing_signal
+                    {
                         vec![Constraint::Percentage(100)]
                     } else {
                         vec![Constraint::Min(0), Constraint::Length(3)]
@@ -113,7 +221,7 @@ impl KillDialog for Painter {
                 )
                 .split(draw_loc);
 
-            // This being true implies that dd_err is none.
+            // This being true implies that dd_err is none, and that we aren't picking a signal.
             if let Some(button_draw_loc) = split_draw_loc.get(1) {
                 let (yes_button, no_button) = if app_state.delete_dialog_state.is_on_yes {
                     (
@@ -0,0 +1,205 @@
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::App,
+    canvas::{
+        drawing_utils::{build_search_query, get_search_start_position},
+        Painter,
+    },
+};
+
+pub trait ProcessInfoDialog {
+    fn draw_process_info_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl ProcessInfoDialog for Painter {
+    fn draw_process_info_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let dialog_state = &mut app_state.process_info_dialog_state;
+
+        let title_base = format!(" {} ── Esc to close ", dialog_state.title);
+        let title = Span::styled(
+            format!(
+                " {} ─{}─ Esc to close ",
+                dialog_state.title,
+                "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                    UnicodeSegmentation::graphemes(title_base.as_str(), true).count() + 2
+                ))
+            ),
+            self.colours.border_style,
+        );
+
+        let block = Block::default()
+            .title(title)
+            .style(self.colours.border_style)
+            .borders(Borders::ALL)
+            .border_style(self.colours.border_style);
+        let inner_loc = block.inner(draw_loc);
+        f.render_widget(block, draw_loc);
+
+        // Leave the last line for the filter, and the first for the header if there is one.
+        let header_height = if dialog_state.header.is_some() { 1 } else { 0 };
+        let split_loc = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(header_height),
+                    Constraint::Min(0),
+                    Constraint::Length(1),
+                ]
+                .as_ref(),
+            )
+            .split(inner_loc);
+
+        // Fix if over-scrolled, ie: after the filter changed.
+        let num_entries = dialog_state.get_filtered_entries().len() as u16;
+        let scroll_state = &mut dialog_state.scroll_state;
+        scroll_state.max_scroll_index = num_entries.saturating_sub(split_loc[1].height);
+        if scroll_state.current_scroll_index > scroll_state.max_scroll_index {
+            scroll_state.current_scroll_index = scroll_state.max_scroll_index;
+        }
+
+        let longest_entry_width = dialog_state
+            .get_filtered_entries()
+            .iter()
+            .map(|entry| UnicodeWidthStr::width(*entry) as u16)
+            .max()
+            .unwrap_or(0);
+        let horizontal_scroll_state = &mut dialog_state.horizontal_scroll_state;
+        horizontal_scroll_state.max_scroll_index =
+            longest_entry_width.saturating_sub(split_loc[1].width);
+        if horizontal_scroll_state.current_scroll_index > horizontal_scroll_state.max_scroll_index {
+            horizontal_scroll_state.current_scroll_index = horizontal_scroll_state.max_scroll_index;
+        }
+
+        // The filter's options go after it, like in the process search.
+        let filter_options = [
+            (
+                if self.is_mac_os {
+                    "Case(F1)"
+                } else {
+                    "Case(Alt+C)"
+                },
+                !dialog_state.filter.is_ignoring_case,
+            ),
+            (
+                if self.is_mac_os {
+                    "Whole(F2)"
+                } else {
+                    "Whole(Alt+W)"
+                },
+                dialog_state.filter.is_searching_whole_word,
+            ),
+            (
+                if self.is_mac_os {
+                    "Regex(F3)"
+                } else {
+                    "Regex(Alt+R)"
+                },
+                dialog_state.filter.is_searching_with_regex,
+            ),
+        ];
+        let filter_title = "Filter: ";
+        let filter_options_width = filter_options
+            .iter()
+            .map(|(option, _)| option.len() + 2)
+            .sum::<usize>();
+        let search_state = &mut dialog_state.filter.search_state;
+        let start_position = get_search_start_position(
+            usize::from(split_loc[2].width)
+                .saturating_sub(filter_title.len() + filter_options_width + 1),
+            &search_state.cursor_direction,
+            &mut search_state.cursor_bar,
+            search_state.char_cursor_position,
+            app_state.is_force_redraw,
+        );
+
+        let dialog_state = &app_state.process_info_dialog_state;
+        let entries = dialog_state.get_filtered_entries();
+        let text = if entries.is_empty() {
+            vec![Spans::from(Span::styled(
+                "No matching entries.",
+                self.colours.text_style,
+            ))]
+        } else {
+            entries
+                .into_iter()
+                .map(|entry| {
+                    let style = if dialog_state
+                        .highlighted_entries
+                        .iter()
+                        .any(|highlighted_entry| highlighted_entry == entry)
+                    {
+                        self.colours.highlighted_border_style
+                    } else {
+                        self.colours.text_style
+                    };
+                    Spans::from(Span::styled(entry, style))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if let Some(header) = &dialog_state.header {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    header.as_str(),
+                    self.colours.table_header_style,
+                )),
+                split_loc[0],
+            );
+        }
+
+        f.render_widget(
+            Paragraph::new(text).style(self.colours.text_style).scroll((
+                dialog_state.scroll_state.current_scroll_index,
+                dialog_state.horizontal_scroll_state.current_scroll_index,
+            )),
+            split_loc[1],
+        );
+
+        let search_state = &dialog_state.filter.search_state;
+        let query = search_state.current_search_query.as_str();
+        let mut filter_text = vec![Span::styled(
+            filter_title,
+            if search_state.is_invalid_search {
+                self.colours.invalid_query_style
+            } else {
+                self.colours.table_header_style
+            },
+        )];
+        filter_text.extend(build_search_query(
+            true,
+            UnicodeSegmentation::grapheme_indices(query, true),
+            start_position,
+            search_state.get_cursor_position(),
+            query,
+            self.colours.currently_selected_text_style,
+            self.colours.text_style,
+        ));
+        filter_text.push(Span::raw(" "));
+        for (option, is_enabled) in filter_options.iter() {
+            filter_text.push(Span::raw("  "));
+            filter_text.push(Span::styled(
+                *option,
+                if *is_enabled {
+                    self.colours.currently_selected_text_style
+                } else {
+                    self.colours.text_style
+                },
+            ));
+        }
+
+        f.render_widget(Paragraph::new(Spans::from(filter_text)), split_loc[2]);
+    }
+}
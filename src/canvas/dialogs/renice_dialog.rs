@@ -0,0 +1,122 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::{
+    app::{App, ProcessGroupingKey},
+    canvas::Painter,
+};
+
+const RENICE_BASE: &str = " Renice Process ── Esc to close ";
+const RENICE_ERROR_BASE: &str = " Error ── Esc to close ";
+
+pub trait ReniceDialog {
+    fn draw_renice_dialog<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect);
+}
+
+impl ReniceDialog for Painter {
+    fn draw_renice_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let dialog_state = &app_state.renice_dialog_state;
+
+        let (title_text, title_base, text) = if let Some(error) = &dialog_state.error {
+            (
+                "Error",
+                RENICE_ERROR_BASE,
+                Text::from(vec![
+                    Spans::default(),
+                    Spans::from(error.clone()),
+                    Spans::from("Please press ENTER or ESC to close this dialog."),
+                ]),
+            )
+        } else {
+            let processes = format!(
+                "{} {}{}",
+                dialog_state.pids.len(),
+                if dialog_state.is_renicing_tagged {
+                    "tagged "
+                } else {
+                    ""
+                },
+                if dialog_state.pids.len() == 1 {
+                    "process"
+                } else {
+                    "processes"
+                }
+            );
+            let target = match dialog_state.grouping_key {
+                _ if dialog_state.is_renicing_tagged => processes,
+                Some(ProcessGroupingKey::Name) => {
+                    format!("{} with the name \"{}\"", processes, dialog_state.name)
+                }
+                Some(ProcessGroupingKey::User) => {
+                    format!("{} of the user \"{}\"", processes, dialog_state.name)
+                }
+                Some(ProcessGroupingKey::Container) => {
+                    format!("{} in the container \"{}\"", processes, dialog_state.name)
+                }
+                None => format!(
+                    "\"{}\" (PID {})",
+                    dialog_state.name,
+                    dialog_state.pids.first().copied().unwrap_or_default()
+                ),
+            };
+
+            let hint = match dialog_state.get_nice() {
+                Some(nice) if nice < 0 && !dialog_state.can_lower_nice => Span::styled(
+                    "Warning: negative nice values need CAP_SYS_NICE, so this will likely fail.",
+                    self.colours.invalid_query_style,
+                ),
+                Some(_) => Span::raw("Press ENTER to confirm."),
+                None => Span::styled(
+                    "Nice values range from -20 to 19.",
+                    self.colours.invalid_query_style,
+                ),
+            };
+
+            (
+                "Renice Process",
+                RENICE_BASE,
+                Text::from(vec![
+                    Spans::from(format!("Set the nice value of {}:", target)),
+                    Spans::from(Span::styled(
+                        format!("{}_", dialog_state.input),
+                        self.colours.currently_selected_text_style,
+                    )),
+                    Spans::from(hint),
+                ]),
+            )
+        };
+
+        let title = Span::styled(
+            format!(
+                " {} ─{}─ Esc to close ",
+                title_text,
+                "─".repeat(
+                    usize::from(draw_loc.width).saturating_sub(title_base.chars().count() + 2)
+                )
+            ),
+            self.colours.border_style,
+        );
+
+        f.render_widget(
+            Paragraph::new(text)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .style(self.colours.border_style)
+                        .borders(Borders::ALL)
+                        .border_style(self.colours.border_style),
+                )
+                .style(self.colours.text_style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+    }
+}
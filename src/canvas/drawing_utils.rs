@@ -1,5 +1,8 @@
 use crate::app;
 use std::cmp::{max, min};
+use tui::{style::Style, text::Span};
+use unicode_segmentation::GraphemeIndices;
+use unicode_width::UnicodeWidthStr;
 
 /// Return a (hard)-width vector for column widths.
 ///
@@ -144,6 +147,55 @@ pub fn get_search_start_position(
     }
 }
 
+/// Builds the spans of a search query, skipping what's scrolled past and highlighting the cursor
+/// if the search is selected.
+pub fn build_search_query<'a>(
+    is_on_widget: bool, grapheme_indices: GraphemeIndices<'a>, start_position: usize,
+    cursor_position: usize, query: &str, currently_selected_text_style: Style, text_style: Style,
+) -> Vec<Span<'a>> {
+    let mut current_grapheme_posn = 0;
+
+    if is_on_widget {
+        let mut res = grapheme_indices
+            .filter_map(|grapheme| {
+                current_grapheme_posn += UnicodeWidthStr::width(grapheme.1);
+
+                if current_grapheme_posn <= start_position {
+                    None
+                } else {
+                    let styled = if grapheme.0 == cursor_position {
+                        Span::styled(grapheme.1, currently_selected_text_style)
+                    } else {
+                        Span::styled(grapheme.1, text_style)
+                    };
+                    Some(styled)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if cursor_position >= query.len() {
+            res.push(Span::styled(" ", currently_selected_text_style))
+        }
+
+        res
+    } else {
+        // This is easier - we just need to get a range of graphemes, rather than
+        // dealing with possibly inserting a cursor (as none is shown!)
+
+        grapheme_indices
+            .filter_map(|grapheme| {
+                current_grapheme_posn += UnicodeWidthStr::width(grapheme.1);
+                if current_grapheme_posn <= start_position {
+                    None
+                } else {
+                    let styled = Span::styled(grapheme.1, text_style);
+                    Some(styled)
+                }
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
 pub fn get_start_position(
     num_rows: usize, scroll_direction: &app::ScrollDirection, scroll_position_bar: &mut usize,
     currently_selected_position: usize, is_force_redraw: bool,
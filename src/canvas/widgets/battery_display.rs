@@ -129,6 +129,7 @@ impl BatteryDisplayWidget for Painter {
 
                 let battery_items = vec![
                     ["Charge %", &bars],
+                    ["State", &battery_details.state],
                     ["Consumption", &battery_details.watt_consumption],
                     if let Some(duration_until_full) = &battery_details.duration_until_full {
                         ["Time to full", duration_until_full]
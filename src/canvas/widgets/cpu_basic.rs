@@ -1,5 +1,3 @@
-use std::cmp::min;
-
 use crate::{
     app::App,
     canvas::{drawing_utils::*, Painter},
@@ -15,6 +13,25 @@ use tui::{
     widgets::{Block, Paragraph},
 };
 
+/// The width of everything in a core's entry but the bar: the name, brackets, and percentage.
+const BASIC_CPU_LABEL_WIDTH: usize = 9;
+
+/// The narrowest a column of cores can get, including the space between columns.
+const MIN_BASIC_CPU_COLUMN_WIDTH: usize = BASIC_CPU_LABEL_WIDTH + 10 + 2;
+
+/// Returns how many columns and rows to split the basic CPU widget's entries into, given its
+/// width.  As many columns are used as fit, up to one per entry, to keep the widget short.
+pub fn get_basic_cpu_layout(num_entries: usize, width: u16) -> (usize, usize) {
+    let num_columns =
+        (usize::from(width) / MIN_BASIC_CPU_COLUMN_WIDTH).clamp(1, num_entries.max(1));
+    let mut num_rows = num_entries / num_columns;
+    if num_rows * num_columns < num_entries {
+        num_rows += 1;
+    }
+
+    (num_columns, num_rows)
+}
+
 pub trait CpuBasicWidget {
     fn draw_basic_cpu<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
@@ -25,17 +42,9 @@ impl CpuBasicWidget for Painter {
     fn draw_basic_cpu<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
-        let cpu_data: &[ConvertedCpuData] = &app_state.canvas_data.cpu_data;
-
-        // This is a bit complicated, but basically, we want to draw SOME number
-        // of columns to draw all CPUs.  Ideally, as well, we want to not have
-        // to ever scroll.
-        // **General logic** - count number of elements in cpu_data.  Then see how
-        // many rows and columns we have in draw_loc (-2 on both sides for border?).
-        // I think what we can do is try to fit in as many in one column as possible.
-        // If not, then add a new column.
-        // Then, from this, split the row space across ALL columns.  From there, generate
-        // the desired lengths.
+        // Skip the "All" entry, which only exists for the chart legend.
+        let cpu_data: &[ConvertedCpuData] =
+            app_state.canvas_data.cpu_data.get(1..).unwrap_or_default();
 
         if app_state.current_widget.widget_id == widget_id {
             f.render_widget(
@@ -46,90 +55,64 @@ impl CpuBasicWidget for Painter {
             );
         }
 
-        let num_cpus = cpu_data.len();
-        if draw_loc.height > 0 {
-            let remaining_height = usize::from(draw_loc.height);
-            const REQUIRED_COLUMNS: usize = 4;
-
-            let chunk_vec =
-                vec![Constraint::Percentage((100 / REQUIRED_COLUMNS) as u16); REQUIRED_COLUMNS];
+        let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
+        if draw_loc.height > 0 && !cpu_data.is_empty() {
+            let (num_columns, num_rows) = get_basic_cpu_layout(cpu_data.len(), draw_loc.width);
             let chunks = Layout::default()
-                .constraints(chunk_vec.as_ref())
+                .constraints(vec![Constraint::Ratio(1, num_columns as u32); num_columns].as_ref())
                 .direction(Direction::Horizontal)
                 .split(draw_loc);
 
-            // +9 due to 3 + 4 + 2 columns for the name & space + percentage + bar bounds
-            const MARGIN_SPACE: usize = 2;
-            let remaining_width = usize::from(draw_loc.width)
-                .saturating_sub((9 + MARGIN_SPACE) * REQUIRED_COLUMNS - MARGIN_SPACE);
-
-            let bar_length = remaining_width / REQUIRED_COLUMNS;
-
-            // CPU (and RAM) percent bars are, uh, "heavily" inspired from htop.
-            let cpu_bars = (0..num_cpus)
-                .map(|cpu_index| {
-                    let use_percentage =
-                        if let Some(cpu_usage) = cpu_data[cpu_index].cpu_data.last() {
-                            cpu_usage.1
-                        } else {
-                            0.0
-                        };
-
-                    let num_bars = calculate_basic_use_bars(use_percentage, bar_length);
-                    format!(
-                        "{:3}[{}{}{:3.0}%]",
-                        if app_state.app_config_fields.show_average_cpu {
-                            if cpu_index == 0 {
-                                "AVG".to_string()
-                            } else {
-                                (cpu_index - 1).to_string()
+            // Cores fill each column from top to bottom before moving on to the next, like htop.
+            for (column, chunk) in chunks.iter().enumerate() {
+                let margined_loc = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(100)].as_ref())
+                    .horizontal_margin(1)
+                    .split(*chunk)[0];
+                let bar_length =
+                    usize::from(margined_loc.width).saturating_sub(BASIC_CPU_LABEL_WIDTH);
+
+                let cpu_column = cpu_data
+                    .iter()
+                    .enumerate()
+                    .skip(column * num_rows)
+                    .take(num_rows)
+                    .map(|(cpu_index, cpu)| {
+                        // CPU (and RAM) percent bars are, uh, "heavily" inspired from htop.
+                        let use_percentage = cpu.cpu_data.last().map_or(0.0, |point| point.1);
+                        let num_bars = calculate_basic_use_bars(use_percentage, bar_length);
+
+                        // Colour by core number, so that cores keep their colours
+                        // whether or not the average is shown.
+                        let style = match (show_avg_cpu, cpu_index) {
+                            (true, 0) => self.colours.avg_colour_style,
+                            (true, _) => {
+                                self.colours.cpu_colour_styles
+                                    [(cpu_index - 1) % self.colours.cpu_colour_styles.len()]
                             }
-                        } else {
-                            cpu_index.to_string()
-                        },
-                        "|".repeat(num_bars),
-                        " ".repeat(bar_length - num_bars),
-                        use_percentage.round(),
-                    )
-                })
-                .collect::<Vec<_>>();
-
-            let mut row_counter = num_cpus;
-            let mut start_index = 0;
-            for (itx, chunk) in chunks.iter().enumerate() {
-                // Explicitly check... don't want an accidental DBZ or underflow
-                if REQUIRED_COLUMNS > itx {
-                    let to_divide = REQUIRED_COLUMNS - itx;
-                    let how_many_cpus = min(
-                        remaining_height,
-                        (row_counter / to_divide)
-                            + (if row_counter % to_divide == 0 { 0 } else { 1 }),
-                    );
-                    row_counter -= how_many_cpus;
-                    let end_index = min(start_index + how_many_cpus, num_cpus);
-                    let cpu_column = (start_index..end_index)
-                        .map(|cpu_index| {
-                            Spans::from(Span {
-                                content: (&cpu_bars[cpu_index]).into(),
-                                style: self.colours.cpu_colour_styles
-                                    [cpu_index % self.colours.cpu_colour_styles.len()],
-                            })
-                        })
-                        .collect::<Vec<_>>();
-
-                    start_index += how_many_cpus;
-
-                    let margined_loc = Layout::default()
-                        .direction(Direction::Horizontal)
-                        .constraints([Constraint::Percentage(100)].as_ref())
-                        .horizontal_margin(1)
-                        .split(*chunk)[0];
-
-                    f.render_widget(
-                        Paragraph::new(cpu_column).block(Block::default()),
-                        margined_loc,
-                    );
-                }
+                            (false, _) => {
+                                self.colours.cpu_colour_styles
+                                    [cpu_index % self.colours.cpu_colour_styles.len()]
+                            }
+                        };
+                        Spans::from(Span::styled(
+                            format!(
+                                "{:>3}[{}{}{:3.0}%]",
+                                cpu.short_cpu_name,
+                                "|".repeat(num_bars),
+                                " ".repeat(bar_length - num_bars),
+                                use_percentage.round(),
+                            ),
+                            style,
+                        ))
+                    })
+                    .collect::<Vec<_>>();
+
+                f.render_widget(
+                    Paragraph::new(cpu_column).block(Block::default()),
+                    margined_loc,
+                );
             }
         }
 
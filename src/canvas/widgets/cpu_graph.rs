@@ -1,4 +1,3 @@
-use lazy_static::lazy_static;
 use std::borrow::Cow;
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -9,28 +8,35 @@ use crate::{
         Painter,
     },
     constants::*,
-    data_conversion::ConvertedCpuData,
+    data_conversion::{get_cpu_display_order, ConvertedCpuData},
 };
 
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     symbols::Marker,
     terminal::Frame,
     text::Span,
     text::Spans,
-    widgets::{Axis, Block, Borders, Chart, Dataset, Row, Table},
+    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, Paragraph, Row, Table},
 };
 
 const CPU_LEGEND_HEADER: [&str; 2] = ["CPU", "Use%"];
+const CPU_FREQ_HEADER: &str = "Freq";
+const CPU_IOWAIT_HEADER: &str = "IO%";
+const CPU_STEAL_HEADER: &str = "St%";
 const AVG_POSITION: usize = 1;
 const ALL_POSITION: usize = 0;
 
-lazy_static! {
-    static ref CPU_LEGEND_HEADER_LENS: Vec<u16> = CPU_LEGEND_HEADER
-        .iter()
-        .map(|entry| entry.len() as u16)
-        .collect::<Vec<_>>();
+/// Returns which of the CPU colours to use for an entry.  Colours go by core number, so that
+/// cores keep their colours whether or not the average is shown.
+fn get_core_colour_index(data_index: usize, show_avg_cpu: bool) -> usize {
+    if show_avg_cpu {
+        data_index.saturating_sub(AVG_POSITION + 1)
+    } else {
+        data_index.saturating_sub(ALL_POSITION + 1)
+    }
 }
 
 pub trait CpuGraphWidget {
@@ -136,7 +142,7 @@ impl CpuGraphWidget for Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
         if let Some(cpu_widget_state) = app_state.cpu_state.widget_states.get_mut(&widget_id) {
-            let cpu_data: &mut [ConvertedCpuData] = &mut app_state.canvas_data.cpu_data;
+            let cpu_data: &[ConvertedCpuData] = &app_state.canvas_data.cpu_data;
 
             let display_time_labels = vec![
                 Span::styled(
@@ -185,48 +191,138 @@ impl CpuGraphWidget for Painter {
             let use_dot = app_state.app_config_fields.use_dot;
             let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
             let current_scroll_position = cpu_widget_state.scroll_state.current_scroll_position;
-            let dataset_vector: Vec<Dataset<'_>> = if current_scroll_position == ALL_POSITION {
-                cpu_data
+            let hidden_cores = &cpu_widget_state.hidden_cores;
+
+            // While the widget is focused, the core selected in the legend is emphasized;
+            // otherwise, only a locked core is.
+            let is_on_cpu_widget = app_state.current_widget.widget_id == widget_id
+                || app_state.current_widget.widget_id == widget_id + 1;
+            let emphasized_index =
+                get_cpu_display_order(cpu_data, cpu_widget_state.is_numa_grouped)
+                    .get(current_scroll_position)
+                    .copied()
+                    .filter(|index| is_on_cpu_widget && *index != ALL_POSITION)
+                    .or(cpu_widget_state.locked_core)
+                    .filter(|index| *index < cpu_data.len());
+
+            let is_showing_interrupts = cpu_widget_state.is_showing_interrupts
+                && !app_state.canvas_data.cpu_stacked_data.is_empty();
+            let is_showing_breakdown = cpu_widget_state.is_showing_breakdown
+                && !is_showing_interrupts
+                && emphasized_index.is_none()
+                && app_state.canvas_data.cpu_breakdown_label.is_some();
+            let core_style = |itx: usize| {
+                if show_avg_cpu && itx == AVG_POSITION {
+                    self.colours.avg_colour_style
+                } else {
+                    self.colours.cpu_colour_styles[get_core_colour_index(itx, show_avg_cpu)
+                        % self.colours.cpu_colour_styles.len()]
+                }
+            };
+            let core_dataset = |itx: usize, style: Style| {
+                Dataset::default()
+                    .marker(if use_dot {
+                        Marker::Dot
+                    } else {
+                        Marker::Braille
+                    })
+                    .style(style)
+                    .data(&cpu_data[itx].cpu_data[..])
+                    .graph_type(tui::widgets::GraphType::Line)
+            };
+
+            let mut dataset_vector: Vec<Dataset<'_>> = if is_showing_interrupts {
+                // Each layer is the running total of the states below it, so draw the top one
+                // first and let the lower ones sit over it; idle time is the gap up to 100%.
+                app_state
+                    .canvas_data
+                    .cpu_stacked_data
                     .iter()
-                    .enumerate()
+                    .zip(&[
+                        self.colours.cpu_user_style,
+                        self.colours.cpu_system_style,
+                        self.colours.cpu_irq_style,
+                        self.colours.cpu_softirq_style,
+                        self.colours.cpu_iowait_style,
+                        self.colours.cpu_steal_style,
+                    ])
                     .rev()
-                    .map(|(itx, cpu)| {
+                    .map(|(data, style)| {
                         Dataset::default()
                             .marker(if use_dot {
                                 Marker::Dot
                             } else {
                                 Marker::Braille
                             })
-                            .style(if show_avg_cpu && itx == AVG_POSITION {
-                                self.colours.avg_colour_style
-                            } else {
-                                self.colours.cpu_colour_styles
-                                    [itx % self.colours.cpu_colour_styles.len()]
-                            })
-                            .data(&cpu.cpu_data[..])
+                            .style(*style)
+                            .data(&data[..])
                             .graph_type(tui::widgets::GraphType::Line)
                     })
                     .collect()
-            } else if let Some(cpu) = cpu_data.get(current_scroll_position) {
-                vec![Dataset::default()
-                    .marker(if use_dot {
-                        Marker::Dot
-                    } else {
-                        Marker::Braille
+            } else {
+                cpu_data
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .filter(|(itx, _)| {
+                        !hidden_cores.contains(itx) && Some(*itx) != emphasized_index
                     })
-                    .style(if show_avg_cpu && current_scroll_position == AVG_POSITION {
-                        self.colours.avg_colour_style
-                    } else {
-                        self.colours.cpu_colour_styles[cpu_widget_state
-                            .scroll_state
-                            .current_scroll_position
-                            % self.colours.cpu_colour_styles.len()]
+                    .map(|(itx, _)| {
+                        core_dataset(
+                            itx,
+                            if emphasized_index.is_some() {
+                                self.colours.disabled_text_style
+                            } else {
+                                core_style(itx)
+                            },
+                        )
                     })
-                    .data(&cpu.cpu_data[..])
-                    .graph_type(tui::widgets::GraphType::Line)]
-            } else {
-                vec![]
+                    .collect()
             };
+            // Draw the emphasized core last so that it ends up on top, with its I/O wait below.
+            if let Some(emphasized_index) = emphasized_index.filter(|_| !is_showing_interrupts) {
+                if app_state.app_config_fields.show_iowait {
+                    dataset_vector.push(
+                        Dataset::default()
+                            .marker(if use_dot {
+                                Marker::Dot
+                            } else {
+                                Marker::Braille
+                            })
+                            .style(self.colours.cpu_iowait_style)
+                            .data(&cpu_data[emphasized_index].iowait_data[..])
+                            .graph_type(tui::widgets::GraphType::Line),
+                    );
+                }
+                dataset_vector.push(core_dataset(
+                    emphasized_index,
+                    core_style(emphasized_index).add_modifier(Modifier::BOLD),
+                ));
+            }
+            if is_showing_breakdown {
+                for (data, style) in &[
+                    (
+                        &app_state.canvas_data.cpu_system_data,
+                        self.colours.cpu_system_style,
+                    ),
+                    (
+                        &app_state.canvas_data.cpu_iowait_data,
+                        self.colours.cpu_iowait_style,
+                    ),
+                ] {
+                    dataset_vector.push(
+                        Dataset::default()
+                            .marker(if use_dot {
+                                Marker::Dot
+                            } else {
+                                Marker::Braille
+                            })
+                            .style(*style)
+                            .data(&data[..])
+                            .graph_type(tui::widgets::GraphType::Line),
+                    );
+                }
+            }
 
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_on_widget {
@@ -235,22 +331,47 @@ impl CpuGraphWidget for Painter {
                 self.colours.border_style
             };
 
+            let mut title_text =
+                format!(" CPU ({}s) ", cpu_widget_state.current_display_time / 1000);
+            if !cpu_widget_state.hidden_cores.is_empty() {
+                title_text += &format!("({} hidden) ", cpu_widget_state.hidden_cores.len());
+            }
+            if let Some(locked_cpu) = cpu_widget_state
+                .locked_core
+                .and_then(|locked_core| cpu_data.get(locked_core))
+            {
+                title_text += &format!("({} locked) ", locked_cpu.cpu_name);
+            }
+            if let Some(load_avg_label) = &app_state.canvas_data.load_avg_label {
+                title_text += &format!("─ {} ", load_avg_label);
+            }
+            if is_showing_interrupts {
+                if let Some(cpu_interrupt_label) = &app_state.canvas_data.cpu_interrupt_label {
+                    title_text += &format!("─ {} ", cpu_interrupt_label);
+                }
+            } else if is_showing_breakdown {
+                if let Some(cpu_breakdown_label) = &app_state.canvas_data.cpu_breakdown_label {
+                    title_text += &format!("─ {} ", cpu_breakdown_label);
+                }
+            }
             let title = if app_state.is_expanded {
-                const TITLE_BASE: &str = " CPU ── Esc to go back ";
+                let title_base_len = UnicodeSegmentation::graphemes(title_text.as_str(), true)
+                    .count()
+                    + UnicodeSegmentation::graphemes("── Esc to go back ", true).count();
                 Spans::from(vec![
-                    Span::styled(" CPU ", self.colours.widget_title_style),
+                    Span::styled(title_text, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
-                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
-                            ))
+                            "─".repeat(
+                                usize::from(draw_loc.width).saturating_sub(title_base_len + 2)
+                            )
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(" CPU ", self.colours.widget_title_style))
+                Spans::from(Span::styled(title_text, self.colours.widget_title_style))
             };
 
             f.render_widget(
@@ -265,6 +386,34 @@ impl CpuGraphWidget for Painter {
                     .y_axis(y_axis),
                 draw_loc,
             );
+
+            // The chart's own legend would leave a row for every unnamed core, so the breakdown
+            // gets one of its own, in the same corner the chart would put it.
+            if is_showing_breakdown {
+                let legend_text = vec![
+                    Spans::from(Span::styled("System", self.colours.cpu_system_style)),
+                    Spans::from(Span::styled("I/O wait", self.colours.cpu_iowait_style)),
+                ];
+                let legend_width = 10;
+                let legend_height = legend_text.len() as u16 + 2;
+                if draw_loc.width >= legend_width * 3 && draw_loc.height >= legend_height * 2 {
+                    let legend_loc = Rect::new(
+                        draw_loc.x + draw_loc.width - 1 - legend_width,
+                        draw_loc.y + 1,
+                        legend_width,
+                        legend_height,
+                    );
+                    f.render_widget(Clear, legend_loc);
+                    f.render_widget(
+                        Paragraph::new(legend_text).block(
+                            Block::default()
+                                .borders(Borders::ALL)
+                                .border_style(self.colours.border_style),
+                        ),
+                        legend_loc,
+                    );
+                }
+            }
         }
     }
 
@@ -276,48 +425,85 @@ impl CpuGraphWidget for Painter {
         {
             cpu_widget_state.is_legend_hidden = false;
             let cpu_data: &mut [ConvertedCpuData] = &mut app_state.canvas_data.cpu_data;
-            let cpu_table_state = &mut cpu_widget_state.scroll_state.table_state;
+            let is_numa_grouped = cpu_widget_state.is_numa_grouped;
+            let display_order = get_cpu_display_order(cpu_data, is_numa_grouped);
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
                 0
             } else {
                 app_state.app_config_fields.table_gap
             };
+            // Leave room for a separator above each NUMA node.
+            let num_separators = if is_numa_grouped {
+                cpu_data
+                    .iter()
+                    .filter_map(|cpu| cpu.numa_node)
+                    .collect::<std::collections::HashSet<_>>()
+                    .len()
+            } else {
+                0
+            };
+            let num_rows = usize::from(
+                (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
+            )
+            .saturating_sub(num_separators);
+            cpu_widget_state.scroll_state.num_visible_rows = num_rows;
             let start_position = get_start_position(
-                usize::from(
-                    (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
-                ),
+                num_rows,
                 &cpu_widget_state.scroll_state.scroll_direction,
                 &mut cpu_widget_state.scroll_state.previous_scroll_position,
                 cpu_widget_state.scroll_state.current_scroll_position,
                 app_state.is_force_redraw,
             );
-            cpu_table_state.select(Some(
-                cpu_widget_state
-                    .scroll_state
-                    .current_scroll_position
-                    .saturating_sub(start_position),
-            ));
-
-            let sliced_cpu_data = &cpu_data[start_position..];
-
-            let mut offset_scroll_index = cpu_widget_state
-                .scroll_state
-                .current_scroll_position
-                .saturating_sub(start_position);
+            let current_scroll_position = cpu_widget_state.scroll_state.current_scroll_position;
             let show_avg_cpu = app_state.app_config_fields.show_average_cpu;
+            // Platforms that don't report frequencies just get the usage column.
+            let show_cpu_freq = app_state.app_config_fields.show_cpu_freq
+                && cpu_data.iter().any(|cpu| !cpu.frequency_value.is_empty());
+            let show_iowait = app_state.app_config_fields.show_iowait
+                && cpu_data.iter().any(|cpu| !cpu.iowait_value.is_empty());
+            let show_steal = app_state.app_config_fields.show_steal
+                && cpu_data.iter().any(|cpu| !cpu.steal_value.is_empty());
+
+            let mut legend_header = CPU_LEGEND_HEADER.to_vec();
+            let mut desired_column_widths = vec![6, 4];
+            for (is_shown, header, desired_width) in &[
+                (show_cpu_freq, CPU_FREQ_HEADER, 7),
+                (show_iowait, CPU_IOWAIT_HEADER, 4),
+                (show_steal, CPU_STEAL_HEADER, 4),
+            ] {
+                if *is_shown {
+                    legend_header.push(header);
+                    desired_column_widths.push(*desired_width);
+                }
+            }
+            let num_columns = legend_header.len();
 
             // Calculate widths
-            if recalculate_column_widths {
-                cpu_widget_state.table_width_state.desired_column_widths = vec![6, 4];
+            if recalculate_column_widths
+                || cpu_widget_state
+                    .table_width_state
+                    .desired_column_widths
+                    .len()
+                    != num_columns
+            {
+                // The name gets a larger share once there are extra columns.
+                let soft_widths_max = (0..num_columns)
+                    .map(|column| match (column, num_columns) {
+                        (_, 2) => Some(0.5),
+                        (0, _) => Some(0.4),
+                        _ => Some(0.6 / (num_columns - 1) as f64),
+                    })
+                    .collect::<Vec<_>>();
+                cpu_widget_state.table_width_state.desired_column_widths = desired_column_widths;
                 cpu_widget_state.table_width_state.calculated_column_widths = get_column_widths(
                     draw_loc.width,
-                    &[None, None],
-                    &(CPU_LEGEND_HEADER_LENS
+                    &vec![None; num_columns],
+                    &(legend_header
                         .iter()
-                        .map(|width| Some(*width))
+                        .map(|header| Some(header.len() as u16))
                         .collect::<Vec<_>>()),
-                    &[Some(0.5), Some(0.5)],
+                    &soft_widths_max,
                     &(cpu_widget_state
                         .table_width_state
                         .desired_column_widths
@@ -330,7 +516,29 @@ impl CpuGraphWidget for Painter {
 
             let dcw = &cpu_widget_state.table_width_state.desired_column_widths;
             let ccw = &cpu_widget_state.table_width_state.calculated_column_widths;
-            let cpu_rows = sliced_cpu_data.iter().enumerate().filter_map(|(itx, cpu)| {
+            let mut cpu_rows = Vec::new();
+            let mut numa_separator_rows = Vec::new();
+            let mut selected_row = 0;
+            let mut previous_numa_node = None;
+            for (itx, data_index) in display_order.iter().enumerate().skip(start_position) {
+                let data_index = *data_index;
+                let cpu = &cpu_data[data_index];
+
+                if is_numa_grouped && cpu.numa_node.is_some() && cpu.numa_node != previous_numa_node
+                {
+                    let mut separator_row = vec![Cow::Owned(format!(
+                        "Node {}",
+                        cpu.numa_node.unwrap_or_default()
+                    ))];
+                    separator_row.resize(num_columns, Cow::Borrowed(""));
+                    numa_separator_rows.push(cpu_rows.len());
+                    cpu_rows.push(Row::StyledData(
+                        separator_row.into_iter(),
+                        self.colours.table_header_style,
+                    ));
+                    previous_numa_node = cpu.numa_node;
+                }
+
                 let truncated_name: Cow<'_, str> =
                     if let (Some(desired_column_width), Some(calculated_column_width)) =
                         (dcw.get(0), ccw.get(0))
@@ -354,34 +562,41 @@ impl CpuGraphWidget for Painter {
                         Cow::Borrowed(&cpu.legend_value)
                     };
 
-                let cpu_string_row: Vec<Cow<'_, str>> = vec![truncated_name, truncated_legend];
+                let mut cpu_string_row: Vec<Cow<'_, str>> = vec![truncated_name, truncated_legend];
+                if show_cpu_freq {
+                    cpu_string_row.push(Cow::Borrowed(&cpu.frequency_value));
+                }
+                if show_iowait {
+                    cpu_string_row.push(Cow::Borrowed(&cpu.iowait_value));
+                }
+                if show_steal {
+                    cpu_string_row.push(Cow::Borrowed(&cpu.steal_value));
+                }
 
-                if cpu_string_row.is_empty() {
-                    offset_scroll_index += 1;
-                    None
-                } else {
-                    Some(Row::StyledData(
-                        cpu_string_row.into_iter(),
-                        if itx == offset_scroll_index {
-                            self.colours.currently_selected_text_style
-                        } else if itx == ALL_POSITION {
-                            self.colours.all_colour_style
-                        } else if show_avg_cpu {
-                            if itx == AVG_POSITION {
-                                self.colours.avg_colour_style
-                            } else {
-                                self.colours.cpu_colour_styles[itx + start_position
-                                    - AVG_POSITION
-                                    - 1 % self.colours.cpu_colour_styles.len()]
-                            }
-                        } else {
-                            self.colours.cpu_colour_styles[itx + start_position
-                                - ALL_POSITION
-                                - 1 % self.colours.cpu_colour_styles.len()]
-                        },
-                    ))
+                if itx == current_scroll_position {
+                    selected_row = cpu_rows.len();
                 }
-            });
+                cpu_rows.push(Row::StyledData(
+                    cpu_string_row.into_iter(),
+                    if itx == current_scroll_position {
+                        self.colours.currently_selected_text_style
+                    } else if cpu_widget_state.hidden_cores.contains(&data_index) {
+                        self.colours.disabled_text_style
+                    } else if data_index == ALL_POSITION {
+                        self.colours.all_colour_style
+                    } else if show_avg_cpu && data_index == AVG_POSITION {
+                        self.colours.avg_colour_style
+                    } else {
+                        self.colours.cpu_colour_styles[get_core_colour_index(
+                            data_index,
+                            show_avg_cpu,
+                        ) % self.colours.cpu_colour_styles.len()]
+                    },
+                ));
+            }
+            cpu_widget_state.numa_separator_rows = numa_separator_rows;
+            let cpu_table_state = &mut cpu_widget_state.scroll_state.table_state;
+            cpu_table_state.select(Some(selected_row));
 
             // Note we don't set highlight_style, as it should always be shown for this widget.
             let border_and_title_style = if is_on_widget {
@@ -390,11 +605,24 @@ impl CpuGraphWidget for Painter {
                 self.colours.border_style
             };
 
+            // Show how many entries are scrolled out of view above and below.
+            let num_entries_after_start = display_order.len().saturating_sub(start_position);
+            let num_entries_below = num_entries_after_start.saturating_sub(num_rows);
+            let legend_title = if start_position > 0 {
+                Spans::from(Span::styled(
+                    format!("↑{}", start_position),
+                    border_and_title_style,
+                ))
+            } else {
+                Spans::default()
+            };
+
             // Draw
             f.render_stateful_widget(
-                Table::new(CPU_LEGEND_HEADER.iter(), cpu_rows)
+                Table::new(legend_header.iter(), cpu_rows.into_iter())
                     .block(
                         Block::default()
+                            .title(legend_title)
                             .borders(Borders::ALL)
                             .border_style(border_and_title_style),
                     )
@@ -412,6 +640,21 @@ impl CpuGraphWidget for Painter {
                 draw_loc,
                 cpu_table_state,
             );
+
+            if num_entries_below > 0 && draw_loc.width > 2 && draw_loc.height > 0 {
+                f.render_widget(
+                    Paragraph::new(Span::styled(
+                        format!("↓{}", num_entries_below),
+                        border_and_title_style,
+                    )),
+                    Rect::new(
+                        draw_loc.x + 1,
+                        draw_loc.y + draw_loc.height - 1,
+                        draw_loc.width - 2,
+                        1,
+                    ),
+                );
+            }
         }
     }
 }
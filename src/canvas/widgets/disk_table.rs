@@ -1,11 +1,10 @@
-use lazy_static::lazy_static;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
     terminal::Frame,
     text::Span,
     text::Spans,
-    widgets::{Block, Borders, Row, Table},
+    widgets::{Block, Borders, Paragraph, Row, Table},
 };
 
 use crate::{
@@ -15,19 +14,11 @@ use crate::{
         Painter,
     },
     constants::*,
+    data_conversion::{DiskUsageLevel, DISK_USAGE_BAR_WIDTH},
 };
 use std::borrow::Cow;
 use unicode_segmentation::UnicodeSegmentation;
 
-const DISK_HEADERS: [&str; 7] = ["Disk", "Mount", "Used", "Free", "Total", "R/s", "W/s"];
-
-lazy_static! {
-    static ref DISK_HEADERS_LENS: Vec<u16> = DISK_HEADERS
-        .iter()
-        .map(|entry| entry.len() as u16)
-        .collect::<Vec<_>>();
-}
-
 pub trait DiskTableWidget {
     fn draw_disk_table<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
@@ -41,17 +32,25 @@ impl DiskTableWidget for Painter {
         widget_id: u64,
     ) {
         let recalculate_column_widths = app_state.should_get_widget_bounds();
+        let disk_headers = app_state.disk_state.get_column_headers();
+        let disk_headers_lens = disk_headers
+            .iter()
+            .map(|entry| UnicodeSegmentation::graphemes(entry.as_str(), true).count() as u16)
+            .collect::<Vec<_>>();
         if let Some(disk_widget_state) = app_state.disk_state.widget_states.get_mut(&widget_id) {
-            let disk_data: &mut [Vec<String>] = &mut app_state.canvas_data.disk_data;
+            let disk_data: &mut [(Vec<String>, bool, DiskUsageLevel)] =
+                &mut app_state.canvas_data.disk_data;
             let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
                 0
             } else {
                 app_state.app_config_fields.table_gap
             };
+            let num_rows = usize::from(
+                (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
+            );
+            disk_widget_state.scroll_state.num_visible_rows = num_rows;
             let start_position = get_start_position(
-                usize::from(
-                    (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
-                ),
+                num_rows,
                 &disk_widget_state.scroll_state.scroll_direction,
                 &mut disk_widget_state.scroll_state.previous_scroll_position,
                 disk_widget_state.scroll_state.current_scroll_position,
@@ -68,11 +67,20 @@ impl DiskTableWidget for Painter {
             let sliced_vec = &disk_data[start_position..];
 
             // Calculate widths
-            let hard_widths = [None, None, Some(4), Some(6), Some(6), Some(7), Some(7)];
+            let hard_widths = [
+                None,
+                None,
+                Some(4),
+                Some(DISK_USAGE_BAR_WIDTH as u16),
+                Some(7),
+                Some(7),
+                Some(8),
+                Some(8),
+            ];
             if recalculate_column_widths {
                 disk_widget_state.table_width_state.desired_column_widths = {
-                    let mut column_widths = DISK_HEADERS_LENS.clone();
-                    for row in sliced_vec {
+                    let mut column_widths = disk_headers_lens.clone();
+                    for (row, _, _) in sliced_vec {
                         for (col, entry) in row.iter().enumerate() {
                             if entry.len() as u16 > column_widths[col] {
                                 column_widths[col] = entry.len() as u16;
@@ -102,11 +110,11 @@ impl DiskTableWidget for Painter {
                 disk_widget_state.table_width_state.calculated_column_widths = get_column_widths(
                     draw_loc.width,
                     &hard_widths,
-                    &(DISK_HEADERS_LENS
+                    &(disk_headers_lens
                         .iter()
                         .map(|w| Some(*w))
                         .collect::<Vec<_>>()),
-                    &[Some(0.2), Some(0.2), None, None, None, None, None],
+                    &[Some(0.2), Some(0.2), None, None, None, None, None, None],
                     &(disk_widget_state
                         .table_width_state
                         .desired_column_widths
@@ -120,7 +128,7 @@ impl DiskTableWidget for Painter {
             let dcw = &disk_widget_state.table_width_state.desired_column_widths;
             let ccw = &disk_widget_state.table_width_state.calculated_column_widths;
             let disk_rows =
-                sliced_vec.iter().map(|disk_row| {
+                sliced_vec.iter().map(|(disk_row, is_above_threshold, _)| {
                     let truncated_data = disk_row.iter().zip(&hard_widths).enumerate().map(
                         |(itx, (entry, width))| {
                             if width.is_none() {
@@ -151,7 +159,11 @@ impl DiskTableWidget for Painter {
                         },
                     );
 
-                    Row::Data(truncated_data)
+                    if *is_above_threshold {
+                        Row::StyledData(truncated_data, self.colours.high_disk_io_style)
+                    } else {
+                        Row::StyledData(truncated_data, self.colours.text_style)
+                    }
                 });
 
             // TODO: This seems to be bugged?  The selected text style gets "stuck"?  I think this gets fixed with tui 0.10?
@@ -164,22 +176,28 @@ impl DiskTableWidget for Painter {
                 (self.colours.border_style, self.colours.text_style)
             };
 
+            let title_text = format!(
+                " Disk ({} I/O) ",
+                app_state.canvas_data.disk_io_total_display
+            );
             let title = if app_state.is_expanded {
-                const TITLE_BASE: &str = " Disk ── Esc to go back ";
+                let title_base_len = UnicodeSegmentation::graphemes(title_text.as_str(), true)
+                    .count()
+                    + UnicodeSegmentation::graphemes("── Esc to go back ", true).count();
                 Spans::from(vec![
-                    Span::styled(" Disk ", self.colours.widget_title_style),
+                    Span::styled(title_text, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back, ",
-                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
-                            ))
+                            "─".repeat(
+                                usize::from(draw_loc.width).saturating_sub(title_base_len + 2)
+                            )
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(" Disk ", self.colours.widget_title_style))
+                Spans::from(Span::styled(title_text, self.colours.widget_title_style))
             };
 
             let disk_block = if draw_border {
@@ -201,9 +219,52 @@ impl DiskTableWidget for Painter {
                 .direction(Direction::Horizontal)
                 .split(draw_loc)[0];
 
+            // Rows can only be styled as a whole, so the usage bars are coloured by drawing them
+            // again over the table, which is laid out with a one-character gap between columns.
+            let table_area = disk_block.inner(margined_draw_loc);
+            let bar_column_index = 3;
+            let ccw = &disk_widget_state.table_width_state.calculated_column_widths;
+            let bar_x = table_area.x
+                + ccw
+                    .iter()
+                    .take(bar_column_index)
+                    .map(|width| width + 1)
+                    .sum::<u16>();
+            let bar_rows_y = table_area.y + 1 + table_gap;
+            let bar_areas = if ccw.get(bar_column_index).copied().unwrap_or(0)
+                >= DISK_USAGE_BAR_WIDTH as u16
+                && bar_x + DISK_USAGE_BAR_WIDTH as u16 <= table_area.x + table_area.width
+            {
+                sliced_vec
+                    .iter()
+                    .enumerate()
+                    .take_while(|(itx, _)| {
+                        bar_rows_y + (*itx as u16) < table_area.y + table_area.height
+                    })
+                    .map(|(itx, (disk_row, _, usage_level))| {
+                        let style = match usage_level {
+                            DiskUsageLevel::Normal => self.colours.disk_usage_bar_styles[0],
+                            DiskUsageLevel::High => self.colours.disk_usage_bar_styles[1],
+                            DiskUsageLevel::Critical => self.colours.disk_usage_bar_styles[2],
+                        };
+                        (
+                            Span::styled(disk_row[bar_column_index].as_str(), style),
+                            Rect::new(
+                                bar_x,
+                                bar_rows_y + itx as u16,
+                                DISK_USAGE_BAR_WIDTH as u16,
+                                1,
+                            ),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                Vec::new()
+            };
+
             // Draw!
             f.render_stateful_widget(
-                Table::new(DISK_HEADERS.iter(), disk_rows)
+                Table::new(disk_headers.iter(), disk_rows)
                     .block(disk_block)
                     .header_style(self.colours.table_header_style)
                     .highlight_style(highlight_style)
@@ -220,6 +281,9 @@ impl DiskTableWidget for Painter {
                 margined_draw_loc,
                 disk_table_state,
             );
+            for (bar, bar_area) in bar_areas {
+                f.render_widget(Paragraph::new(Spans::from(bar)), bar_area);
+            }
 
             if app_state.should_get_widget_bounds() {
                 // Update draw loc in widget map
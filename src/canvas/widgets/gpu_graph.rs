@@ -0,0 +1,204 @@
+use crate::{
+    app::App,
+    canvas::{drawing_utils::calculate_basic_use_bars, Painter},
+    constants::*,
+};
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    symbols::Marker,
+    terminal::Frame,
+    text::Span,
+    text::Spans,
+    widgets::{Axis, Block, Borders, Chart, Dataset, Row, Table},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+const GPU_LEGEND_HEADER: [&str; 4] = ["GPU", "Use%", "VRAM", "Temp"];
+const VRAM_BAR_LENGTH: usize = 10;
+
+pub trait GpuGraphWidget {
+    fn draw_gpu_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    );
+}
+
+impl GpuGraphWidget for Painter {
+    fn draw_gpu_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if let Some(gpu_widget_state) = app_state.gpu_state.widget_states.get_mut(&widget_id) {
+            let gpu_data = &app_state.canvas_data.gpu_data;
+
+            let display_time_labels = vec![
+                Span::styled(
+                    format!("{}s", gpu_widget_state.current_display_time / 1000),
+                    self.colours.graph_style,
+                ),
+                Span::styled("0s".to_string(), self.colours.graph_style),
+            ];
+            let y_axis_labels = vec![
+                Span::styled("0%", self.colours.graph_style),
+                Span::styled("100%", self.colours.graph_style),
+            ];
+
+            let x_axis = if app_state.app_config_fields.hide_time
+                || (app_state.app_config_fields.autohide_time
+                    && gpu_widget_state.autohide_timer.is_none())
+            {
+                Axis::default().bounds([-(gpu_widget_state.current_display_time as f64), 0.0])
+            } else if let Some(time) = gpu_widget_state.autohide_timer {
+                if std::time::Instant::now().duration_since(time).as_millis()
+                    < AUTOHIDE_TIMEOUT_MILLISECONDS as u128
+                {
+                    Axis::default()
+                        .bounds([-(gpu_widget_state.current_display_time as f64), 0.0])
+                        .style(self.colours.graph_style)
+                        .labels(display_time_labels)
+                } else {
+                    gpu_widget_state.autohide_timer = None;
+                    Axis::default().bounds([-(gpu_widget_state.current_display_time as f64), 0.0])
+                }
+            } else if draw_loc.height < TIME_LABEL_HEIGHT_LIMIT {
+                Axis::default().bounds([-(gpu_widget_state.current_display_time as f64), 0.0])
+            } else {
+                Axis::default()
+                    .bounds([-(gpu_widget_state.current_display_time as f64), 0.0])
+                    .style(self.colours.graph_style)
+                    .labels(display_time_labels)
+            };
+
+            let y_axis = Axis::default()
+                .style(self.colours.graph_style)
+                .bounds([0.0, 100.5])
+                .labels(y_axis_labels);
+
+            let use_dot = app_state.app_config_fields.use_dot;
+            let dataset_vector: Vec<Dataset<'_>> = gpu_data
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(itx, gpu)| {
+                    Dataset::default()
+                        .marker(if use_dot {
+                            Marker::Dot
+                        } else {
+                            Marker::Braille
+                        })
+                        .style(
+                            self.colours.cpu_colour_styles
+                                [itx % self.colours.cpu_colour_styles.len()],
+                        )
+                        .data(&gpu.gpu_data[..])
+                        .graph_type(tui::widgets::GraphType::Line)
+                })
+                .collect();
+
+            let is_on_widget = widget_id == app_state.current_widget.widget_id;
+            let border_style = if is_on_widget {
+                self.colours.highlighted_border_style
+            } else {
+                self.colours.border_style
+            };
+
+            let title_text = format!(" GPU ({}s) ", gpu_widget_state.current_display_time / 1000);
+            let title = if app_state.is_expanded {
+                let title_base_len = UnicodeSegmentation::graphemes(title_text.as_str(), true)
+                    .count()
+                    + UnicodeSegmentation::graphemes("── Esc to go back ", true).count();
+                Spans::from(vec![
+                    Span::styled(title_text, self.colours.widget_title_style),
+                    Span::styled(
+                        format!(
+                            "─{}─ Esc to go back ",
+                            "─".repeat(
+                                usize::from(draw_loc.width).saturating_sub(title_base_len + 2)
+                            )
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(title_text, self.colours.widget_title_style))
+            };
+
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style);
+            let inner_loc = block.inner(draw_loc);
+            f.render_widget(block, draw_loc);
+
+            // Split the graph and legend, giving the legend just enough room for the VRAM bars.
+            let vram_width = gpu_data
+                .iter()
+                .map(|gpu| gpu.vram_label.len())
+                .max()
+                .unwrap_or(0)
+                + VRAM_BAR_LENGTH
+                + 3;
+            let legend_width = std::cmp::min(
+                (vram_width + 24) as u16,
+                inner_loc.width.saturating_sub(inner_loc.width / 2),
+            );
+            let split_loc = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(0), Constraint::Length(legend_width)].as_ref())
+                .split(inner_loc);
+
+            f.render_widget(
+                Chart::new(dataset_vector).x_axis(x_axis).y_axis(y_axis),
+                split_loc[0],
+            );
+
+            let gpu_rows = gpu_data.iter().enumerate().map(|(itx, gpu)| {
+                let num_bars = calculate_basic_use_bars(gpu.vram_ratio * 100.0, VRAM_BAR_LENGTH);
+                let vram_bar = format!(
+                    "[{}{}] {}",
+                    "|".repeat(num_bars),
+                    " ".repeat(VRAM_BAR_LENGTH - num_bars),
+                    gpu.vram_label
+                );
+
+                Row::StyledData(
+                    vec![
+                        gpu.gpu_name.clone(),
+                        gpu.utilization_label.clone(),
+                        vram_bar,
+                        gpu.temperature_label.clone(),
+                    ]
+                    .into_iter(),
+                    self.colours.cpu_colour_styles[itx % self.colours.cpu_colour_styles.len()],
+                )
+            });
+
+            f.render_widget(
+                Table::new(GPU_LEGEND_HEADER.iter(), gpu_rows)
+                    .block(
+                        Block::default()
+                            .borders(Borders::LEFT)
+                            .border_style(border_style),
+                    )
+                    .header_style(self.colours.table_header_style)
+                    .widths(&[
+                        Constraint::Min(3),
+                        Constraint::Length(4),
+                        Constraint::Length(vram_width as u16),
+                        Constraint::Length(4),
+                    ])
+                    .header_gap(0),
+                split_loc[1],
+            );
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
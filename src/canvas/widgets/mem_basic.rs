@@ -4,6 +4,8 @@ use crate::{
     constants::*,
 };
 
+use unicode_width::UnicodeWidthStr;
+
 use tui::{
     backend::Backend,
     layout::{Constraint, Layout, Rect},
@@ -52,32 +54,24 @@ impl MemBasicWidget for Painter {
         };
 
         // +7 due to 3 + 2 + 2 columns for the name & space + bar bounds + margin spacing
-        // Then + length of fraction
+        // Then + width of fraction, which can hold multi-byte characters like the zram
+        // arrow
+        let mem_label_frac_width =
+            UnicodeWidthStr::width(app_state.canvas_data.mem_label_frac.trim());
         let ram_bar_length = usize::from(
             draw_loc
                 .width
                 .saturating_sub(7)
-                .saturating_sub(app_state.canvas_data.mem_label_frac.trim().len() as u16),
-        );
-        let swap_bar_length = usize::from(
-            draw_loc
-                .width
-                .saturating_sub(7)
-                .saturating_sub(app_state.canvas_data.swap_label_frac.trim().len() as u16),
+                .saturating_sub(mem_label_frac_width as u16),
         );
 
         let num_bars_ram = calculate_basic_use_bars(ram_use_percentage, ram_bar_length);
-        let num_bars_swap = calculate_basic_use_bars(swap_use_percentage, swap_bar_length);
         // TODO: Use different styling for the frac.
         let mem_label = if app_state.basic_mode_use_percent {
             format!(
                 "RAM[{}{}{:3.0}%]\n",
                 "|".repeat(num_bars_ram),
-                " ".repeat(
-                    ram_bar_length - num_bars_ram
-                        + app_state.canvas_data.mem_label_frac.trim().len()
-                        - 4
-                ),
+                " ".repeat(ram_bar_length - num_bars_ram + mem_label_frac_width - 4),
                 ram_use_percentage.round()
             )
         } else {
@@ -88,30 +82,44 @@ impl MemBasicWidget for Painter {
                 &app_state.canvas_data.mem_label_frac.trim()
             )
         };
-        let swap_label = if app_state.basic_mode_use_percent {
-            format!(
-                "SWP[{}{}{:3.0}%]",
-                "|".repeat(num_bars_swap),
-                " ".repeat(
-                    swap_bar_length - num_bars_swap
-                        + app_state.canvas_data.swap_label_frac.trim().len()
-                        - 4
-                ),
-                swap_use_percentage.round()
-            )
-        } else {
-            format!(
-                "SWP[{}{}{}]",
-                "|".repeat(num_bars_swap),
-                " ".repeat(swap_bar_length - num_bars_swap),
-                &app_state.canvas_data.swap_label_frac.trim()
-            )
-        };
+        // Systems without swap only get the RAM bar.
+        let swap_label = app_state
+            .canvas_data
+            .swap_labels
+            .as_ref()
+            .map(|(_, swap_label_frac)| {
+                let swap_label_frac_width = UnicodeWidthStr::width(swap_label_frac.trim());
+                let swap_bar_length = usize::from(
+                    draw_loc
+                        .width
+                        .saturating_sub(7)
+                        .saturating_sub(swap_label_frac_width as u16),
+                );
+                let num_bars_swap = calculate_basic_use_bars(swap_use_percentage, swap_bar_length);
+                if app_state.basic_mode_use_percent {
+                    format!(
+                        "SWP[{}{}{:3.0}%]",
+                        "|".repeat(num_bars_swap),
+                        " ".repeat(swap_bar_length - num_bars_swap + swap_label_frac_width - 4),
+                        swap_use_percentage.round()
+                    )
+                } else {
+                    format!(
+                        "SWP[{}{}{}]",
+                        "|".repeat(num_bars_swap),
+                        " ".repeat(swap_bar_length - num_bars_swap),
+                        swap_label_frac.trim()
+                    )
+                }
+            });
 
-        let mem_text = vec![
-            Spans::from(Span::styled(mem_label, self.colours.ram_style)),
-            Spans::from(Span::styled(swap_label, self.colours.swap_style)),
-        ];
+        let mut mem_text = vec![Spans::from(Span::styled(mem_label, self.colours.ram_style))];
+        if let Some(swap_label) = swap_label {
+            mem_text.push(Spans::from(Span::styled(
+                swap_label,
+                self.colours.swap_style,
+            )));
+        }
 
         f.render_widget(
             Paragraph::new(mem_text).block(Block::default()),
@@ -24,6 +24,7 @@ impl MemGraphWidget for Painter {
         if let Some(mem_widget_state) = app_state.mem_state.widget_states.get_mut(&widget_id) {
             let mem_data: &[(f64, f64)] = &app_state.canvas_data.mem_data;
             let swap_data: &[(f64, f64)] = &app_state.canvas_data.swap_data;
+            let arc_data: &[(f64, f64)] = &app_state.canvas_data.arc_data;
 
             let display_time_labels = vec![
                 Span::styled(
@@ -86,23 +87,76 @@ impl MemGraphWidget for Painter {
                     .graph_type(tui::widgets::GraphType::Line),
             );
 
-            // FIXME: [SWAP] Hide this if denominator is 0...
-            let swap_label = format!(
-                "SWP:{}{}",
-                app_state.canvas_data.swap_label_percent, app_state.canvas_data.swap_label_frac
+            // Without any swap, its line would just sit at zero.
+            let swap_label = app_state.canvas_data.swap_labels.as_ref().map(
+                |(swap_label_percent, swap_label_frac)| {
+                    format!("SWP:{}{}", swap_label_percent, swap_label_frac)
+                },
             );
-            mem_canvas_vec.push(
-                Dataset::default()
-                    .name(&swap_label)
-                    .marker(if app_state.app_config_fields.use_dot {
-                        Marker::Dot
-                    } else {
-                        Marker::Braille
-                    })
-                    .style(self.colours.swap_style)
-                    .data(&swap_data)
-                    .graph_type(tui::widgets::GraphType::Line),
+            if let Some(swap_label) = &swap_label {
+                mem_canvas_vec.push(
+                    Dataset::default()
+                        .name(swap_label)
+                        .marker(if app_state.app_config_fields.use_dot {
+                            Marker::Dot
+                        } else {
+                            Marker::Braille
+                        })
+                        .style(self.colours.swap_style)
+                        .data(&swap_data)
+                        .graph_type(tui::widgets::GraphType::Line),
+                );
+            }
+
+            let arc_label = app_state.canvas_data.arc_labels.as_ref().map(
+                |(arc_label_percent, arc_label_frac)| {
+                    format!("ARC:{}{}", arc_label_percent, arc_label_frac)
+                },
             );
+            if let Some(arc_label) = &arc_label {
+                mem_canvas_vec.push(
+                    Dataset::default()
+                        .name(arc_label)
+                        .marker(if app_state.app_config_fields.use_dot {
+                            Marker::Dot
+                        } else {
+                            Marker::Braille
+                        })
+                        .style(self.colours.arc_style)
+                        .data(arc_data)
+                        .graph_type(tui::widgets::GraphType::Line),
+                );
+            }
+
+            // These have no data, and are just listed in the legend.
+            if let Some(swap_io_label) = &app_state.canvas_data.swap_io_label {
+                mem_canvas_vec.push(
+                    Dataset::default()
+                        .name(swap_io_label)
+                        .style(self.colours.swap_style),
+                );
+            }
+            let huge_page_labels = if mem_widget_state.is_showing_huge_pages {
+                vec![
+                    app_state.canvas_data.huge_page_labels.as_ref().map(
+                        |(huge_page_label_percent, huge_page_label_frac)| {
+                            format!("HGP:{}{}", huge_page_label_percent, huge_page_label_frac)
+                        },
+                    ),
+                    app_state.canvas_data.thp_labels.as_ref().map(
+                        |(thp_label_percent, thp_label_frac)| {
+                            format!("THP:{}{}", thp_label_percent, thp_label_frac)
+                        },
+                    ),
+                ]
+            } else {
+                vec![]
+            };
+            mem_canvas_vec.extend(huge_page_labels.iter().flatten().map(|label| {
+                Dataset::default()
+                    .name(label)
+                    .style(self.colours.text_style)
+            }));
 
             let is_on_widget = widget_id == app_state.current_widget.widget_id;
             let border_style = if is_on_widget {
@@ -111,25 +165,28 @@ impl MemGraphWidget for Painter {
                 self.colours.border_style
             };
 
+            let title_text = format!(
+                " Memory ({}s) ",
+                mem_widget_state.current_display_time / 1000
+            );
             let title = if app_state.is_expanded {
-                const TITLE_BASE: &str = " Memory ── Esc to go back ";
+                let title_base_len = UnicodeSegmentation::graphemes(title_text.as_str(), true)
+                    .count()
+                    + UnicodeSegmentation::graphemes("── Esc to go back ", true).count();
                 Spans::from(vec![
-                    Span::styled(" Memory ", self.colours.widget_title_style),
+                    Span::styled(title_text, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
-                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
-                            ))
+                            "─".repeat(
+                                usize::from(draw_loc.width).saturating_sub(title_base_len + 2)
+                            )
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(
-                    " Memory ".to_string(),
-                    self.colours.widget_title_style,
-                ))
+                Spans::from(Span::styled(title_text, self.colours.widget_title_style))
             };
 
             f.render_widget(
@@ -87,10 +87,10 @@ impl NetworkGraphWidget for Painter {
 
         /// Returns the required max data point and labels.
         fn adjust_network_data_point(
-            rx: &[Point], tx: &[Point], time_start: f64, time_end: f64,
+            rx: &[Point], tx: &[Point], time_start: f64, time_end: f64, min_max_val: f64,
         ) -> (f64, Vec<String>) {
             // First, filter and find the maximal rx or tx so we know how to scale
-            let mut max_val_bytes = 0.0;
+            let mut max_val_bytes = min_max_val;
             let filtered_rx = rx
                 .iter()
                 .cloned()
@@ -172,16 +172,60 @@ impl NetworkGraphWidget for Painter {
             (true_max_val, labels)
         }
 
+        /// Returns the points of a dashed horizontal line at the given value, spanning the graph.
+        fn get_threshold_points(value: f64, time_start: f64, width: u16) -> Vec<Point> {
+            // Braille markers fit two points per column; draw dashes of four points.
+            let num_points = usize::from(width) * 2;
+            (0..num_points)
+                .filter(|index| (index / 4) % 2 == 0)
+                .map(|index| {
+                    (
+                        time_start - time_start * index as f64 / num_points as f64,
+                        value,
+                    )
+                })
+                .collect()
+        }
+
         if let Some(network_widget_state) = app_state.net_state.widget_states.get_mut(&widget_id) {
             let network_data_rx: &[(f64, f64)] = &app_state.canvas_data.network_data_rx;
             let network_data_tx: &[(f64, f64)] = &app_state.canvas_data.network_data_tx;
+            let thresholds = [
+                (&app_state.canvas_data.rx_threshold, self.colours.rx_style),
+                (&app_state.canvas_data.tx_threshold, self.colours.tx_style),
+            ];
 
             let (max_range, labels) = adjust_network_data_point(
                 network_data_rx,
                 network_data_tx,
                 -(network_widget_state.current_display_time as f64),
                 0.0,
+                thresholds
+                    .iter()
+                    .filter_map(|(threshold, _style)| threshold.as_ref())
+                    .map(|threshold| threshold.value)
+                    .fold(0.0, f64::max),
             );
+            let threshold_points = thresholds
+                .iter()
+                .filter_map(|(threshold, style)| {
+                    threshold.as_ref().map(|threshold| {
+                        (
+                            threshold,
+                            if threshold.is_exceeded {
+                                self.colours.network_threshold_exceeded_style
+                            } else {
+                                *style
+                            },
+                            get_threshold_points(
+                                threshold.value,
+                                -(network_widget_state.current_display_time as f64),
+                                draw_loc.width,
+                            ),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>();
             let display_time_labels = vec![
                 Span::styled(
                     format!("{}s", network_widget_state.current_display_time / 1000),
@@ -232,22 +276,31 @@ impl NetworkGraphWidget for Painter {
                 self.colours.border_style
             };
 
+            let mut title_text = format!(
+                " Network ({}s) ",
+                network_widget_state.current_display_time / 1000
+            );
+            if !app_state.canvas_data.packets_display.is_empty() {
+                title_text += &format!("─ {} ", app_state.canvas_data.packets_display);
+            }
             let title = if app_state.is_expanded {
-                const TITLE_BASE: &str = " Network ── Esc to go back ";
+                let title_base_len = UnicodeSegmentation::graphemes(title_text.as_str(), true)
+                    .count()
+                    + UnicodeSegmentation::graphemes("── Esc to go back ", true).count();
                 Spans::from(vec![
-                    Span::styled(" Network ", self.colours.widget_title_style),
+                    Span::styled(title_text, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
-                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
-                            ))
+                            "─".repeat(
+                                usize::from(draw_loc.width).saturating_sub(title_base_len + 2)
+                            )
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(" Network ", self.colours.widget_title_style))
+                Spans::from(Span::styled(title_text, self.colours.widget_title_style))
             };
 
             let legend_constraints = if hide_legend {
@@ -256,7 +309,8 @@ impl NetworkGraphWidget for Painter {
                 (Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))
             };
 
-            let dataset = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
+            let mut dataset = if app_state.app_config_fields.use_old_network_legend && !hide_legend
+            {
                 let mut ret_val = vec![];
                 ret_val.push(
                     Dataset::default()
@@ -333,6 +387,14 @@ impl NetworkGraphWidget for Painter {
 
                 ret_val
             };
+            dataset.extend(threshold_points.iter().map(|(threshold, style, points)| {
+                Dataset::default()
+                    .name(&threshold.label)
+                    .marker(Marker::Braille)
+                    .style(*style)
+                    .data(points)
+                    .graph_type(tui::widgets::GraphType::Scatter)
+            }));
 
             f.render_widget(
                 Chart::new(dataset)
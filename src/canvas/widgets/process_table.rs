@@ -1,7 +1,12 @@
 use crate::{
-    app::App,
+    app::{
+        data_harvester::processes::ProcessSorting, query::SearchMatch, App, ProcessGroupingKey,
+        ProcessStateFilter,
+    },
     canvas::{
-        drawing_utils::{get_column_widths, get_search_start_position, get_start_position},
+        drawing_utils::{
+            build_search_query, get_column_widths, get_search_start_position, get_start_position,
+        },
         Painter,
     },
     constants::*,
@@ -16,7 +21,7 @@ use tui::{
 };
 
 use std::borrow::Cow;
-use unicode_segmentation::{GraphemeIndices, UnicodeSegmentation};
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 pub trait ProcessTableWidget {
@@ -55,6 +60,13 @@ pub trait ProcessTableWidget {
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
         widget_id: u64,
     );
+
+    /// Draws the username filter input box, below the search box.
+    ///
+    /// This should not be directly called.
+    fn draw_user_filter_input<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect, draw_border: bool,
+    );
 }
 
 impl ProcessTableWidget for Painter {
@@ -68,11 +80,29 @@ impl ProcessTableWidget for Painter {
             let header_len = process_widget_state.columns.longest_header_len;
 
             let mut proc_draw_loc = draw_loc;
+            let is_selected = app_state.current_widget.widget_id == widget_id
+                || app_state.current_widget.widget_id == widget_id + 1;
+            if app_state.user_filter_state.is_showing_input && is_selected {
+                let processes_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(
+                        [
+                            Constraint::Min(0),
+                            Constraint::Length(if draw_border { 3 } else { 1 }),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(proc_draw_loc);
+                proc_draw_loc = processes_chunk[0];
+
+                self.draw_user_filter_input(f, app_state, processes_chunk[1], draw_border);
+            }
+
             if process_widget_state.is_search_enabled() {
                 let processes_chunk = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints([Constraint::Min(0), Constraint::Length(search_height)].as_ref())
-                    .split(draw_loc);
+                    .split(proc_draw_loc);
                 proc_draw_loc = processes_chunk[0];
 
                 self.draw_search_field(
@@ -132,6 +162,71 @@ impl ProcessTableWidget for Painter {
                 (self.colours.border_style, self.colours.text_style)
             };
 
+            let mut title_text = match app_state.canvas_data.process_count_map.get(&widget_id) {
+                Some(count) => {
+                    let counts = if count.shown == count.total {
+                        format!("{}", count.total)
+                    } else {
+                        format!("{}/{}", count.shown, count.total)
+                    };
+                    match count.total_pids {
+                        Some(total_pids) => {
+                            format!(" Processes ({}) ({} PIDs) ", counts, total_pids)
+                        }
+                        None => format!(" Processes ({}) ", counts),
+                    }
+                }
+                None => " Processes ".to_string(),
+            };
+            if app_state.app_config_fields.hide_kernel_threads {
+                title_text.push_str("(kernel threads hidden) ");
+            }
+            if app_state.app_config_fields.use_current_cpu_total {
+                title_text.push_str("(CPU% of current usage) ");
+            }
+            if proc_widget_state.is_hiding_idle {
+                let hidden_idle = app_state
+                    .canvas_data
+                    .process_count_map
+                    .get(&widget_id)
+                    .map(|count| count.hidden_idle)
+                    .unwrap_or(0);
+                title_text.push_str(&format!("({} idle hidden) ", hidden_idle));
+            }
+            if proc_widget_state.followed_process.is_some() {
+                title_text.push_str("(following) ");
+            } else if let Some(follow_lost_instant) = proc_widget_state.follow_lost_instant {
+                if follow_lost_instant.elapsed().as_millis()
+                    < u128::from(FOLLOW_LOST_MESSAGE_MILLISECONDS)
+                {
+                    title_text.push_str("(followed process exited) ");
+                } else {
+                    proc_widget_state.follow_lost_instant = None;
+                }
+            }
+            if proc_widget_state.is_grouped {
+                match proc_widget_state.grouping_key {
+                    ProcessGroupingKey::Name => {}
+                    ProcessGroupingKey::User => title_text.push_str("(by user) "),
+                    ProcessGroupingKey::Container => title_text.push_str("(by container) "),
+                }
+            }
+            if let Some(user) = &app_state.username_filter {
+                title_text.push_str(&format!("(user: {}) ", user));
+            }
+            if proc_widget_state.state_filter != ProcessStateFilter::All {
+                title_text.push_str(&format!("(state: {}) ", proc_widget_state.state_filter));
+            }
+            if let Some((message, message_instant)) = &proc_widget_state.status_message {
+                if message_instant.elapsed().as_millis()
+                    < u128::from(PROCESS_STATUS_MESSAGE_MILLISECONDS)
+                {
+                    title_text.push_str(&format!("({}) ", message));
+                } else {
+                    proc_widget_state.status_message = None;
+                }
+            }
+
             let title = if app_state.is_expanded
                 && !proc_widget_state
                     .process_search_state
@@ -139,21 +234,23 @@ impl ProcessTableWidget for Painter {
                     .is_enabled
                 && !proc_widget_state.is_sort_open
             {
-                const TITLE_BASE: &str = " Processes ── Esc to go back ";
+                let title_base_len = UnicodeSegmentation::graphemes(title_text.as_str(), true)
+                    .count()
+                    + UnicodeSegmentation::graphemes("── Esc to go back ", true).count();
                 Spans::from(vec![
-                    Span::styled(" Processes ", self.colours.widget_title_style),
+                    Span::styled(title_text, self.colours.widget_title_style),
                     Span::styled(
                         format!(
                             "─{}─ Esc to go back ",
-                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
-                                UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
-                            ))
+                            "─".repeat(
+                                usize::from(draw_loc.width).saturating_sub(title_base_len + 2)
+                            )
                         ),
                         border_style,
                     ),
                 ])
             } else {
-                Spans::from(Span::styled(" Processes ", self.colours.widget_title_style))
+                Spans::from(Span::styled(title_text, self.colours.widget_title_style))
             };
 
             let process_block = if draw_border {
@@ -179,11 +276,12 @@ impl ProcessTableWidget for Painter {
                 } else {
                     app_state.app_config_fields.table_gap
                 };
+                let num_rows = usize::from(
+                    (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
+                );
+                proc_widget_state.scroll_state.num_visible_rows = num_rows;
                 let position = get_start_position(
-                    usize::from(
-                        (draw_loc.height + (1 - table_gap))
-                            .saturating_sub(self.table_height_offset),
-                    ),
+                    num_rows,
                     &proc_widget_state.scroll_state.scroll_direction,
                     &mut proc_widget_state.scroll_state.previous_scroll_position,
                     proc_widget_state.scroll_state.current_scroll_position,
@@ -218,33 +316,34 @@ impl ProcessTableWidget for Painter {
                 let process_headers = proc_widget_state.columns.get_column_headers(
                     &proc_widget_state.process_sorting_type,
                     proc_widget_state.is_process_sort_descending,
+                    if proc_widget_state.is_grouped {
+                        proc_widget_state.grouping_key.name_column_header()
+                    } else {
+                        None
+                    },
                 );
 
                 // Calculate widths
-                let hard_widths = if proc_widget_state.is_grouped {
-                    vec![
-                        Some(7),
-                        None,
-                        Some(8),
-                        Some(8),
-                        Some(8),
-                        Some(8),
-                        Some(7),
-                        Some(8),
-                    ]
-                } else {
-                    vec![
-                        Some(7),
-                        None,
-                        Some(8),
-                        Some(8),
-                        Some(8),
-                        Some(8),
-                        Some(7),
-                        Some(8),
-                        None,
-                    ]
-                };
+                let enabled_columns = proc_widget_state.columns.get_enabled_columns();
+                let hard_widths = enabled_columns
+                    .iter()
+                    .map(|column| match column {
+                        ProcessSorting::Count | ProcessSorting::Pid => Some(7),
+                        ProcessSorting::ProcessName
+                        | ProcessSorting::Command
+                        | ProcessSorting::User
+                        | ProcessSorting::Container
+                        | ProcessSorting::State => None,
+                        ProcessSorting::TotalRead => Some(7),
+                        ProcessSorting::FdCount => Some(6),
+                        ProcessSorting::ThreadCount => Some(7),
+                        ProcessSorting::Nice => Some(5),
+                        ProcessSorting::CpuTime => Some(11),
+                        ProcessSorting::StartTime => Some(7),
+                        ProcessSorting::Age => Some(6),
+                        _ => Some(8),
+                    })
+                    .collect::<Vec<_>>();
 
                 if recalculate_column_widths {
                     let mut column_widths = process_headers
@@ -288,51 +387,27 @@ impl ProcessTableWidget for Painter {
                         })
                         .collect::<Vec<_>>();
 
-                    let soft_widths_max = if proc_widget_state.is_grouped {
-                        if proc_widget_state.is_using_command {
-                            vec![None, Some(0.7), None, None, None, None, None, None]
-                        } else if proc_widget_state.is_tree_mode {
-                            vec![None, Some(0.5), None, None, None, None, None, None]
-                        } else {
-                            vec![None, Some(0.4), None, None, None, None, None, None]
-                        }
-                    } else if proc_widget_state.is_using_command {
-                        vec![
-                            None,
-                            Some(0.7),
-                            None,
-                            None,
-                            None,
-                            None,
-                            None,
-                            None,
-                            Some(0.2),
-                        ]
+                    let name_width_max = if proc_widget_state.is_using_command {
+                        0.7
                     } else if proc_widget_state.is_tree_mode {
-                        vec![
-                            None,
-                            Some(0.5),
-                            None,
-                            None,
-                            None,
-                            None,
-                            None,
-                            None,
-                            Some(0.2),
-                        ]
+                        0.5
+                    } else if proc_widget_state.is_grouped {
+                        0.4
                     } else {
-                        vec![
-                            None,
-                            Some(0.3),
-                            None,
-                            None,
-                            None,
-                            None,
-                            None,
-                            None,
-                            Some(0.2),
-                        ]
+                        0.3
                     };
+                    let soft_widths_max = enabled_columns
+                        .iter()
+                        .map(|column| match column {
+                            ProcessSorting::ProcessName | ProcessSorting::Command => {
+                                Some(name_width_max)
+                            }
+                            ProcessSorting::User => Some(0.1),
+                            ProcessSorting::Container => Some(0.2),
+                            ProcessSorting::State => Some(0.2),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
 
                     proc_widget_state.table_width_state.calculated_column_widths =
                         get_column_widths(
@@ -362,51 +437,116 @@ impl ProcessTableWidget for Painter {
                 let dcw = &proc_widget_state.table_width_state.desired_column_widths;
                 let ccw = &proc_widget_state.table_width_state.calculated_column_widths;
 
-                let process_rows = sliced_vec.iter().map(|(data, disabled)| {
-                    let truncated_data = data.iter().zip(&hard_widths).enumerate().map(
-                        |(itx, ((entry, alternative), width))| {
-                            if let (Some(desired_col_width), Some(calculated_col_width)) =
-                                (dcw.get(itx), ccw.get(itx))
-                            {
-                                if width.is_none() {
-                                    if *desired_col_width > *calculated_col_width
-                                        && *calculated_col_width > 0
+                // Row styles are looked up via the unstringified data, which has the same order.
+                let tagged_pids = &app_state.tagged_pids;
+                let sliced_styles = app_state
+                    .canvas_data
+                    .finalized_process_data_map
+                    .get(&widget_id)
+                    .map(|finalized_data| {
+                        finalized_data
+                            .iter()
+                            .skip(start_position)
+                            .map(|process| {
+                                if !tagged_pids.is_empty()
+                                    && (tagged_pids.contains(&process.pid)
+                                        || process
+                                            .group_pids
+                                            .iter()
+                                            .any(|pid| tagged_pids.contains(pid)))
+                                {
+                                    Some(self.colours.tagged_process_style)
+                                } else {
+                                    match process.process_char {
+                                        'Z' => Some(self.colours.zombie_process_style),
+                                        'S' | 'I' => Some(self.colours.sleeping_process_style),
+                                        _ => None,
+                                    }
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+
+                let process_rows =
+                    sliced_vec
+                        .iter()
+                        .enumerate()
+                        .map(|(row_itx, (data, disabled))| {
+                            let truncated_data = data.iter().zip(&hard_widths).enumerate().map(
+                                |(itx, ((entry, alternative), width))| {
+                                    if let (Some(desired_col_width), Some(calculated_col_width)) =
+                                        (dcw.get(itx), ccw.get(itx))
                                     {
-                                        let graphemes =
-                                            UnicodeSegmentation::graphemes(entry.as_str(), true)
+                                        if width.is_none() {
+                                            if *desired_col_width > *calculated_col_width
+                                                && *calculated_col_width > 0
+                                            {
+                                                let graphemes = UnicodeSegmentation::graphemes(
+                                                    entry.as_str(),
+                                                    true,
+                                                )
                                                 .collect::<Vec<&str>>();
 
-                                        if let Some(alternative) = alternative {
-                                            Cow::Borrowed(alternative)
-                                        } else if graphemes.len() > *calculated_col_width as usize
-                                            && *calculated_col_width > 1
-                                        {
-                                            // Truncate with ellipsis
-                                            let first_n = graphemes
-                                                [..(*calculated_col_width as usize - 1)]
-                                                .concat();
-                                            Cow::Owned(format!("{}…", first_n))
+                                                if let Some(alternative) = alternative {
+                                                    Cow::Borrowed(alternative)
+                                                } else if graphemes.len()
+                                                    > *calculated_col_width as usize
+                                                    && *calculated_col_width > 1
+                                                {
+                                                    // Truncate with ellipsis
+                                                    let first_n = graphemes
+                                                        [..(*calculated_col_width as usize - 1)]
+                                                        .concat();
+                                                    Cow::Owned(format!("{}…", first_n))
+                                                } else {
+                                                    Cow::Borrowed(entry)
+                                                }
+                                            } else {
+                                                Cow::Borrowed(entry)
+                                            }
                                         } else {
                                             Cow::Borrowed(entry)
                                         }
                                     } else {
                                         Cow::Borrowed(entry)
                                     }
-                                } else {
-                                    Cow::Borrowed(entry)
-                                }
+                                },
+                            );
+
+                            if *disabled {
+                                Row::StyledData(truncated_data, self.colours.disabled_text_style)
+                            } else if let Some(Some(style)) = sliced_styles.get(row_itx) {
+                                Row::StyledData(truncated_data, *style)
                             } else {
-                                Cow::Borrowed(entry)
+                                Row::Data(truncated_data)
                             }
-                        },
-                    );
+                        });
 
-                    if *disabled {
-                        Row::StyledData(truncated_data, self.colours.disabled_text_style)
-                    } else {
-                        Row::Data(truncated_data)
+                if recalculate_column_widths {
+                    // Remember where each header was drawn so that clicking it can change the
+                    // sorting.  Columns are separated by a single space, and the right bound is
+                    // exclusive so that hidden (zero-width) columns can't be clicked.
+                    let header_loc = process_block.inner(margined_draw_loc);
+                    let mut current_x = header_loc.x;
+                    let mut header_click_locs: Vec<((u16, u16), (u16, u16))> = vec![];
+                    for calculated_width in
+                        &proc_widget_state.table_width_state.calculated_column_widths
+                    {
+                        header_click_locs.push((
+                            (current_x, header_loc.y),
+                            (current_x + calculated_width, header_loc.y),
+                        ));
+                        current_x += calculated_width + 1;
                     }
-                });
+                    proc_widget_state.header_click_locs = Some(header_click_locs);
+                }
+
+                let table_loc = process_block.inner(margined_draw_loc);
+                let selected_position = proc_widget_state
+                    .scroll_state
+                    .current_scroll_position
+                    .saturating_sub(start_position);
 
                 // TODO: gotop's "x out of y" thing is really nice to help keep track of the scroll position.  Add to everything?
                 f.render_stateful_widget(
@@ -429,6 +569,143 @@ impl ProcessTableWidget for Painter {
                     margined_draw_loc,
                     proc_table_state,
                 );
+
+                // Highlight what each row matched in the search, either in the name column or,
+                // if only the PID matched, the PID column.
+                if let Some(finalized_process_data) = app_state
+                    .canvas_data
+                    .finalized_process_data_map
+                    .get(&widget_id)
+                {
+                    let ccw = &proc_widget_state.table_width_state.calculated_column_widths;
+                    let name_column = enabled_columns.iter().position(|column| {
+                        matches!(
+                            column,
+                            ProcessSorting::ProcessName | ProcessSorting::Command
+                        )
+                    });
+                    let pid_column = enabled_columns
+                        .iter()
+                        .position(|column| matches!(column, ProcessSorting::Pid));
+
+                    let first_row_y = table_loc.y + 1 + table_gap;
+                    let num_rows = usize::from(table_loc.bottom().saturating_sub(first_row_y));
+                    // Mirrors how the table scrolls to keep the selected row visible.
+                    let table_offset = (selected_position + 1).saturating_sub(num_rows);
+
+                    for (itx, (process, (row, _disabled))) in finalized_process_data
+                        .iter()
+                        .skip(start_position)
+                        .zip(sliced_vec)
+                        .skip(table_offset)
+                        .take(num_rows)
+                        .enumerate()
+                    {
+                        let (column, range, prefix_len) = match &process.search_match {
+                            Some(SearchMatch::Name(range)) => {
+                                let matched_text = if proc_widget_state.is_using_command {
+                                    &process.command
+                                } else {
+                                    &process.name
+                                };
+                                // In tree mode, the name comes after the tree's branches.
+                                let prefix_len = row
+                                    .get(name_column.unwrap_or(0))
+                                    .map(|(entry, _)| {
+                                        entry.len().saturating_sub(matched_text.len())
+                                    })
+                                    .unwrap_or(0);
+                                (name_column, range, prefix_len)
+                            }
+                            Some(SearchMatch::Pid(range)) => (pid_column, range, 0),
+                            None => continue,
+                        };
+
+                        if let Some(column) = column {
+                            if let (Some((entry, _)), Some(width)) =
+                                (row.get(column), ccw.get(column))
+                            {
+                                let (start, end) =
+                                    (prefix_len + range.start, prefix_len + range.end);
+                                let matched = match entry.get(start..end) {
+                                    Some(matched) => matched,
+                                    None => continue,
+                                };
+
+                                // Truncated entries end with an ellipsis, which shouldn't be highlighted.
+                                let visible_width =
+                                    if UnicodeWidthStr::width(entry.as_str()) as u16 > *width {
+                                        width.saturating_sub(1)
+                                    } else {
+                                        *width
+                                    };
+                                let start_width = UnicodeWidthStr::width(&entry[..start]) as u16;
+                                if start_width >= visible_width {
+                                    continue;
+                                }
+
+                                // Columns are separated by a single space.
+                                let column_x =
+                                    table_loc.x + ccw[..column].iter().sum::<u16>() + column as u16;
+                                f.render_widget(
+                                    Paragraph::new(Span::styled(
+                                        matched,
+                                        self.colours.search_match_style,
+                                    )),
+                                    Rect::new(
+                                        column_x + start_width,
+                                        first_row_y + itx as u16,
+                                        std::cmp::min(
+                                            UnicodeWidthStr::width(matched) as u16,
+                                            visible_width - start_width,
+                                        ),
+                                        1,
+                                    ),
+                                );
+                            }
+                        }
+                    }
+
+                    // Long names are cut off, so show the selected one in full over the bottom
+                    // border.
+                    if is_on_widget && draw_border && margined_draw_loc.height > 2 {
+                        let current_scroll_position =
+                            proc_widget_state.scroll_state.current_scroll_position;
+                        let is_truncated = name_column
+                            .and_then(|column| {
+                                let (row, _disabled) = process_data.get(current_scroll_position)?;
+                                let (entry, _alternative) = row.get(column)?;
+                                Some(
+                                    UnicodeWidthStr::width(entry.as_str()) as u16
+                                        > *ccw.get(column)?,
+                                )
+                            })
+                            .unwrap_or(false);
+
+                        if let Some(process) = finalized_process_data
+                            .get(current_scroll_position)
+                            .filter(|_| is_truncated)
+                        {
+                            let full_name = if proc_widget_state.is_using_command {
+                                &process.command
+                            } else {
+                                &process.name
+                            };
+                            f.render_widget(
+                                Paragraph::new(Span::styled(
+                                    format!(" {} ", full_name),
+                                    self.colours.widget_title_style,
+                                )),
+                                Rect::new(
+                                    margined_draw_loc.x + 1,
+                                    margined_draw_loc.bottom() - 1,
+                                    margined_draw_loc.width.saturating_sub(2),
+                                    1,
+                                ),
+                            );
+                        }
+                    }
+                }
             } else {
                 f.render_widget(process_block, margined_draw_loc);
             }
@@ -450,54 +727,6 @@ impl ProcessTableWidget for Painter {
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
         widget_id: u64,
     ) {
-        fn build_query<'a>(
-            is_on_widget: bool, grapheme_indices: GraphemeIndices<'a>, start_position: usize,
-            cursor_position: usize, query: &str, currently_selected_text_style: tui::style::Style,
-            text_style: tui::style::Style,
-        ) -> Vec<Span<'a>> {
-            let mut current_grapheme_posn = 0;
-
-            if is_on_widget {
-                let mut res = grapheme_indices
-                    .filter_map(|grapheme| {
-                        current_grapheme_posn += UnicodeWidthStr::width(grapheme.1);
-
-                        if current_grapheme_posn <= start_position {
-                            None
-                        } else {
-                            let styled = if grapheme.0 == cursor_position {
-                                Span::styled(grapheme.1, currently_selected_text_style)
-                            } else {
-                                Span::styled(grapheme.1, text_style)
-                            };
-                            Some(styled)
-                        }
-                    })
-                    .collect::<Vec<_>>();
-
-                if cursor_position >= query.len() {
-                    res.push(Span::styled(" ", currently_selected_text_style))
-                }
-
-                res
-            } else {
-                // This is easier - we just need to get a range of graphemes, rather than
-                // dealing with possibly inserting a cursor (as none is shown!)
-
-                grapheme_indices
-                    .filter_map(|grapheme| {
-                        current_grapheme_posn += UnicodeWidthStr::width(grapheme.1);
-                        if current_grapheme_posn <= start_position {
-                            None
-                        } else {
-                            let styled = Span::styled(grapheme.1, text_style);
-                            Some(styled)
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            }
-        }
-
         if let Some(proc_widget_state) =
             app_state.proc_state.widget_states.get_mut(&(widget_id - 1))
         {
@@ -528,7 +757,7 @@ impl ProcessTableWidget for Painter {
 
             // TODO: [CURSOR] blank cursor if not selected
             // TODO: [CURSOR] blinking cursor?
-            let query_with_cursor = build_query(
+            let query_with_cursor = build_search_query(
                 is_on_widget,
                 grapheme_indices,
                 start_position,
@@ -576,9 +805,18 @@ impl ProcessTableWidget for Painter {
                 self.colours.text_style
             };
 
+            let search_state = &proc_widget_state.process_search_state.search_state;
+            let is_excluding = search_state.error_message.is_none()
+                && !search_state.is_invalid_or_blank_search()
+                && search_state
+                    .query
+                    .as_ref()
+                    .map(|query| query.is_excluding)
+                    .unwrap_or(false);
+
             // FIXME: [MOUSE] Mouse support for these in search
             // FIXME: [MOVEMENT] Movement support for these in search
-            let option_text = Spans::from(vec![
+            let mut option_text = Spans::from(vec![
                 Span::styled(
                     format!("Case({})", if self.is_mac_os { "F1" } else { "Alt+C" }),
                     case_style,
@@ -594,19 +832,25 @@ impl ProcessTableWidget for Painter {
                     regex_style,
                 ),
             ]);
+            if is_excluding {
+                option_text.0.push(Span::raw("  "));
+                option_text.0.push(Span::styled(
+                    "INVERTED",
+                    self.colours.currently_selected_text_style,
+                ));
+            }
 
-            search_text.push(Spans::from(Span::styled(
-                if let Some(err) = &proc_widget_state
-                    .process_search_state
-                    .search_state
-                    .error_message
-                {
-                    err.as_str()
-                } else {
-                    ""
-                },
-                self.colours.invalid_query_style,
-            )));
+            search_text.push(if let Some(err) = &search_state.error_message {
+                Spans::from(Span::styled(err.as_str(), self.colours.invalid_query_style))
+            } else if is_excluding {
+                // Make it clear that an empty table means everything matched.
+                Spans::from(Span::styled(
+                    "Excluding matching processes",
+                    self.colours.table_header_style,
+                ))
+            } else {
+                Spans::from(Span::raw(""))
+            });
             search_text.push(option_text);
 
             let current_border_style = if proc_widget_state
@@ -793,4 +1037,36 @@ impl ProcessTableWidget for Painter {
             }
         }
     }
+
+    fn draw_user_filter_input<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect, draw_border: bool,
+    ) {
+        let input = Spans::from(vec![
+            Span::styled("User: ", self.colours.table_header_style),
+            Span::styled(
+                format!("{}_", app_state.user_filter_state.input),
+                self.colours.currently_selected_text_style,
+            ),
+        ]);
+
+        let block = if draw_border {
+            Block::default()
+                .title(Span::styled(
+                    " Filter by user ── Enter to apply, Esc to cancel ",
+                    self.colours.widget_title_style,
+                ))
+                .borders(Borders::ALL)
+                .border_style(self.colours.highlighted_border_style)
+        } else {
+            Block::default().borders(Borders::NONE)
+        };
+
+        f.render_widget(
+            Paragraph::new(input)
+                .block(block)
+                .style(self.colours.text_style)
+                .alignment(Alignment::Left),
+            draw_loc,
+        );
+    }
 }
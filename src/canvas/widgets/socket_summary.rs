@@ -0,0 +1,73 @@
+use crate::{app::App, canvas::Painter};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+pub trait SocketSummaryWidget {
+    fn draw_socket_summary<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    );
+}
+
+impl SocketSummaryWidget for Painter {
+    fn draw_socket_summary<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+
+        let title_text = " Sockets ";
+        let title = if app_state.is_expanded {
+            let title_base_len = UnicodeSegmentation::graphemes(title_text, true).count()
+                + UnicodeSegmentation::graphemes("── Esc to go back ", true).count();
+            Spans::from(vec![
+                Span::styled(title_text, self.colours.widget_title_style),
+                Span::styled(
+                    format!(
+                        "─{}─ Esc to go back ",
+                        "─".repeat(usize::from(draw_loc.width).saturating_sub(title_base_len + 2))
+                    ),
+                    border_style,
+                ),
+            ])
+        } else {
+            Spans::from(Span::styled(title_text, self.colours.widget_title_style))
+        };
+
+        let socket_text = app_state
+            .canvas_data
+            .socket_data
+            .iter()
+            .map(|line| Spans::from(Span::styled(line.as_str(), self.colours.text_style)))
+            .collect::<Vec<_>>();
+
+        f.render_widget(
+            Paragraph::new(socket_text).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            ),
+            draw_loc,
+        );
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
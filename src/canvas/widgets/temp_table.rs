@@ -15,6 +15,7 @@ use crate::{
         Painter,
     },
     constants::*,
+    data_conversion::TemperatureLevel,
 };
 use std::borrow::Cow;
 use unicode_segmentation::UnicodeSegmentation;
@@ -41,17 +42,20 @@ impl TempTableWidget for Painter {
     ) {
         let recalculate_column_widths = app_state.should_get_widget_bounds();
         if let Some(temp_widget_state) = app_state.temp_state.widget_states.get_mut(&widget_id) {
-            let temp_sensor_data: &mut [Vec<String>] = &mut app_state.canvas_data.temp_sensor_data;
+            let temp_sensor_data: &mut [(Vec<String>, TemperatureLevel)] =
+                &mut app_state.canvas_data.temp_sensor_data;
 
             let table_gap = if draw_loc.height < TABLE_GAP_HEIGHT_LIMIT {
                 0
             } else {
                 app_state.app_config_fields.table_gap
             };
+            let num_rows = usize::from(
+                (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
+            );
+            temp_widget_state.scroll_state.num_visible_rows = num_rows;
             let start_position = get_start_position(
-                usize::from(
-                    (draw_loc.height + (1 - table_gap)).saturating_sub(self.table_height_offset),
-                ),
+                num_rows,
                 &temp_widget_state.scroll_state.scroll_direction,
                 &mut temp_widget_state.scroll_state.previous_scroll_position,
                 temp_widget_state.scroll_state.current_scroll_position,
@@ -72,7 +76,7 @@ impl TempTableWidget for Painter {
             if recalculate_column_widths {
                 temp_widget_state.table_width_state.desired_column_widths = {
                     let mut column_widths = TEMP_HEADERS_LENS.clone();
-                    for row in sliced_vec {
+                    for (row, _level) in sliced_vec {
                         for (col, entry) in row.iter().enumerate() {
                             if entry.len() as u16 > column_widths[col] {
                                 column_widths[col] = entry.len() as u16;
@@ -103,7 +107,7 @@ impl TempTableWidget for Painter {
             let dcw = &temp_widget_state.table_width_state.desired_column_widths;
             let ccw = &temp_widget_state.table_width_state.calculated_column_widths;
             let temperature_rows =
-                sliced_vec.iter().map(|temp_row| {
+                sliced_vec.iter().map(|(temp_row, level)| {
                     let truncated_data = temp_row.iter().zip(&hard_widths).enumerate().map(
                         |(itx, (entry, width))| {
                             if width.is_none() {
@@ -140,7 +144,15 @@ impl TempTableWidget for Painter {
                         },
                     );
 
-                    Row::Data(truncated_data)
+                    match level {
+                        TemperatureLevel::Normal => Row::Data(truncated_data),
+                        TemperatureLevel::High => {
+                            Row::StyledData(truncated_data, self.colours.high_temperature_style)
+                        }
+                        TemperatureLevel::Critical => {
+                            Row::StyledData(truncated_data, self.colours.critical_temperature_style)
+                        }
+                    }
                 });
 
             let (border_style, highlight_style) = if is_on_widget {
@@ -73,6 +73,18 @@ inspired by htop's.\n\n",
 Shows the battery widget in default or basic mode. No effect on
 custom layouts.\n\n",
         );
+    let bind = Arg::with_name("bind")
+        .long("bind")
+        .takes_value(true)
+        .value_name("ADDRESS")
+        .help("Sets the address used by --server and --prometheus.")
+        .long_help(
+            "\
+Sets the IP address that the --server and --prometheus HTTP
+servers listen on.  Defaults to 127.0.0.1, so that only this machine can connect; use
+0.0.0.0 to serve every network, but note that anyone who can reach
+the server can see every process and its command line.\n\n",
+        );
     let case_sensitive = Arg::with_name("case_sensitive")
         .short("S")
         .long("case_sensitive")
@@ -88,6 +100,15 @@ When searching for a process, enables case sensitivity by default.\n\n",
             "\
 Disables mouse clicks from interacting with the program.\n\n",
         );
+    let docker = Arg::with_name("docker")
+        .long("docker")
+        .help("Shows which Docker container each process belongs to.")
+        .long_help(
+            "\
+Reads containers from the Docker API, and shows which container each
+process belongs to in the process widget.  This also lets processes be
+grouped by container.  Linux only.\n\n",
+        );
     let dot_marker = Arg::with_name("dot_marker")
         .short("m")
         .long("dot_marker")
@@ -97,6 +118,14 @@ Disables mouse clicks from interacting with the program.\n\n",
 Uses a dot marker for graphs as opposed to the default braille
 marker.\n\n",
         );
+    let fans = Arg::with_name("fans")
+        .long("fans")
+        .help("Shows fan speeds in the temperature widget.")
+        .long_help(
+            "\
+Shows the speed of each fan in the temperature widget, below the
+temperature sensors.  Currently only supported on Linux.\n\n",
+        );
     let group = Arg::with_name("group")
         .short("g")
         .long("group")
@@ -113,6 +142,22 @@ Groups processes with the same name by default.\n\n",
             "\
 Hides the average CPU usage from being shown.\n\n",
         );
+    let hide_idle = Arg::with_name("hide_idle")
+        .long("hide_idle")
+        .help("Hides idle processes from the process widget.")
+        .long_help(
+            "\
+Hides processes using no CPU and almost no memory from the
+process widget by default.  This can be toggled with i.\n\n",
+        );
+    let hide_kernel_threads = Arg::with_name("hide_kernel_threads")
+        .long("hide_kernel_threads")
+        .help("Hides kernel threads from the process widget.")
+        .long_help(
+            "\
+Hides kernel threads (such as kworker) from the process widget.
+These can be shown again at runtime with T.  Linux only.\n\n",
+        );
     let hide_table_gap = Arg::with_name("hide_table_gap")
         .long("hide_table_gap")
         .help("Hides the spacing between table headers and entries.")
@@ -127,6 +172,15 @@ Hides the spacing between table headers and entries.\n\n",
             "\
 Completely hides the time scaling from being shown.\n\n",
         );
+    let json = Arg::with_name("json")
+        .long("json")
+        .help("Prints a single snapshot of all data as JSON and exits.")
+        .long_help(
+            "\
+Collects data once, prints all of it to stdout as JSON, and then
+exits without starting the interface.  Useful for scripts, for
+example: btm --json | jq '.list_of_processes[] | select(.cpu_usage_percent > 50)'\n\n",
+        );
     let left_legend = Arg::with_name("left_legend")
         .short("l")
         .long("left_legend")
@@ -150,6 +204,62 @@ Disables config changes in-app from writing to the config file.",
             "\
 When searching for a process, enables regex by default.\n\n",
         );
+    let server = Arg::with_name("server")
+        .long("server")
+        .help("Serves metrics over HTTP instead of opening the interface.")
+        .long_help(
+            "\
+Starts a headless HTTP server instead of opening the interface.
+GET /metrics returns the latest collected data as JSON, and
+GET /health returns {\"status\":\"ok\"}.  Data is collected at
+the normal refresh rate.  See --port to set the port.\n\n",
+        );
+    let show_cpu_freq = Arg::with_name("show_cpu_freq")
+        .long("show_cpu_freq")
+        .help("Shows the current frequency in the CPU legend.")
+        .long_help(
+            "\
+Shows the current frequency of each core in the CPU legend.  On
+Linux, this is read from cpufreq, or from /proc/cpuinfo if cpufreq
+is unavailable.  If no frequencies can be read, only the usage is
+shown.\n\n",
+        );
+    let show_iowait = Arg::with_name("show_iowait")
+        .long("show_iowait")
+        .help("Shows the I/O wait time of each core in the CPU legend.")
+        .long_help(
+            "\
+Shows the share of time each core spent waiting on I/O as a column
+in the CPU legend, and draws it below the usage of the selected core
+on the chart.  Currently only supported on Linux.\n\n",
+        );
+    let show_steal = Arg::with_name("show_steal")
+        .long("show_steal")
+        .help("Shows the steal time of each core in the CPU legend.")
+        .long_help(
+            "\
+Shows the share of time taken from each core by the hypervisor as a
+column in the CPU legend.  This is only non-zero in virtual machines,
+where it shows contention for the host's CPUs.  Currently only
+supported on Linux.\n\n",
+        );
+    let show_thread_count = Arg::with_name("show_thread_count")
+        .long("show_thread_count")
+        .help("Shows the thread count column in the process widget.")
+        .long_help(
+            "\
+Shows the number of threads of each process as a column in the process
+widget.  This is the same as adding \"threads\" to --process_columns.\n\n",
+        );
+    let sockets = Arg::with_name("sockets")
+        .long("sockets")
+        .help("Shows the socket summary widget.")
+        .long_help(
+            "\
+Shows a summary of the number of TCP, UDP, raw, and Unix sockets
+below the disk widget in default mode.  No effect on basic mode or
+custom layouts.  Currently only supported on Linux.\n\n",
+        );
     let current_usage = Arg::with_name("current_usage")
         .short("u")
         .long("current_usage")
@@ -264,6 +374,104 @@ Supported widget names:
 +--------------------------+
 \n\n",
         );
+    let colors = Arg::with_name("colors")
+        .long("colors")
+        .takes_value(true)
+        .value_name("COLOURS")
+        .help("Sets the colours used for lines on the graphs.")
+        .long_help(
+            "\
+Sets the colours used for lines on the graphs, as a
+comma-separated list of named or hex colours (for example,
+\"red,#ff8800,lightblue\").  Cores are coloured in order, cycling
+through the list.  RAM and RX use the first colour, and swap and
+TX use the second.  This takes priority over the colours in the
+config file.\n\n",
+        );
+    let disk_io_threshold = Arg::with_name("disk_io_threshold")
+        .long("disk_io_threshold")
+        .takes_value(true)
+        .value_name("MB/s")
+        .help("Highlights disks above this I/O rate in MB/s.")
+        .long_help(
+            "\
+Highlights disks in the disk widget whose combined read and
+write rate is above this many megabytes per second.  The
+minimum is 1, and by default no disks are highlighted.\n\n\n",
+        );
+    let influx_db = Arg::with_name("influx_db")
+        .long("influx_db")
+        .takes_value(true)
+        .value_name("NAME")
+        .help("Sets the InfluxDB database to write metrics to.")
+        .long_help(
+            "\
+Sets the InfluxDB database that metrics are written to.  Requires
+--influx_host to also be set.\n\n",
+        );
+    let influx_host = Arg::with_name("influx_host")
+        .long("influx_host")
+        .takes_value(true)
+        .value_name("URL")
+        .help("Writes metrics to the InfluxDB server at this URL.")
+        .long_help(
+            "\
+Writes CPU, memory, disk, network, and temperature metrics to the
+InfluxDB server at this URL (for example, \"http://localhost:8086\")
+each time data is collected, in its line protocol.  Requires
+--influx_db to also be set.  Only plain HTTP is supported, and the
+port defaults to 8086.  If the server falls behind, writes are
+dropped rather than queued.\n\n",
+        );
+    let net_filter = Arg::with_name("net_filter")
+        .long("net_filter")
+        .takes_value(true)
+        .value_name("PATTERNS")
+        .help("Hides network interfaces matching any of the given patterns.")
+        .long_help(
+            "\
+Hides network interfaces matching any of the given glob patterns,
+as a comma-separated list (for example, \"lo,virbr*,docker*\").
+Hidden interfaces are not counted towards the network usage.\n\n",
+        );
+    let port = Arg::with_name("port")
+        .long("port")
+        .takes_value(true)
+        .value_name("PORT")
+        .requires("server")
+        .help("Sets the port used by --server.")
+        .long_help(
+            "\
+Sets the port that the --server HTTP server listens on.  Defaults
+to 6969.\n\n",
+        );
+    let prometheus = Arg::with_name("prometheus")
+        .long("prometheus")
+        .takes_value(true)
+        .value_name("PORT")
+        .conflicts_with("server")
+        .help("Serves Prometheus metrics on this port alongside the interface.")
+        .long_help(
+            "\
+Serves the collected data as Prometheus metrics on this port while
+the interface runs, at GET /metrics, on the address set by --bind.
+Only the data the current layout collects is included; for example,
+disk metrics need a disk widget.  Can't be used with --server,
+which has no interface.\n\n",
+        );
+    let process_columns = Arg::with_name("process_columns")
+        .long("process_columns")
+        .takes_value(true)
+        .value_name("COLUMNS")
+        .help("Sets which process columns are shown, in order.")
+        .long_help(
+            "\
+Sets which columns are shown in the process widget, and in what order, as a
+comma-separated list.  Supported columns are \"pid\", \"name\", \"cpu\", \"mem\",
+\"time\", \"started\", \"age\", \"read\", \"write\", \"total_read\", \"total_write\", \"fds\",
+\"threads\", \"nice\", \"user\", \"state\", and \"container\".  Defaults to showing all
+of them except \"age\", \"threads\", and \"nice\".\n\n",
+        );
     let rate = Arg::with_name("rate")
         .short("r")
         .long("rate")
@@ -284,7 +492,31 @@ and defaults to 1000ms.  Smaller values may take more resources.\n\n\n",
         .long_help(
             "\
 The amount of time in milliseconds changed when zooming in/out.
-The minimum is 1s (1000), and defaults to 15s (15000).\n\n\n",
+The minimum is 1s (1000).  If not set, zooming halves or doubles
+the time interval instead.\n\n\n",
+        );
+    let zfs = Arg::with_name("zfs")
+        .long("zfs")
+        .takes_value(true)
+        .value_name("on|off")
+        .help("Forces the ZFS ARC in the memory widget on or off.")
+        .long_help(
+            "\
+Forces the ZFS ARC usage and hit rate to be shown in (or hidden
+from) the memory widget.  By default, it is shown if ZFS is
+loaded.  Currently only supported on Linux.\n\n\n",
+        );
+    let zram = Arg::with_name("zram")
+        .long("zram")
+        .takes_value(true)
+        .value_name("on|off")
+        .help("Forces zram statistics in the memory widget on or off.")
+        .long_help(
+            "\
+Forces the original and compressed sizes of data in zram to be
+shown in (or hidden from) the swap label of the memory widget.
+By default, they are shown if there are zram devices.  Currently
+only supported on Linux.\n\n\n",
         );
 
     App::new(crate_name!())
@@ -303,23 +535,45 @@ The minimum is 1s (1000), and defaults to 15s (15000).\n\n\n",
         .arg(autohide_time)
         .arg(basic)
         .arg(battery)
+        .arg(bind)
         .arg(case_sensitive)
+        .arg(colors)
         .arg(config_location)
         .arg(default_time_value)
         .arg(default_widget_count)
         .arg(default_widget_type)
         .arg(disable_click)
+        .arg(disk_io_threshold)
+        .arg(docker)
         .arg(dot_marker)
+        .arg(fans)
         .arg(group)
         .arg(hide_avg_cpu)
+        .arg(hide_idle)
+        .arg(hide_kernel_threads)
         .arg(hide_table_gap)
         .arg(hide_time)
+        .arg(influx_db)
+        .arg(influx_host)
+        .arg(json)
         .arg(left_legend)
+        .arg(net_filter)
         .arg(no_write)
+        .arg(port)
+        .arg(process_columns)
+        .arg(prometheus)
         .arg(rate)
         .arg(regex)
+        .arg(server)
+        .arg(show_cpu_freq)
+        .arg(show_iowait)
+        .arg(show_steal)
+        .arg(show_thread_count)
+        .arg(sockets)
         .arg(time_delta)
         .arg(current_usage)
         .arg(use_old_network_legend)
         .arg(whole_word)
+        .arg(zfs)
+        .arg(zram)
 }
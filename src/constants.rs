@@ -9,13 +9,23 @@ pub const STALE_MAX_MILLISECONDS: u64 = 600 * 1000; // Keep 10 minutes of data.
 // How much data is SHOWN
 pub const DEFAULT_TIME_MILLISECONDS: u64 = 60 * 1000; // Defaults to 1 min.
 pub const STALE_MIN_MILLISECONDS: u64 = 30 * 1000; // Lowest is 30 seconds
-pub const TIME_CHANGE_MILLISECONDS: u64 = 15 * 1000; // How much to increment each time
 pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
+pub const FOLLOW_LOST_MESSAGE_MILLISECONDS: u64 = 3000; // How long to say a followed process exited
+pub const PROCESS_STATUS_MESSAGE_MILLISECONDS: u64 = 3000; // How long to show messages like renice errors
+pub const PROCESS_INFO_HORIZONTAL_SCROLL_STEP: u16 = 8;
 
 pub const TICK_RATE_IN_MILLISECONDS: u64 = 200;
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
+pub const DEFAULT_SERVER_PORT: u16 = 6969;
+pub const DEFAULT_INFLUX_PORT: u16 = 8086;
+pub const MAX_SEARCH_HISTORY_LENGTH: usize = 50;
+pub const IDLE_PROCESS_MAX_MEM_PERCENT: f64 = 0.1; // Processes at 0% CPU and below this memory are idle
+pub const DEFAULT_HIGH_TEMPERATURE_CELSIUS: f32 = 75.0;
+pub const DEFAULT_CRITICAL_TEMPERATURE_CELSIUS: f32 = 90.0;
+pub const DEFAULT_HIGH_DISK_USAGE_PERCENT: f64 = 70.0;
+pub const DEFAULT_CRITICAL_DISK_USAGE_PERCENT: f64 = 90.0;
 // Number of colours to generate for the CPU chart/table
 pub const NUM_COLOURS: usize = 256;
 
@@ -39,7 +49,7 @@ lazy_static! {
 
 // FIXME: [HELP] I wanna update this before release... it's missing mouse too.
 // Help text
-pub const HELP_CONTENTS_TEXT: [&str; 8] = [
+pub const HELP_CONTENTS_TEXT: [&str; 10] = [
     "Press the corresponding numbers to jump to the section, or scroll:",
     "1 - General",
     "2 - CPU widget",
@@ -48,14 +58,18 @@ pub const HELP_CONTENTS_TEXT: [&str; 8] = [
     "5 - Process sort widget",
     "6 - Battery widget",
     "7 - Basic memory widget",
+    "8 - Disk widget",
+    "9 - Memory widget",
 ];
 
-pub const GENERAL_HELP_TEXT: [&str; 29] = [
+pub const GENERAL_HELP_TEXT: [&str; 32] = [
     "1 - General",
-    "q, Ctrl-c        Quit",
+    "q, Ctrl-c        Quit, though Ctrl-c copies the contents of a process info dialog",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
     "Ctrl-r           Reset display and any collected data",
     "f                Freeze/unfreeze updating with new data",
+    "Ctrl-e           Export the current data to a CSV file",
+    "Ctrl-o           Export the shown processes to a CSV file",
     "Ctrl-Left,       ",
     "Shift-Left,      Move widget selection left",
     "H, A             ",
@@ -75,47 +89,77 @@ pub const GENERAL_HELP_TEXT: [&str; 29] = [
     "?                Open help menu",
     "gg               Jump to the first entry",
     "G                Jump to the last entry",
+    "PgUp, PgDn       Move up or down by a page of entries",
     "e                Toggle expanding the currently selected widget",
     "+                Zoom in on chart (decrease time range)",
     "-                Zoom out on chart (increase time range)",
-    "=                Reset zoom",
+    "=, r             Reset zoom",
     "Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down",
 ];
 
-pub const CPU_HELP_TEXT: [&str; 2] = [
+pub const CPU_HELP_TEXT: [&str; 10] = [
     "2 - CPU widget\n",
-    "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
+    "Mouse scroll     Scrolling over an CPU core/average emphasizes that entry on the chart",
+    "Enter            Keep the selected entry emphasized after leaving the widget",
+    "Up, k/Down, j    Select an entry in the legend",
+    "N                Group cores by NUMA node in the legend",
+    "Space            Hide/show the selected core on the chart",
+    "a                Show all hidden cores on the chart",
+    "v                Show/hide the average CPU usage",
+    "b                Show/hide system and I/O wait usage on the chart (Linux only)",
+    "i                Show a stacked split of CPU time, including interrupts (Linux only)",
 ];
 
 // TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub const PROCESS_HELP_TEXT: [&str; 13] = [
+pub const PROCESS_HELP_TEXT: [&str; 31] = [
     "3 - Process widget",
-    "dd               Kill the selected process",
+    "dd, Delete       Kill the selected process, or all tagged processes",
+    "F9               Choose a signal to send to the selected or tagged processes",
     "c                Sort by CPU usage, press again to reverse sorting order",
     "m                Sort by memory usage, press again to reverse sorting order",
-    "p                Sort by PID name, press again to reverse sorting order",
+    "p                Sort by PID (or count if grouped), press again to reverse sorting order",
     "n                Sort by process name, press again to reverse sorting order",
-    "Tab              Group/un-group processes with the same name",
+    "Tab              Group/un-group processes with the same name, by user, or by container",
     "Ctrl-f, /        Open process search widget",
     "P                Toggle between showing the full command or just the process name",
     "s, F6            Open process sort widget",
     "I                Invert current sort",
+    "<, >             Sort by the previous or next column",
     "%                Toggle between values and percentages for memory usage",
     "t, F5            Toggle tree mode",
+    "Enter            Show details of the selected process, or collapse/expand it in tree mode",
+    "C                Show the full command line of the selected process",
+    "E                Show the environment variables of the selected process",
+    "o                Show the open files of the selected process",
+    "N                Show the network connections of the selected process",
+    "T                Show/hide kernel threads",
+    "u                Toggle CPU% between a share of all CPUs and of current CPU usage",
+    "i                Show/hide idle processes",
+    "Ctrl-u           Only show processes owned by a given user",
+    "Ctrl-t           Cycle between showing all, running, sleeping, zombie, or stopped processes",
+    "Space            Tag or untag the selected process",
+    "U                Untag all processes",
+    "M                Pin or unpin the selected process at the top of the list",
+    "F                Follow the selected process across refreshes, press again to stop",
+    "R                Set the nice value of the selected process",
+    "[, ]             Lower or raise the nice value of the selected process",
 ];
 
-pub const SEARCH_HELP_TEXT: [&str; 46] = [
+pub const SEARCH_HELP_TEXT: [&str; 51] = [
     "4 - Process search widget",
-    "Tab              Toggle between searching for PID and name",
+    "Tab              Complete the process name before the cursor, press again to cycle",
     "Esc              Close the search widget (retains the filter)",
+    "Enter            Save the current search query to the history",
+    "Up, Down         Cycle through previous search queries",
     "Ctrl-a           Skip to the start of the search query",
     "Ctrl-e           Skip to the end of the search query",
     "Ctrl-u           Clear the current search query",
     "Backspace        Delete the character behind the cursor",
     "Delete           Delete the character at the cursor",
     "Alt-c, F1        Toggle matching case",
-    "Alt-w, F2        Toggle matching the entire word",
+    "Alt-w, Ctrl-w,   Toggle matching whole words",
+    "F2               ",
     "Alt-r, F3        Toggle using regex",
     "Left, Alt-h      Move cursor left",
     "Right, Alt-l     Move cursor right",
@@ -131,6 +175,7 @@ pub const SEARCH_HELP_TEXT: [&str; 46] = [
     "tread, t.read    ex: tread = 1",
     "twrite, t.write  ex: twrite = 1",
     "state            ex: state = running",
+    "user             ex: user = root, user:root",
     "",
     "Comparison operators:",
     "=                ex: cpu = 1",
@@ -142,6 +187,7 @@ pub const SEARCH_HELP_TEXT: [&str; 46] = [
     "Logical operators:",
     "and, &&, <Space> ex: btm and cpu > 1 and mem > 1",
     "or, ||           ex: btm or firefox",
+    "! (at the start) ex: !chrome, hides matching processes",
     "",
     "Supported units:",
     "B                ex: read > 1 b",
@@ -174,6 +220,17 @@ pub const BASIC_MEM_HELP_TEXT: [&str; 2] = [
     "%                Toggle between values and percentages for memory usage",
 ];
 
+pub const DISK_HELP_TEXT: [&str; 3] = [
+    "8 - Disk widget",
+    "<, >             Cycle between sorting by disk name, R/s, and W/s",
+    "I                Invert current sort",
+];
+
+pub const MEM_HELP_TEXT: [&str; 2] = [
+    "9 - Memory widget",
+    "h                Show/hide huge page statistics",
+];
+
 lazy_static! {
     pub static ref HELP_TEXT: Vec<Vec<&'static str>> = vec![
         HELP_CONTENTS_TEXT.to_vec(),
@@ -184,6 +241,8 @@ lazy_static! {
         SORT_HELP_TEXT.to_vec(),
         BATTERY_HELP_TEXT.to_vec(),
         BASIC_MEM_HELP_TEXT.to_vec(),
+        DISK_HELP_TEXT.to_vec(),
+        MEM_HELP_TEXT.to_vec(),
     ];
 }
 
@@ -245,6 +304,8 @@ pub const DEFAULT_BATTERY_LAYOUT: &str = r##"
 // Config and flags
 pub const DEFAULT_CONFIG_FILE_PATH: &str = "bottom/bottom.toml";
 
+pub const DEFAULT_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
 pub const CONFIG_TOP_HEAD: &str = r##"# This is bottom's config file.  Values in this config file will change when changed in the
 # interface.  You can also manually change these values.
 
@@ -2,12 +2,15 @@
 //! can actually handle.
 use crate::Pid;
 use crate::{
-    app::{data_farmer, data_harvester, App, Filter, ProcWidgetState},
+    app::{
+        data_farmer, data_harvester, query::SearchMatch, App, Filter, ProcWidgetState,
+        ProcessGroupRule, ProcessGroupingKey,
+    },
     utils::{self, gen_util::*},
 };
-use data_harvester::processes::ProcessSorting;
+use data_harvester::{disks::DiskSorting, processes::ProcessSorting};
 use indexmap::IndexSet;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Point is of time, data
 type Point = (f64, f64);
@@ -20,6 +23,7 @@ pub struct ConvertedBatteryData {
     pub duration_until_full: Option<String>,
     pub duration_until_empty: Option<String>,
     pub health: String,
+    pub state: String,
 }
 
 #[derive(Default, Debug)]
@@ -30,6 +34,10 @@ pub struct ConvertedNetworkData {
     pub tx_display: String,
     pub total_rx_display: Option<String>,
     pub total_tx_display: Option<String>,
+    pub rx_threshold: Option<NetworkThresholdLine>,
+    pub tx_threshold: Option<NetworkThresholdLine>,
+    /// The received and transmitted packets per second, as shown in the network graph's title.
+    pub packets_display: String,
     // TODO: [NETWORKING] add min/max/mean of each
     // min_rx : f64,
     // max_rx : f64,
@@ -39,6 +47,17 @@ pub struct ConvertedNetworkData {
     // mean_tx: f64,
 }
 
+/// How many processes are shown in a process widget versus how many there are in total.  When
+/// grouped, these count groups, and `total_pids` holds the number of actual processes.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct ProcessCount {
+    pub shown: usize,
+    pub total: usize,
+    pub total_pids: Option<usize>,
+    /// How many entries were hidden for being idle.
+    pub hidden_idle: usize,
+}
+
 // TODO: [REFACTOR] Process data... stuff really needs a rewrite.  Again.
 #[derive(Clone, Default, Debug)]
 pub struct ConvertedProcessData {
@@ -51,7 +70,16 @@ pub struct ConvertedProcessData {
     pub mem_percent_usage: f64,
     pub mem_usage_bytes: u64,
     pub mem_usage_str: (f64, String),
+    /// The total CPU time used, in seconds.
+    pub total_cpu_time: Option<f64>,
+    /// When the process started, in seconds since the Unix epoch.  For groups, this is the
+    /// earliest start time.
+    pub start_time_secs: Option<u64>,
     pub group_pids: Vec<Pid>,
+    /// The distinct states of the processes in a group, or just the state of a single process.
+    pub group_states: Vec<String>,
+    /// The distinct users of the processes in a group, or just the user of a single process.
+    pub group_users: Vec<String>,
     pub read_per_sec: String,
     pub write_per_sec: String,
     pub total_read: String,
@@ -60,12 +88,31 @@ pub struct ConvertedProcessData {
     pub wps_f64: f64,
     pub tr_f64: f64,
     pub tw_f64: f64,
+    /// Whether we could read the I/O usage of the process (or any process in the group) at all.
+    pub is_io_readable: bool,
+    pub fd_count: Option<u32>,
+    pub thread_count: Option<u64>,
+    /// The nice value.  For groups, this is the most common nice value of the group.
+    pub nice: Option<i32>,
     pub process_state: String,
     pub process_char: char,
+    pub user: String,
+    /// The name of the container the process is running in, if any.
+    pub container: String,
     /// Prefix printed before the process when displayed.
     pub process_description_prefix: Option<String>,
     /// Whether to mark this process entry as disabled (mostly for tree mode).
     pub is_disabled_entry: bool,
+    /// Where this process matched the current search, if anywhere.
+    pub search_match: Option<SearchMatch>,
+}
+
+impl ConvertedProcessData {
+    /// Whether this process, or for a group, any process in it, has one of the given PIDs.
+    pub fn has_any_pid(&self, pids: &HashSet<Pid>) -> bool {
+        !pids.is_empty()
+            && (pids.contains(&self.pid) || self.group_pids.iter().any(|pid| pids.contains(pid)))
+    }
 }
 
 #[derive(Clone, Default, Debug)]
@@ -76,14 +123,46 @@ pub struct ConvertedCpuData {
     pub cpu_data: Vec<Point>,
     /// Represents the value displayed on the legend.
     pub legend_value: String,
+    /// Represents the current frequency displayed on the legend, if enabled.
+    pub frequency_value: String,
+    /// The NUMA node of the core, if known.
+    pub numa_node: Option<u32>,
+    /// The share of time spent waiting on I/O, if the platform reports it.
+    pub iowait_data: Vec<Point>,
+    /// Represents the I/O wait and steal time displayed on the legend, if enabled.
+    pub iowait_value: String,
+    pub steal_value: String,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct ConvertedGpuData {
+    pub gpu_name: String,
+    /// Tuple is time, value
+    pub gpu_data: Vec<Point>,
+    pub utilization_label: String,
+    /// Ratio of used VRAM, between 0 and 1.
+    pub vram_ratio: f64,
+    pub vram_label: String,
+    pub temperature_label: String,
+}
+
+/// How a temperature compares against the configured thresholds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemperatureLevel {
+    Normal,
+    High,
+    Critical,
 }
 
-pub fn convert_temp_row(app: &App) -> Vec<Vec<String>> {
+/// Converts the temperature sensors (and fans, if shown) into rows of the temperature widget,
+/// alongside how each temperature compares against the configured thresholds.
+pub fn convert_temp_row(app: &App) -> Vec<(Vec<String>, TemperatureLevel)> {
     let current_data = &app.data_collection;
     let temp_type = &app.app_config_fields.temperature_type;
     let temp_filter = &app.filters.temp_filter;
+    let (high_threshold, critical_threshold) = app.app_config_fields.temperature_thresholds;
 
-    let mut sensor_vector: Vec<Vec<String>> = current_data
+    let mut sensor_vector: Vec<(Vec<String>, TemperatureLevel)> = current_data
         .temp_harvest
         .iter()
         .filter_map(|temp_harvest| {
@@ -108,74 +187,326 @@ pub fn convert_temp_row(app: &App) -> Vec<Vec<String>> {
             };
 
             if to_keep {
-                Some(vec![
-                    name,
-                    (temp_harvest.temperature.ceil() as u64).to_string()
-                        + match temp_type {
-                            data_harvester::temperature::TemperatureType::Celsius => "C",
-                            data_harvester::temperature::TemperatureType::Kelvin => "K",
-                            data_harvester::temperature::TemperatureType::Fahrenheit => "F",
-                        },
-                ])
+                // Thresholds are in Celsius, so compare before converting for display.
+                let level = if temp_harvest.temperature >= critical_threshold {
+                    TemperatureLevel::Critical
+                } else if temp_harvest.temperature >= high_threshold {
+                    TemperatureLevel::High
+                } else {
+                    TemperatureLevel::Normal
+                };
+                let temperature = data_harvester::temperature::convert_celsius(
+                    temp_harvest.temperature,
+                    temp_type,
+                );
+
+                Some((
+                    vec![
+                        name,
+                        (temperature.ceil() as u64).to_string()
+                            + match temp_type {
+                                data_harvester::temperature::TemperatureType::Celsius => "C",
+                                data_harvester::temperature::TemperatureType::Kelvin => "K",
+                                data_harvester::temperature::TemperatureType::Fahrenheit => "F",
+                            },
+                    ],
+                    level,
+                ))
             } else {
                 None
             }
         })
         .collect();
 
+    if app.app_config_fields.show_fans {
+        sensor_vector.extend(current_data.fan_harvest.iter().filter_map(|fan_harvest| {
+            let to_keep = if let Some(temp_filter) = temp_filter {
+                let mut ret = temp_filter.is_list_ignored;
+                for r in &temp_filter.list {
+                    if r.is_match(&fan_harvest.name) {
+                        ret = !temp_filter.is_list_ignored;
+                        break;
+                    }
+                }
+                ret
+            } else {
+                true
+            };
+
+            if to_keep {
+                Some((
+                    vec![
+                        format!("[FAN] {}", fan_harvest.name),
+                        format!("{}RPM", fan_harvest.rpm),
+                    ],
+                    TemperatureLevel::Normal,
+                ))
+            } else {
+                None
+            }
+        }));
+    }
+
     if sensor_vector.is_empty() {
-        sensor_vector.push(vec!["No Sensors Found".to_string(), "".to_string()]);
+        sensor_vector.push((
+            vec!["No Sensors Found".to_string(), "".to_string()],
+            TemperatureLevel::Normal,
+        ));
     }
 
     sensor_vector
 }
 
+fn is_disk_shown(disk: &data_harvester::disks::DiskHarvest, disk_filter: &Option<Filter>) -> bool {
+    if let Some(disk_filter) = disk_filter {
+        for r in &disk_filter.list {
+            if r.is_match(&disk.name) {
+                return !disk_filter.is_list_ignored;
+            }
+        }
+        disk_filter.is_list_ignored
+    } else {
+        true
+    }
+}
+
+/// How full a disk is compared against the configured thresholds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiskUsageLevel {
+    Normal,
+    High,
+    Critical,
+}
+
+/// The width of the disk usage bar, in characters.
+pub const DISK_USAGE_BAR_WIDTH: usize = 10;
+
+/// Draws a bar of [`DISK_USAGE_BAR_WIDTH`] characters filled in proportion to `ratio`, using
+/// partial blocks for the last character.
+fn get_disk_usage_bar(ratio: f64) -> String {
+    const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    let eighths = (ratio.clamp(0.0, 1.0) * (DISK_USAGE_BAR_WIDTH * 8) as f64).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    let partial_block = PARTIAL_BLOCKS[eighths % 8];
+    if partial_block != ' ' {
+        bar.push(partial_block);
+    }
+    let filled = bar.chars().count();
+    bar.push_str(&"░".repeat(DISK_USAGE_BAR_WIDTH - filled));
+
+    bar
+}
+
+/// Converts the disks into rows for the disk widget, sorted as given.  Each row is paired with
+/// whether the disk's combined I/O rate is above `io_threshold`, in bytes per second, and how
+/// full it is compared against `usage_thresholds`.
 pub fn convert_disk_row(
     current_data: &data_farmer::DataCollection, disk_filter: &Option<Filter>,
-) -> Vec<Vec<String>> {
-    let mut disk_vector: Vec<Vec<String>> = Vec::new();
-
-    current_data
+    sort_type: DiskSorting, is_sort_descending: bool, io_threshold: Option<u64>,
+    usage_thresholds: (f64, f64),
+) -> Vec<(Vec<String>, bool, DiskUsageLevel)> {
+    let mut disks = current_data
         .disk_harvest
         .iter()
-        .filter(|disk_harvest| {
-            if let Some(disk_filter) = disk_filter {
-                for r in &disk_filter.list {
-                    if r.is_match(&disk_harvest.name) {
-                        return !disk_filter.is_list_ignored;
-                    }
+        .zip(&current_data.io_labels)
+        .filter(|(disk, _)| is_disk_shown(disk, disk_filter))
+        .collect::<Vec<_>>();
+
+    // Disks are harvested in name order, so that only needs reversing.
+    match sort_type {
+        DiskSorting::Name => {
+            if is_sort_descending {
+                disks.reverse();
+            }
+        }
+        DiskSorting::ReadRate => disks.sort_by(|(a, _), (b, _)| {
+            get_ordering(
+                a.read_bytes_per_sec,
+                b.read_bytes_per_sec,
+                is_sort_descending,
+            )
+        }),
+        DiskSorting::WriteRate => disks.sort_by(|(a, _), (b, _)| {
+            get_ordering(
+                a.write_bytes_per_sec,
+                b.write_bytes_per_sec,
+                is_sort_descending,
+            )
+        }),
+    }
+
+    disks
+        .into_iter()
+        .map(|(disk, (io_read, io_write))| {
+            let is_above_threshold = match io_threshold {
+                Some(io_threshold) => {
+                    disk.read_bytes_per_sec + disk.write_bytes_per_sec > io_threshold as f64
                 }
-                disk_filter.is_list_ignored
+                None => false,
+            };
+            let used_ratio = if disk.total_space > 0 {
+                disk.used_space as f64 / disk.total_space as f64
             } else {
-                true
-            }
+                0.0
+            };
+            let (high_usage, critical_usage) = usage_thresholds;
+            let usage_level = if used_ratio * 100.0 >= critical_usage {
+                DiskUsageLevel::Critical
+            } else if used_ratio * 100.0 >= high_usage {
+                DiskUsageLevel::High
+            } else {
+                DiskUsageLevel::Normal
+            };
+
+            (
+                vec![
+                    disk.name.to_string(),
+                    disk.mount_point.to_string(),
+                    format!("{:.0}%", used_ratio * 100_f64),
+                    get_disk_usage_bar(used_ratio),
+                    humanize_bytes(disk.free_space),
+                    humanize_bytes(disk.total_space),
+                    io_read.to_string(),
+                    io_write.to_string(),
+                ],
+                is_above_threshold,
+                usage_level,
+            )
         })
-        .zip(&current_data.io_labels)
-        .for_each(|(disk, (io_read, io_write))| {
-            let converted_free_space = get_simple_byte_values(disk.free_space, false);
-            let converted_total_space = get_simple_byte_values(disk.total_space, false);
-            disk_vector.push(vec![
-                disk.name.to_string(),
-                disk.mount_point.to_string(),
-                format!(
-                    "{:.0}%",
-                    disk.used_space as f64 / disk.total_space as f64 * 100_f64
-                ),
-                format!("{:.*}{}", 0, converted_free_space.0, converted_free_space.1),
-                format!(
-                    "{:.*}{}",
-                    0, converted_total_space.0, converted_total_space.1
-                ),
-                io_read.to_string(),
-                io_write.to_string(),
-            ]);
-        });
+        .collect()
+}
 
-    disk_vector
+/// Returns the combined read and write rate of all shown disks, for the disk widget's title.
+pub fn convert_disk_io_total(
+    current_data: &data_farmer::DataCollection, disk_filter: &Option<Filter>,
+) -> String {
+    let total_io = current_data
+        .disk_harvest
+        .iter()
+        .filter(|disk| is_disk_shown(disk, disk_filter))
+        .map(|disk| disk.read_bytes_per_sec + disk.write_bytes_per_sec)
+        .sum::<f64>();
+    format!("{}/s", humanize_bytes(total_io as u64))
 }
 
-pub fn convert_cpu_data_points(
+/// Returns the load averages as shown in the CPU widget, if the platform has them.
+pub fn convert_load_avg_label(current_data: &data_farmer::DataCollection) -> Option<String> {
+    current_data.load_avg_harvest.as_ref().map(|load_avg| {
+        format!(
+            "load avg: {:.2} {:.2} {:.2}",
+            load_avg.one, load_avg.five, load_avg.fifteen
+        )
+    })
+}
+
+/// Returns the share of CPU time spent by user, system, I/O wait, and steal as shown in the CPU
+/// widget, if the platform reports them.
+pub fn convert_cpu_breakdown_label(current_data: &data_farmer::DataCollection) -> Option<String> {
+    current_data
+        .cpu_breakdown_harvest
+        .as_ref()
+        .map(|breakdown| {
+            format!(
+                "usr {:.0}% sys {:.0}% io {:.0}% st {:.0}%",
+                breakdown.user_percent,
+                breakdown.system_percent + breakdown.irq_percent + breakdown.softirq_percent,
+                breakdown.iowait_percent,
+                breakdown.steal_percent
+            )
+        })
+}
+
+/// Returns a label splitting CPU time between all states, including interrupts and idle time, if
+/// the platform reports it.
+pub fn convert_cpu_interrupt_label(current_data: &data_farmer::DataCollection) -> Option<String> {
+    current_data
+        .cpu_breakdown_harvest
+        .as_ref()
+        .map(|breakdown| {
+            format!(
+                "usr {:.0}% sys {:.0}% irq {:.0}% sirq {:.0}% io {:.0}% st {:.0}% idle {:.0}%",
+                breakdown.user_percent,
+                breakdown.system_percent,
+                breakdown.irq_percent,
+                breakdown.softirq_percent,
+                breakdown.iowait_percent,
+                breakdown.steal_percent,
+                breakdown.idle_percent
+            )
+        })
+}
+
+/// Returns the points of each layer of the stacked CPU time breakdown, in the order user, system,
+/// IRQ, softIRQ, I/O wait, and steal time, if the platform reports them.
+pub fn convert_cpu_stacked_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> Vec<Vec<Point>> {
+    let mut result: Vec<Vec<Point>> = Vec::new();
+    if current_data.cpu_breakdown_harvest.is_none() {
+        return result;
+    }
+
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+        result.resize(data.cpu_stacked_data.len().max(result.len()), Vec::new());
+        for (layer, value) in result.iter_mut().zip(&data.cpu_stacked_data) {
+            layer.push((-time_from_start, *value));
+        }
+        if *time == current_time {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Returns the system and I/O wait CPU usage points, if the platform reports them.
+pub fn convert_cpu_breakdown_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> (Vec<Point>, Vec<Point>) {
+    let mut system_result: Vec<Point> = Vec::new();
+    let mut iowait_result: Vec<Point> = Vec::new();
+    if current_data.cpu_breakdown_harvest.is_none() {
+        return (system_result, iowait_result);
+    }
+
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+        system_result.push((-time_from_start, data.cpu_system_data));
+        iowait_result.push((-time_from_start, data.cpu_iowait_data));
+        if *time == current_time {
+            break;
+        }
+    }
+
+    (system_result, iowait_result)
+}
+
+/// Converts the CPU data for the CPU widgets.  The average is left out if `show_average_cpu` is
+/// false, even if it was collected, so that it can be toggled at runtime.
+pub fn convert_cpu_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool, show_average_cpu: bool,
 ) -> Vec<ConvertedCpuData> {
     let mut cpu_data_vector: Vec<ConvertedCpuData> = Vec::new();
     let current_time = if is_frozen {
@@ -188,6 +519,9 @@ pub fn convert_cpu_data_points(
         current_data.current_instant
     };
 
+    // I/O wait and steal time are only read where the split of CPU time is.
+    let has_cpu_breakdown = current_data.cpu_breakdown_harvest.is_some();
+
     for (time, data) in &current_data.timed_data_vec {
         let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
 
@@ -221,6 +555,11 @@ pub fn convert_cpu_data_points(
             if let Some(cpu_data) = cpu_data_vector.get_mut(itx) {
                 cpu_data.legend_value = format!("{:.0}%", cpu.round());
                 cpu_data.cpu_data.push((-time_from_start, *cpu));
+                if has_cpu_breakdown {
+                    if let Some(iowait) = data.cpu_iowait_per_core_data.get(itx) {
+                        cpu_data.iowait_data.push((-time_from_start, *iowait));
+                    }
+                }
             }
         }
 
@@ -229,16 +568,117 @@ pub fn convert_cpu_data_points(
         }
     }
 
+    // Frequencies aren't graphed, so we only care about the latest harvest.
+    for (cpu_data, cpu_harvest) in cpu_data_vector.iter_mut().zip(&current_data.cpu_harvest) {
+        cpu_data.numa_node = cpu_harvest.numa_node;
+        cpu_data.frequency_value = match cpu_harvest.frequency_mhz {
+            Some(frequency_mhz) if frequency_mhz >= 1000 => {
+                format!("{:.2}GHz", frequency_mhz as f64 / 1000.0)
+            }
+            Some(frequency_mhz) => format!("{}MHz", frequency_mhz),
+            None => String::new(),
+        };
+        if has_cpu_breakdown {
+            cpu_data.iowait_value = format!("{:.0}%", cpu_harvest.iowait_percent.round());
+            cpu_data.steal_value = format!("{:.0}%", cpu_harvest.steal_percent.round());
+        }
+    }
+
+    // The average is always the first entry, and is the only one without a core number.
+    if !show_average_cpu
+        && !cpu_data_vector.is_empty()
+        && matches!(current_data.cpu_harvest.first(), Some(cpu_harvest) if cpu_harvest.cpu_count.is_none())
+    {
+        cpu_data_vector.remove(0);
+    }
+
     let mut extended_vec = vec![ConvertedCpuData {
         cpu_name: "All".to_string(),
         short_cpu_name: "All".to_string(),
         cpu_data: vec![],
         legend_value: String::new(),
+        frequency_value: String::new(),
+        numa_node: None,
+        iowait_data: vec![],
+        iowait_value: String::new(),
+        steal_value: String::new(),
     }];
     extended_vec.extend(cpu_data_vector);
     extended_vec
 }
 
+/// Returns the order in which to list CPU entries, as indices into `cpu_data`.  If grouping by
+/// NUMA node, cores are ordered by node, and otherwise left as they are.
+pub fn get_cpu_display_order(cpu_data: &[ConvertedCpuData], is_numa_grouped: bool) -> Vec<usize> {
+    let mut display_order = (0..cpu_data.len()).collect::<Vec<_>>();
+    if is_numa_grouped {
+        // The "All" and average entries have no node, and so stay on top.
+        display_order.sort_by_key(|itx| cpu_data[*itx].numa_node);
+    }
+    display_order
+}
+
+pub fn convert_gpu_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+    temp_type: &data_harvester::temperature::TemperatureType,
+) -> Vec<ConvertedGpuData> {
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    let mut gpu_data_vector: Vec<ConvertedGpuData> = current_data
+        .gpu_harvest
+        .iter()
+        .map(|gpu| {
+            let used = get_exact_byte_values(gpu.vram_used_bytes, false);
+            let total = get_exact_byte_values(gpu.vram_total_bytes, false);
+
+            ConvertedGpuData {
+                gpu_name: gpu.name.clone(),
+                gpu_data: vec![],
+                utilization_label: format!("{:.0}%", gpu.utilization_percent.round()),
+                vram_ratio: match gpu.vram_total_bytes {
+                    0 => 0.0,
+                    vram_total => gpu.vram_used_bytes as f64 / vram_total as f64,
+                },
+                vram_label: format!("{:.1}{}/{:.1}{}", used.0, used.1, total.0, total.1),
+                temperature_label: if let Some(temperature) = gpu.temperature {
+                    (data_harvester::temperature::convert_celsius(temperature, temp_type).ceil()
+                        as u64)
+                        .to_string()
+                        + match temp_type {
+                            data_harvester::temperature::TemperatureType::Celsius => "C",
+                            data_harvester::temperature::TemperatureType::Kelvin => "K",
+                            data_harvester::temperature::TemperatureType::Fahrenheit => "F",
+                        }
+                } else {
+                    "N/A".to_string()
+                },
+            }
+        })
+        .collect();
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+
+        for (gpu_data, utilization) in gpu_data_vector.iter_mut().zip(&data.gpu_data) {
+            gpu_data.gpu_data.push((-time_from_start, *utilization));
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    gpu_data_vector
+}
+
 pub fn convert_mem_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool,
 ) -> Vec<Point> {
@@ -289,9 +729,123 @@ pub fn convert_swap_data_points(
     result
 }
 
+pub fn convert_arc_data_points(
+    current_data: &data_farmer::DataCollection, is_frozen: bool,
+) -> Vec<Point> {
+    let mut result: Vec<Point> = Vec::new();
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+        result.push((-time_from_start, data.arc_data));
+        if *time == current_time {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Returns the percentage and fraction labels of the ZFS ARC, with the hit rate tacked onto the
+/// fraction, if there is an ARC.
+pub fn convert_arc_labels(current_data: &data_farmer::DataCollection) -> Option<(String, String)> {
+    current_data.zfs_harvest.as_ref().map(|zfs| {
+        (
+            format!(
+                "{:3.0}%",
+                match zfs.arc_max_bytes {
+                    0 => 0.0,
+                    max => zfs.arc_size_bytes as f64 * 100.0 / max as f64,
+                }
+            ),
+            format!(
+                "   {}/{}  hit {:.0}%",
+                humanize_bytes(zfs.arc_size_bytes),
+                humanize_bytes(zfs.arc_max_bytes),
+                zfs.arc_hit_rate
+            ),
+        )
+    })
+}
+
+/// Returns the percentage and fraction labels of the huge page pool in use, if known.
+pub fn convert_huge_page_labels(
+    current_data: &data_farmer::DataCollection,
+) -> Option<(String, String)> {
+    let memory_harvest = &current_data.memory_harvest;
+    match (
+        memory_harvest.huge_pages_total,
+        memory_harvest.huge_pages_free,
+        memory_harvest.huge_page_size_kb,
+    ) {
+        (Some(total), Some(free), Some(page_size_kb)) => {
+            let used = total.saturating_sub(free);
+            Some((
+                format!(
+                    "{:3.0}%",
+                    match total {
+                        0 => 0.0,
+                        _ => used as f64 * 100.0 / total as f64,
+                    }
+                ),
+                format!(
+                    "   {}/{}",
+                    humanize_bytes(used * page_size_kb * KIBI_LIMIT),
+                    humanize_bytes(total * page_size_kb * KIBI_LIMIT)
+                ),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the percentage (of all memory) and size labels of anonymous memory backed by
+/// transparent huge pages, if known.
+pub fn convert_thp_labels(current_data: &data_farmer::DataCollection) -> Option<(String, String)> {
+    let memory_harvest = &current_data.memory_harvest;
+    memory_harvest.anon_huge_pages_kb.map(|anon_huge_pages_kb| {
+        let anon_huge_pages_mb = anon_huge_pages_kb as f64 / 1024.0;
+        (
+            format!(
+                "{:3.0}%",
+                match memory_harvest.mem_total_in_mb {
+                    0 => 0.0,
+                    total => anon_huge_pages_mb * 100.0 / total as f64,
+                }
+            ),
+            format!("   {}", humanize_bytes(anon_huge_pages_kb * KIBI_LIMIT)),
+        )
+    })
+}
+
+/// Returns the label for how fast memory is being swapped in and out.  This is only read on Linux.
+pub fn convert_swap_io_label(current_data: &data_farmer::DataCollection) -> Option<String> {
+    if cfg!(target_os = "linux") && current_data.swap_harvest.mem_total_in_mb > 0 {
+        let (in_value, in_unit) =
+            get_exact_byte_values(current_data.swap_harvest.swap_in_per_sec as u64, false);
+        let (out_value, out_unit) =
+            get_exact_byte_values(current_data.swap_harvest.swap_out_per_sec as u64, false);
+        Some(format!(
+            "Swap I/O: {:.1}{}/s in / {:.1}{}/s out",
+            in_value, in_unit, out_value, out_unit
+        ))
+    } else {
+        None
+    }
+}
+
+/// Returns the percentage and fraction labels of RAM, and of swap if the system has any.
 pub fn convert_mem_labels(
     current_data: &data_farmer::DataCollection,
-) -> (String, String, String, String) {
+) -> (String, String, Option<(String, String)>) {
     (
         format!(
             "{:3.0}%",
@@ -303,27 +857,69 @@ pub fn convert_mem_labels(
             }
         ),
         format!(
-            "   {:.1}GB/{:.1}GB",
-            current_data.memory_harvest.mem_used_in_mb as f64 / 1024.0,
-            (current_data.memory_harvest.mem_total_in_mb as f64 / 1024.0)
+            "   {}/{}{}",
+            humanize_bytes(current_data.memory_harvest.mem_used_in_mb * MEBI_LIMIT),
+            humanize_bytes(current_data.memory_harvest.mem_total_in_mb * MEBI_LIMIT),
+            // Without this, memory can look nearly full when much of it is reclaimable cache,
+            // or for ZFS, the ARC.  The ARC is left out of the used memory.
+            annotate(&[
+                current_data
+                    .memory_harvest
+                    .cache_in_mb
+                    .map(|cache_in_mb| format!(
+                        "cache {}",
+                        humanize_bytes(cache_in_mb * MEBI_LIMIT)
+                    )),
+                current_data
+                    .zfs_harvest
+                    .as_ref()
+                    .map(|zfs| format!("ARC {}", humanize_bytes(zfs.arc_size_bytes))),
+            ])
         ),
-        format!(
-            "{:3.0}%",
-            match current_data.swap_harvest.mem_total_in_mb {
-                0 => 0.0,
-                _ =>
+        // Checked at every harvest, so swap shows up again if it is enabled later on.
+        match current_data.swap_harvest.mem_total_in_mb {
+            0 => None,
+            swap_total_in_mb => Some((
+                format!(
+                    "{:3.0}%",
                     current_data.swap_harvest.mem_used_in_mb as f64 * 100.0
-                        / current_data.swap_harvest.mem_total_in_mb as f64,
-            }
-        ),
-        format!(
-            "   {:.1}GB/{:.1}GB",
-            current_data.swap_harvest.mem_used_in_mb as f64 / 1024.0,
-            (current_data.swap_harvest.mem_total_in_mb as f64 / 1024.0)
-        ),
+                        / swap_total_in_mb as f64
+                ),
+                format!(
+                    "   {}/{}{}",
+                    humanize_bytes(current_data.swap_harvest.mem_used_in_mb * MEBI_LIMIT),
+                    humanize_bytes(swap_total_in_mb * MEBI_LIMIT),
+                    // zram swap takes up less memory than it holds.
+                    annotate(&[
+                        match (
+                            current_data.swap_harvest.zram_data_in_mb,
+                            current_data.swap_harvest.zram_compressed_in_mb,
+                        ) {
+                            (Some(data_in_mb), Some(compressed_in_mb)) => Some(format!(
+                                "zram {} → {}",
+                                humanize_bytes(data_in_mb * MEBI_LIMIT),
+                                humanize_bytes(compressed_in_mb * MEBI_LIMIT)
+                            )),
+                            _ => None,
+                        }
+                    ])
+                ),
+            )),
+        },
     )
 }
 
+/// Joins whichever notes are present into a parenthesised suffix for a label, like
+/// " (cache 1.2GiB, ARC 3.0GiB)".
+fn annotate(notes: &[Option<String>]) -> String {
+    let notes = notes.iter().flatten().cloned().collect::<Vec<_>>();
+    if notes.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", notes.join(", "))
+    }
+}
+
 pub fn get_rx_tx_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool,
 ) -> (Vec<Point>, Vec<Point>) {
@@ -352,33 +948,66 @@ pub fn get_rx_tx_data_points(
     (rx, tx)
 }
 
+/// A horizontal line drawn across the network graph at a configured threshold.
+#[derive(Clone, Debug)]
+pub struct NetworkThresholdLine {
+    /// The threshold, on the same (log2) scale as the graph.
+    pub value: f64,
+    pub label: String,
+    /// Whether the current rate is above the threshold.
+    pub is_exceeded: bool,
+}
+
+fn get_network_threshold_line(
+    name: &str, threshold: Option<u64>, current_rate: u64,
+) -> Option<NetworkThresholdLine> {
+    threshold.map(|threshold| {
+        let (value, unit) = get_exact_byte_values(threshold, false);
+        NetworkThresholdLine {
+            value: (threshold as f64).log2(),
+            label: format!("{} limit: {:.1}{}/s", name, value, unit),
+            is_exceeded: current_rate > threshold,
+        }
+    })
+}
+
+/// Formats a packet rate, abbreviating thousands and millions.
+fn get_packet_rate_display(packets_per_sec: f64) -> String {
+    if packets_per_sec >= 1_000_000.0 {
+        format!("{:.1}M", packets_per_sec / 1_000_000.0)
+    } else if packets_per_sec >= 1_000.0 {
+        format!("{:.1}k", packets_per_sec / 1_000.0)
+    } else {
+        format!("{:.0}", packets_per_sec)
+    }
+}
+
 pub fn convert_network_data_points(
     current_data: &data_farmer::DataCollection, is_frozen: bool, need_four_points: bool,
+    thresholds: (Option<u64>, Option<u64>),
 ) -> ConvertedNetworkData {
     let (rx, tx) = get_rx_tx_data_points(current_data, is_frozen);
-
-    let total_rx_converted_result: (f64, String);
-    let rx_converted_result: (f64, String);
-    let total_tx_converted_result: (f64, String);
-    let tx_converted_result: (f64, String);
-
-    rx_converted_result = get_exact_byte_values(current_data.network_harvest.rx, false);
-    total_rx_converted_result = get_exact_byte_values(current_data.network_harvest.total_rx, false);
-
-    tx_converted_result = get_exact_byte_values(current_data.network_harvest.tx, false);
-    total_tx_converted_result = get_exact_byte_values(current_data.network_harvest.total_tx, false);
+    let packets_display = format!(
+        "RX: {} pkt/s TX: {} pkt/s",
+        get_packet_rate_display(current_data.network_harvest.rx_packets_per_sec),
+        get_packet_rate_display(current_data.network_harvest.tx_packets_per_sec)
+    );
+    let rx_threshold =
+        get_network_threshold_line("RX", thresholds.0, current_data.network_harvest.rx);
+    let tx_threshold =
+        get_network_threshold_line("TX", thresholds.1, current_data.network_harvest.tx);
+
+    let rx_converted_result = get_exact_byte_values(current_data.network_harvest.rx, false);
+    let total_rx_display = humanize_bytes(current_data.network_harvest.total_rx);
+
+    let tx_converted_result = get_exact_byte_values(current_data.network_harvest.tx, false);
+    let total_tx_display = humanize_bytes(current_data.network_harvest.total_tx);
 
     if need_four_points {
         let rx_display = format!("{:.*}{}", 1, rx_converted_result.0, rx_converted_result.1);
-        let total_rx_display = Some(format!(
-            "{:.*}{}",
-            1, total_rx_converted_result.0, total_rx_converted_result.1
-        ));
+        let total_rx_display = Some(total_rx_display);
         let tx_display = format!("{:.*}{}", 1, tx_converted_result.0, tx_converted_result.1);
-        let total_tx_display = Some(format!(
-            "{:.*}{}",
-            1, total_tx_converted_result.0, total_tx_converted_result.1
-        ));
+        let total_tx_display = Some(total_tx_display);
         ConvertedNetworkData {
             rx,
             tx,
@@ -386,23 +1015,20 @@ pub fn convert_network_data_points(
             tx_display,
             total_rx_display,
             total_tx_display,
+            rx_threshold,
+            tx_threshold,
+            packets_display,
         }
     } else {
         let rx_display = format!(
             "RX: {:<9} All: {:<9}",
             format!("{:.1}{:3}", rx_converted_result.0, rx_converted_result.1),
-            format!(
-                "{:.1}{:3}",
-                total_rx_converted_result.0, total_rx_converted_result.1
-            )
+            total_rx_display
         );
         let tx_display = format!(
             "TX: {:<9} All: {:<9}",
             format!("{:.1}{:3}", tx_converted_result.0, tx_converted_result.1),
-            format!(
-                "{:.1}{:3}",
-                total_tx_converted_result.0, total_tx_converted_result.1
-            )
+            total_tx_display
         );
 
         ConvertedNetworkData {
@@ -412,6 +1038,9 @@ pub fn convert_network_data_points(
             tx_display,
             total_rx_display: None,
             total_tx_display: None,
+            rx_threshold,
+            tx_threshold,
+            packets_display,
         }
     }
 }
@@ -426,6 +1055,45 @@ pub enum ProcessNamingType {
     Path,
 }
 
+/// Formats the memory usage of a process.  Whole numbers are plenty for smaller units, but on
+/// machines with a lot of memory we want to tell apart 1.2GiB and 1.8GiB.
+fn get_mem_usage_string(mem_usage_str: &(f64, String)) -> String {
+    match mem_usage_str.1.as_str() {
+        "GiB" | "TiB" => format!("{:.1}{}", mem_usage_str.0, mem_usage_str.1),
+        _ => format!("{:.0}{}", mem_usage_str.0, mem_usage_str.1),
+    }
+}
+
+/// Formats the I/O columns of a process.  If we couldn't read the process' I/O at all (ie: due to
+/// permissions), we show a dash instead so it isn't mistaken for a process that had no I/O.
+fn get_io_strings(
+    is_io_readable: bool, read_per_sec: u64, write_per_sec: u64, total_read: u64, total_write: u64,
+) -> (String, String, String, String) {
+    if is_io_readable {
+        let converted_rps = get_exact_byte_values(read_per_sec, false);
+        let converted_wps = get_exact_byte_values(write_per_sec, false);
+        let converted_total_read = get_exact_byte_values(total_read, false);
+        let converted_total_write = get_exact_byte_values(total_write, false);
+
+        (
+            format!("{:.*}{}/s", 0, converted_rps.0, converted_rps.1),
+            format!("{:.*}{}/s", 0, converted_wps.0, converted_wps.1),
+            format!("{:.*}{}", 0, converted_total_read.0, converted_total_read.1),
+            format!(
+                "{:.*}{}",
+                0, converted_total_write.0, converted_total_write.1
+            ),
+        )
+    } else {
+        (
+            "-".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+        )
+    }
+}
+
 pub fn convert_process_data(
     current_data: &data_farmer::DataCollection,
 ) -> Vec<ConvertedProcessData> {
@@ -436,17 +1104,12 @@ pub fn convert_process_data(
         .process_harvest
         .iter()
         .map(|process| {
-            let converted_rps = get_exact_byte_values(process.read_bytes_per_sec, false);
-            let converted_wps = get_exact_byte_values(process.write_bytes_per_sec, false);
-            let converted_total_read = get_exact_byte_values(process.total_read_bytes, false);
-            let converted_total_write = get_exact_byte_values(process.total_write_bytes, false);
-
-            let read_per_sec = format!("{:.*}{}/s", 0, converted_rps.0, converted_rps.1);
-            let write_per_sec = format!("{:.*}{}/s", 0, converted_wps.0, converted_wps.1);
-            let total_read = format!("{:.*}{}", 0, converted_total_read.0, converted_total_read.1);
-            let total_write = format!(
-                "{:.*}{}",
-                0, converted_total_write.0, converted_total_write.1
+            let (read_per_sec, write_per_sec, total_read, total_write) = get_io_strings(
+                process.is_io_readable,
+                process.read_bytes_per_sec,
+                process.write_bytes_per_sec,
+                process.total_read_bytes,
+                process.total_write_bytes,
             );
 
             ConvertedProcessData {
@@ -459,7 +1122,11 @@ pub fn convert_process_data(
                 mem_percent_usage: process.mem_usage_percent,
                 mem_usage_bytes: process.mem_usage_bytes,
                 mem_usage_str: get_exact_byte_values(process.mem_usage_bytes, false),
+                total_cpu_time: process.total_cpu_time,
+                start_time_secs: process.start_time_secs,
                 group_pids: vec![process.pid],
+                group_states: vec![process.process_state.to_owned()],
+                group_users: vec![process.user.to_owned()],
                 read_per_sec,
                 write_per_sec,
                 total_read,
@@ -468,13 +1135,139 @@ pub fn convert_process_data(
                 wps_f64: process.write_bytes_per_sec as f64,
                 tr_f64: process.total_read_bytes as f64,
                 tw_f64: process.total_write_bytes as f64,
+                is_io_readable: process.is_io_readable,
+                fd_count: process.fd_count,
+                thread_count: process.thread_count,
+                nice: process.nice,
                 process_state: process.process_state.to_owned(),
                 process_char: process.process_state_char,
+                user: process.user.to_owned(),
+                container: process.container_name.clone().unwrap_or_default(),
                 process_description_prefix: None,
                 is_disabled_entry: false,
+                search_match: None,
+            }
+        })
+        .collect::<Vec<_>>()
+}
+
+/// Formats a CPU time in seconds like `top`'s TIME+ column, as `H:MM:SS.cc`.
+pub fn get_cpu_time_string(cpu_time_secs: f64) -> String {
+    // Work in hundredths of a second so that rounding never shows something like "0:00:60.00".
+    let centiseconds = (cpu_time_secs * 100.0).round() as u64;
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        centiseconds / 360_000,
+        centiseconds / 6000 % 60,
+        centiseconds / 100 % 60,
+        centiseconds % 100
+    )
+}
+
+/// Formats a process start time as `HH:MM` if it was today, or `MMM DD` otherwise.
+pub fn get_start_time_string(start_time_secs: u64, today: chrono::Date<chrono::Local>) -> String {
+    use chrono::TimeZone;
+
+    let start_time = chrono::Local.timestamp(start_time_secs as i64, 0);
+    if start_time.date() == today {
+        start_time.format("%H:%M").to_string()
+    } else {
+        start_time.format("%b %d").to_string()
+    }
+}
+
+/// Formats how long a process has been running using its largest unit, like `45s`, `12m`,
+/// `3h`, or `2d`.
+pub fn get_age_string(age_secs: u64) -> String {
+    match age_secs {
+        0..=59 => format!("{}s", age_secs),
+        60..=3599 => format!("{}m", age_secs / 60),
+        3600..=86399 => format!("{}h", age_secs / 3600),
+        _ => format!("{}d", age_secs / 86400),
+    }
+}
+
+/// Converts the details of a single process into lines for the process details dialog.
+pub fn convert_process_details(
+    pid: Pid, details: &data_harvester::processes::ProcessDetails,
+) -> Vec<String> {
+    use chrono::TimeZone;
+
+    fn or_na<T>(value: Option<T>, format: impl Fn(T) -> String) -> String {
+        value.map(format).unwrap_or_else(|| "N/A".to_string())
+    }
+
+    let format_bytes = |bytes: u64| get_mem_usage_string(&get_exact_byte_values(bytes, false));
+
+    vec![
+        format!("PID:          {}", pid),
+        format!("Name:         {}", details.name),
+        format!("Command:      {}", details.command),
+        format!(
+            "Started:      {}",
+            or_na(details.start_time, |start_time| chrono::Local
+                .timestamp(start_time as i64, 0)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string())
+        ),
+        format!(
+            "CPU time:     {}",
+            or_na(details.cpu_time_secs, get_cpu_time_string)
+        ),
+        format!("Memory (RSS): {}", or_na(details.rss_bytes, format_bytes)),
+        format!(
+            "Virtual mem:  {}",
+            or_na(details.virtual_bytes, format_bytes)
+        ),
+        format!(
+            "Threads:      {}",
+            or_na(details.thread_count, |count| count.to_string())
+        ),
+        format!(
+            "Open FDs:     {}",
+            or_na(details.fd_count, |count| count.to_string())
+        ),
+        format!(
+            "Cgroup:       {}",
+            or_na(details.cgroup.clone(), |cgroup| cgroup)
+        ),
+        convert_affinity_entry(details),
+    ]
+}
+
+/// Returns the affinity line of the process details, listing runs of cores as ranges, like
+/// "0-3,8,10".
+pub fn convert_affinity_entry(details: &data_harvester::processes::ProcessDetails) -> String {
+    let affinity = match &details.affinity {
+        Some(affinity) if !affinity.is_empty() => affinity,
+        _ => return "Affinity:     N/A".to_string(),
+    };
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for core in affinity {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == *core => *end = *core,
+            _ => ranges.push((*core, *core)),
+        }
+    }
+
+    let cores = ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{}-{}", start, end)
             }
         })
         .collect::<Vec<_>>()
+        .join(",");
+
+    if affinity.len() == 1 {
+        format!("Affinity:     {} (pinned)", cores)
+    } else {
+        format!("Affinity:     {}", cores)
+    }
 }
 
 const BRANCH_ENDING: char = '└';
@@ -484,11 +1277,8 @@ const BRANCH_HORIZONTAL: char = '─';
 
 pub fn tree_process_data(
     single_process_data: &[ConvertedProcessData], is_using_command: bool,
-    sort_type: &ProcessSorting, is_sort_descending: bool,
+    sort_type: &ProcessSorting, is_sort_descending: bool, collapsed_pids: &HashSet<Pid>,
 ) -> Vec<ConvertedProcessData> {
-    // TODO: [TREE] Allow for collapsing entries.
-    // TODO: [TREE] Option to sort usage by total branch usage or individual value usage?
-
     // Let's first build up a (really terrible) parent -> child mapping...
     // At the same time, let's make a mapping of PID -> process data!
     let mut parent_child_mapping: HashMap<Pid, IndexSet<Pid>> = HashMap::default();
@@ -700,6 +1490,60 @@ pub fn tree_process_data(
                     utils::gen_util::get_ordering(a.1.tw_f64, b.1.tw_f64, is_sort_descending)
                 });
             }
+            ProcessSorting::FdCount => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.fd_count, b.1.fd_count, is_sort_descending)
+                });
+            }
+            ProcessSorting::Nice => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(a.1.nice, b.1.nice, is_sort_descending)
+                });
+            }
+            ProcessSorting::ThreadCount => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.thread_count,
+                        b.1.thread_count,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::StartTime => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.start_time_secs,
+                        b.1.start_time_secs,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::Age => {
+                // Older processes started earlier.
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.start_time_secs.map(std::cmp::Reverse),
+                        b.1.start_time_secs.map(std::cmp::Reverse),
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::CpuTime => {
+                to_sort_vec.sort_by(|a, b| {
+                    utils::gen_util::get_ordering(
+                        a.1.total_cpu_time,
+                        b.1.total_cpu_time,
+                        is_sort_descending,
+                    )
+                });
+            }
+            ProcessSorting::User => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    &a.1.user.to_lowercase(),
+                    &b.1.user.to_lowercase(),
+                    is_sort_descending,
+                )
+            }),
             ProcessSorting::State => to_sort_vec.sort_by(|a, b| {
                 utils::gen_util::get_ordering(
                     &a.1.process_state.to_lowercase(),
@@ -707,21 +1551,64 @@ pub fn tree_process_data(
                     is_sort_descending,
                 )
             }),
+            ProcessSorting::Container => to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    &a.1.container.to_lowercase(),
+                    &b.1.container.to_lowercase(),
+                    is_sort_descending,
+                )
+            }),
             ProcessSorting::Count => {
                 // Should never occur in this case.
             }
         }
     }
 
+    /// A post-order traversal to sum up the usage of each branch, so that siblings are sorted
+    /// by the usage of their entire subtree rather than just their own.
+    fn sum_branch_usage(
+        current_pid: Pid, parent_child_mapping: &HashMap<Pid, IndexSet<Pid>>,
+        pid_process_mapping: &HashMap<Pid, &ConvertedProcessData>,
+        branch_process_mapping: &mut HashMap<Pid, ConvertedProcessData>,
+    ) -> Option<ConvertedProcessData> {
+        let mut branch = (*pid_process_mapping.get(&current_pid)?).clone();
+
+        if let Some(children) = parent_child_mapping.get(&current_pid) {
+            for child_pid in children {
+                if let Some(child_branch) = sum_branch_usage(
+                    *child_pid,
+                    parent_child_mapping,
+                    pid_process_mapping,
+                    branch_process_mapping,
+                ) {
+                    branch.cpu_percent_usage += child_branch.cpu_percent_usage;
+                    branch.mem_percent_usage += child_branch.mem_percent_usage;
+                    branch.mem_usage_bytes += child_branch.mem_usage_bytes;
+                    branch.rps_f64 += child_branch.rps_f64;
+                    branch.wps_f64 += child_branch.wps_f64;
+                    branch.tr_f64 += child_branch.tr_f64;
+                    branch.tw_f64 += child_branch.tw_f64;
+                }
+            }
+        }
+
+        branch_process_mapping.insert(current_pid, branch.clone());
+        Some(branch)
+    }
+
     /// A DFS traversal to correctly build the prefix lines (the pretty '├' and '─' lines) and
-    /// the correct order to the PID tree as a vector.
+    /// the correct order to the PID tree as a vector.  Children of collapsed PIDs are skipped.
     fn build_explored_pids(
         current_pid: Pid, parent_child_mapping: &HashMap<Pid, IndexSet<Pid>>,
-        prev_drawn_lines: &str,
+        collapsed_pids: &HashSet<Pid>, prev_drawn_lines: &str,
     ) -> (Vec<Pid>, Vec<String>) {
         let mut explored_pids: Vec<Pid> = vec![current_pid];
         let mut lines: Vec<String> = vec![];
 
+        if collapsed_pids.contains(&current_pid) {
+            return (explored_pids, lines);
+        }
+
         if let Some(children) = parent_child_mapping.get(&current_pid) {
             for (itx, child) in children.iter().rev().enumerate() {
                 let new_drawn_lines = if itx == children.len() - 1 {
@@ -730,8 +1617,12 @@ pub fn tree_process_data(
                     format!("{}{}  ", prev_drawn_lines, BRANCH_VERTICAL)
                 };
 
-                let (pid_res, branch_res) =
-                    build_explored_pids(*child, parent_child_mapping, new_drawn_lines.as_str());
+                let (pid_res, branch_res) = build_explored_pids(
+                    *child,
+                    parent_child_mapping,
+                    collapsed_pids,
+                    new_drawn_lines.as_str(),
+                );
 
                 if itx == children.len() - 1 {
                     lines.push(format!(
@@ -763,9 +1654,27 @@ pub fn tree_process_data(
         (explored_pids, lines)
     }
 
+    pids_to_explore
+        .retain(|pid| !prune_disabled_pids(*pid, &mut parent_child_mapping, &pid_process_mapping));
+
+    // Only sum up what's left after pruning, so filtered-out branches don't affect the order.
+    let mut branch_process_mapping: HashMap<Pid, ConvertedProcessData> = HashMap::default();
+    for pid in &pids_to_explore {
+        sum_branch_usage(
+            *pid,
+            &parent_child_mapping,
+            &pid_process_mapping,
+            &mut branch_process_mapping,
+        );
+    }
+    let branch_process_mapping: HashMap<Pid, &ConvertedProcessData> = branch_process_mapping
+        .iter()
+        .map(|(pid, process)| (*pid, process))
+        .collect();
+
     let mut to_sort_vec = Vec::new();
     for pid in pids_to_explore {
-        if let Some(process) = pid_process_mapping.get(&pid) {
+        if let Some(process) = branch_process_mapping.get(&pid) {
             to_sort_vec.push((pid, *process));
         }
     }
@@ -773,20 +1682,19 @@ pub fn tree_process_data(
     pids_to_explore = to_sort_vec.iter().map(|(pid, _proc)| *pid).collect();
 
     while let Some(current_pid) = pids_to_explore.pop_front() {
-        if !prune_disabled_pids(current_pid, &mut parent_child_mapping, &pid_process_mapping) {
-            sort_remaining_pids(
-                current_pid,
-                sort_type,
-                is_sort_descending,
-                &mut parent_child_mapping,
-                &pid_process_mapping,
-            );
+        sort_remaining_pids(
+            current_pid,
+            sort_type,
+            is_sort_descending,
+            &mut parent_child_mapping,
+            &branch_process_mapping,
+        );
 
-            let (pid_res, branch_res) = build_explored_pids(current_pid, &parent_child_mapping, "");
-            lines.push(String::default());
-            lines.extend(branch_res);
-            explored_pids.extend(pid_res);
-        }
+        let (pid_res, branch_res) =
+            build_explored_pids(current_pid, &parent_child_mapping, collapsed_pids, "");
+        lines.push(String::default());
+        lines.extend(branch_res);
+        explored_pids.extend(pid_res);
     }
 
     // Now let's "rearrange" our current list of converted process data into the correct
@@ -797,9 +1705,15 @@ pub fn tree_process_data(
         .filter_map(|(pid, prefix)| match pid_process_mapping.remove(pid) {
             Some(process) => {
                 let mut p = process.clone();
+                let is_collapsed = collapsed_pids.contains(pid)
+                    && parent_child_mapping
+                        .get(pid)
+                        .map(|children| !children.is_empty())
+                        .unwrap_or(false);
                 p.process_description_prefix = Some(format!(
-                    "{}{}",
+                    "{}{}{}",
                     prefix,
+                    if is_collapsed { "+ " } else { "" },
                     if is_using_command {
                         &p.command
                     } else {
@@ -815,65 +1729,127 @@ pub fn tree_process_data(
 
 pub fn stringify_process_data(
     proc_widget_state: &ProcWidgetState, finalized_process_data: &[ConvertedProcessData],
+    pinned_pids: &HashSet<Pid>,
 ) -> Vec<(Vec<(String, Option<String>)>, bool)> {
-    let is_proc_widget_grouped = proc_widget_state.is_grouped;
-    let is_using_command = proc_widget_state.is_using_command;
     let is_tree = proc_widget_state.is_tree_mode;
-    let mem_enabled = proc_widget_state.columns.is_enabled(&ProcessSorting::Mem);
+    let enabled_columns = proc_widget_state.columns.get_enabled_columns();
+    let today = chrono::Local::today();
+    let now_secs = chrono::Local::now().timestamp().max(0) as u64;
 
     finalized_process_data
         .iter()
         .map(|process| {
+            let pin_marker = if process.has_any_pid(pinned_pids) {
+                PIN_MARKER
+            } else {
+                ""
+            };
+
             (
-                vec![
-                    (
-                        if is_proc_widget_grouped {
-                            process.group_pids.len().to_string()
-                        } else {
-                            process.pid.to_string()
-                        },
-                        None,
-                    ),
-                    (
-                        if is_tree {
-                            if let Some(prefix) = &process.process_description_prefix {
-                                prefix.clone()
-                            } else {
-                                String::default()
-                            }
-                        } else if is_using_command {
-                            process.command.clone()
-                        } else {
-                            process.name.clone()
-                        },
-                        None,
-                    ),
-                    (format!("{:.1}%", process.cpu_percent_usage), None),
-                    (
-                        if mem_enabled {
-                            format!("{:.0}{}", process.mem_usage_str.0, process.mem_usage_str.1)
-                        } else {
-                            format!("{:.1}%", process.mem_percent_usage)
-                        },
-                        None,
-                    ),
-                    (process.read_per_sec.clone(), None),
-                    (process.write_per_sec.clone(), None),
-                    (process.total_read.clone(), None),
-                    (process.total_write.clone(), None),
-                    (
-                        process.process_state.clone(),
-                        Some(process.process_char.to_string()),
-                    ),
-                ],
+                enabled_columns
+                    .iter()
+                    .map(|column| match column {
+                        ProcessSorting::Count => (process.group_pids.len().to_string(), None),
+                        ProcessSorting::Pid => (process.pid.to_string(), None),
+                        ProcessSorting::ProcessName | ProcessSorting::Command => (
+                            format!(
+                                "{}{}",
+                                pin_marker,
+                                if is_tree {
+                                    process
+                                        .process_description_prefix
+                                        .as_deref()
+                                        .unwrap_or_default()
+                                } else if let ProcessSorting::Command = column {
+                                    &process.command
+                                } else {
+                                    &process.name
+                                }
+                            ),
+                            None,
+                        ),
+                        ProcessSorting::CpuPercent => {
+                            (format!("{:.1}%", process.cpu_percent_usage), None)
+                        }
+                        ProcessSorting::Mem => (get_mem_usage_string(&process.mem_usage_str), None),
+                        ProcessSorting::MemPercent => {
+                            (format!("{:.1}%", process.mem_percent_usage), None)
+                        }
+                        ProcessSorting::StartTime => (
+                            process
+                                .start_time_secs
+                                .map(|start_time_secs| {
+                                    get_start_time_string(start_time_secs, today)
+                                })
+                                .unwrap_or_else(|| "-".to_string()),
+                            None,
+                        ),
+                        ProcessSorting::Age => (
+                            process
+                                .start_time_secs
+                                .map(|start_time_secs| {
+                                    get_age_string(now_secs.saturating_sub(start_time_secs))
+                                })
+                                .unwrap_or_else(|| "-".to_string()),
+                            None,
+                        ),
+                        ProcessSorting::CpuTime => (
+                            process
+                                .total_cpu_time
+                                .map(get_cpu_time_string)
+                                .unwrap_or_else(|| "-".to_string()),
+                            None,
+                        ),
+                        ProcessSorting::ReadPerSecond => (process.read_per_sec.clone(), None),
+                        ProcessSorting::WritePerSecond => (process.write_per_sec.clone(), None),
+                        ProcessSorting::TotalRead => (process.total_read.clone(), None),
+                        ProcessSorting::TotalWrite => (process.total_write.clone(), None),
+                        ProcessSorting::FdCount => (
+                            process
+                                .fd_count
+                                .map(|fd_count| fd_count.to_string())
+                                .unwrap_or_else(|| "-".to_string()),
+                            None,
+                        ),
+                        ProcessSorting::ThreadCount => (
+                            process
+                                .thread_count
+                                .map(|thread_count| thread_count.to_string())
+                                .unwrap_or_else(|| "-".to_string()),
+                            None,
+                        ),
+                        ProcessSorting::Nice => (
+                            process
+                                .nice
+                                .map(|nice| nice.to_string())
+                                .unwrap_or_else(|| "-".to_string()),
+                            None,
+                        ),
+                        ProcessSorting::User => (process.user.clone(), None),
+                        ProcessSorting::Container => (process.container.clone(), None),
+                        ProcessSorting::State => (
+                            process.process_state.clone(),
+                            Some(process.process_char.to_string()),
+                        ),
+                    })
+                    .collect(),
                 process.is_disabled_entry,
             )
         })
         .collect()
 }
 
+/// Processes not in any container are grouped under this name when grouping by container.
+const NO_CONTAINER_GROUP: &str = "(none)";
+
+/// Shown before the names of pinned processes.
+const PIN_MARKER: &str = "● ";
+
+/// Groups processes by the given key.  When grouping by name, processes matching one of
+/// `process_groups` are grouped under the name of the first rule they match instead.
 pub fn group_process_data(
     single_process_data: &[ConvertedProcessData], is_using_command: bool,
+    grouping_key: ProcessGroupingKey, process_groups: &[ProcessGroupRule],
 ) -> Vec<ConvertedProcessData> {
     #[derive(Clone, Default, Debug)]
     struct SingleProcessData {
@@ -881,55 +1857,136 @@ pub fn group_process_data(
         pub cpu_percent_usage: f64,
         pub mem_percent_usage: f64,
         pub mem_usage_bytes: u64,
+        pub total_cpu_time: Option<f64>,
+        pub start_time_secs: Option<u64>,
         pub group_pids: Vec<Pid>,
         pub read_per_sec: f64,
         pub write_per_sec: f64,
         pub total_read: f64,
         pub total_write: f64,
-        pub process_state: String,
+        pub is_io_readable: bool,
+        pub fd_count: Option<u32>,
+        pub thread_count: Option<u64>,
+        pub nice_counts: HashMap<i32, usize>,
+        pub state_counts: HashMap<char, usize>,
+        pub states: HashSet<String>,
+        pub user_counts: HashMap<String, usize>,
+        pub container_counts: HashMap<String, usize>,
     }
 
     let mut grouped_hashmap: HashMap<String, SingleProcessData> = std::collections::HashMap::new();
 
     single_process_data.iter().for_each(|process| {
         let entry = grouped_hashmap
-            .entry(if is_using_command {
-                process.command.to_string()
-            } else {
-                process.name.to_string()
+            .entry(match grouping_key {
+                ProcessGroupingKey::Name => {
+                    let name = if is_using_command {
+                        &process.command
+                    } else {
+                        &process.name
+                    };
+                    process_groups
+                        .iter()
+                        .find(|rule| rule.regex.is_match(name))
+                        .map_or(name, |rule| &rule.name)
+                        .to_string()
+                }
+                ProcessGroupingKey::User => process.user.to_string(),
+                ProcessGroupingKey::Container if process.container.is_empty() => {
+                    NO_CONTAINER_GROUP.to_string()
+                }
+                ProcessGroupingKey::Container => process.container.to_string(),
             })
             .or_insert(SingleProcessData {
                 pid: process.pid,
                 ..SingleProcessData::default()
             });
 
-        (*entry).cpu_percent_usage += process.cpu_percent_usage;
-        (*entry).mem_percent_usage += process.mem_percent_usage;
-        (*entry).mem_usage_bytes += process.mem_usage_bytes;
-        (*entry).group_pids.push(process.pid);
-        (*entry).read_per_sec += process.rps_f64;
-        (*entry).write_per_sec += process.wps_f64;
-        (*entry).total_read += process.tr_f64;
-        (*entry).total_write += process.tw_f64;
+        entry.cpu_percent_usage += process.cpu_percent_usage;
+        entry.mem_percent_usage += process.mem_percent_usage;
+        entry.mem_usage_bytes += process.mem_usage_bytes;
+        if let Some(total_cpu_time) = process.total_cpu_time {
+            entry.total_cpu_time = Some(entry.total_cpu_time.unwrap_or(0.0) + total_cpu_time);
+        }
+        if let Some(start_time_secs) = process.start_time_secs {
+            entry.start_time_secs = Some(match entry.start_time_secs {
+                Some(earliest_start_time_secs) => {
+                    std::cmp::min(earliest_start_time_secs, start_time_secs)
+                }
+                None => start_time_secs,
+            });
+        }
+        entry.group_pids.push(process.pid);
+        entry.read_per_sec += process.rps_f64;
+        entry.write_per_sec += process.wps_f64;
+        entry.total_read += process.tr_f64;
+        entry.total_write += process.tw_f64;
+        entry.is_io_readable |= process.is_io_readable;
+        if let Some(fd_count) = process.fd_count {
+            entry.fd_count = Some(entry.fd_count.unwrap_or(0) + fd_count);
+        }
+        if let Some(nice) = process.nice {
+            *entry.nice_counts.entry(nice).or_insert(0) += 1;
+        }
+        if let Some(thread_count) = process.thread_count {
+            entry.thread_count = Some(entry.thread_count.unwrap_or(0) + thread_count);
+        }
+        *entry.state_counts.entry(process.process_char).or_insert(0) += 1;
+        entry.states.insert(process.process_state.clone());
+        *entry.user_counts.entry(process.user.clone()).or_insert(0) += 1;
+        *entry
+            .container_counts
+            .entry(process.container.clone())
+            .or_insert(0) += 1;
     });
 
     grouped_hashmap
         .iter()
         .map(|(identifier, process_details)| {
             let p = process_details.clone();
-            let converted_rps = get_exact_byte_values(p.read_per_sec as u64, false);
-            let converted_wps = get_exact_byte_values(p.write_per_sec as u64, false);
-            let converted_total_read = get_exact_byte_values(p.total_read as u64, false);
-            let converted_total_write = get_exact_byte_values(p.total_write as u64, false);
-
-            let read_per_sec = format!("{:.*}{}/s", 0, converted_rps.0, converted_rps.1);
-            let write_per_sec = format!("{:.*}{}/s", 0, converted_wps.0, converted_wps.1);
-            let total_read = format!("{:.*}{}", 0, converted_total_read.0, converted_total_read.1);
-            let total_write = format!(
-                "{:.*}{}",
-                0, converted_total_write.0, converted_total_write.1
+            let (read_per_sec, write_per_sec, total_read, total_write) = get_io_strings(
+                p.is_io_readable,
+                p.read_per_sec as u64,
+                p.write_per_sec as u64,
+                p.total_read as u64,
+                p.total_write as u64,
             );
 
+            // Summarize the states of the group, most common first (ie: "3R 1S").
+            let mut state_counts = p.state_counts.into_iter().collect::<Vec<_>>();
+            state_counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            let process_state = state_counts
+                .iter()
+                .map(|(state_char, count)| format!("{}{}", count, state_char))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let process_char = state_counts
+                .first()
+                .map(|(state_char, _count)| *state_char)
+                .unwrap_or_default();
+
+            let group_states = p.states.into_iter().collect::<Vec<_>>();
+            let group_users = p.user_counts.keys().cloned().collect::<Vec<_>>();
+
+            // Show the user that owns the most processes in the group.
+            let user = p
+                .user_counts
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+                .map(|(user, _count)| user)
+                .unwrap_or_default();
+            let nice = p
+                .nice_counts
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+                .map(|(nice, _count)| nice);
+            let container = p
+                .container_counts
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+                .map(|(container, _count)| container)
+                .unwrap_or_default();
+
             ConvertedProcessData {
                 pid: p.pid,
                 ppid: None,
@@ -940,7 +1997,11 @@ pub fn group_process_data(
                 mem_percent_usage: p.mem_percent_usage,
                 mem_usage_bytes: p.mem_usage_bytes,
                 mem_usage_str: get_exact_byte_values(p.mem_usage_bytes, false),
+                total_cpu_time: p.total_cpu_time,
+                start_time_secs: p.start_time_secs,
                 group_pids: p.group_pids,
+                group_states,
+                group_users,
                 read_per_sec,
                 write_per_sec,
                 total_read,
@@ -949,10 +2010,17 @@ pub fn group_process_data(
                 wps_f64: p.write_per_sec,
                 tr_f64: p.total_read,
                 tw_f64: p.total_write,
-                process_state: p.process_state,
+                is_io_readable: p.is_io_readable,
+                fd_count: p.fd_count,
+                thread_count: p.thread_count,
+                nice,
+                process_state,
                 process_description_prefix: None,
-                process_char: char::default(),
+                process_char,
+                user,
+                container,
                 is_disabled_entry: false,
+                search_match: None,
             }
         })
         .collect::<Vec<_>>()
@@ -1002,6 +2070,30 @@ pub fn convert_battery_harvest(
                 None
             },
             health: format!("{:.2}%", battery_harvest.health_percent),
+            state: match battery_harvest.state {
+                battery::State::Charging => "Charging",
+                battery::State::Discharging => "Discharging",
+                battery::State::Empty => "Empty",
+                battery::State::Full => "Full",
+                _ => "Unknown",
+            }
+            .to_string(),
         })
         .collect()
 }
+
+/// Returns the lines of the socket widget, one per kind of socket.
+pub fn convert_socket_data(current_data: &data_farmer::DataCollection) -> Vec<String> {
+    match &current_data.socket_harvest {
+        Some(sockets) => vec![
+            format!(
+                "TCP: {} (ESTABLISHED:{}, TIME_WAIT:{}, CLOSE_WAIT:{})",
+                sockets.tcp, sockets.tcp_established, sockets.tcp_time_wait, sockets.tcp_close_wait
+            ),
+            format!("UDP: {}", sockets.udp),
+            format!("RAW: {}", sockets.raw),
+            format!("UNIX: {}", sockets.unix),
+        ],
+        None => vec!["Not supported".to_string()],
+    }
+}
@@ -22,8 +22,9 @@ use crossterm::{
 
 use app::{
     data_harvester::{self, processes::ProcessSorting},
+    keybindings::KeyAction,
     layout_manager::{UsedWidgets, WidgetDirection},
-    App,
+    App, ProcessStateFilter,
 };
 use constants::*;
 use data_conversion::*;
@@ -33,6 +34,7 @@ use utils::error;
 pub mod app;
 pub mod utils {
     pub mod error;
+    pub mod exporters;
     pub mod gen_util;
     pub mod logging;
 }
@@ -86,6 +88,27 @@ pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
 
 pub fn handle_key_event_or_break(
     event: KeyEvent, app: &mut App, reset_sender: &std::sync::mpsc::Sender<CollectionThreadEvent>,
+) -> bool {
+    let hide_kernel_threads = app.app_config_fields.hide_kernel_threads;
+    let use_current_cpu_total = app.app_config_fields.use_current_cpu_total;
+    let should_break = handle_key_event(event, app, reset_sender);
+
+    // Some settings affect what the collection thread harvests, so pass them along.
+    if app.app_config_fields.hide_kernel_threads != hide_kernel_threads
+        || app.app_config_fields.use_current_cpu_total != use_current_cpu_total
+    {
+        reset_sender
+            .send(CollectionThreadEvent::UpdateConfig(Box::new(
+                app.app_config_fields.clone(),
+            )))
+            .ok();
+    }
+
+    should_break
+}
+
+fn handle_key_event(
+    event: KeyEvent, app: &mut App, reset_sender: &std::sync::mpsc::Sender<CollectionThreadEvent>,
 ) -> bool {
     // debug!("KeyEvent: {:?}", event);
 
@@ -94,14 +117,33 @@ pub fn handle_key_event_or_break(
     // We can solve this (when we do paste probably) while keeping the throttle (mainly meant for movement)
     // by throttling after *bulk+singular* actions, not just singular ones.
 
+    // Keys the config rebound or freed are handled from the keybinding map instead of the
+    // defaults below.
+    if let Some(actions) = app.get_key_actions(&event) {
+        for action in actions {
+            match action {
+                KeyAction::Quit => return true,
+                KeyAction::Reset => {
+                    if reset_sender.send(CollectionThreadEvent::Reset).is_ok() {
+                        app.reset();
+                    }
+                }
+                _ => app.on_key_action(action),
+            }
+        }
+        return false;
+    }
+
     if event.modifiers.is_empty() {
-        // Required catch for searching - otherwise you couldn't search with q.
-        if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
+        // Required catch for searching and dialogs - otherwise you couldn't type a q.
+        if event.code == KeyCode::Char('q') && !app.is_capturing_keys() {
             return true;
         }
         match event.code {
             KeyCode::End => app.skip_to_last(),
             KeyCode::Home => app.skip_to_first(),
+            KeyCode::PageUp => app.skip_page_up(),
+            KeyCode::PageDown => app.skip_page_down(),
             KeyCode::Up => app.on_up_key(),
             KeyCode::Down => app.on_down_key(),
             KeyCode::Left => app.on_left_key(),
@@ -117,6 +159,7 @@ pub fn handle_key_event_or_break(
             KeyCode::F(3) => app.toggle_search_regex(),
             KeyCode::F(5) => app.toggle_tree_mode(),
             KeyCode::F(6) => app.toggle_sort(),
+            KeyCode::F(9) => app.start_signal_dialog(),
             _ => {}
         }
     } else {
@@ -131,11 +174,13 @@ pub fn handle_key_event_or_break(
                 _ => {}
             }
         } else if let KeyModifiers::CONTROL = event.modifiers {
-            if event.code == KeyCode::Char('c') {
+            // Ctrl-c copies from a process info dialog, rather than quitting.
+            if event.code == KeyCode::Char('c') && !app.process_info_dialog_state.is_showing_info {
                 return true;
             }
 
             match event.code {
+                KeyCode::Char('c') => app.copy_process_info(),
                 KeyCode::Char('f') => app.on_slash(),
                 KeyCode::Left => app.move_widget_selection(&WidgetDirection::Left),
                 KeyCode::Right => app.move_widget_selection(&WidgetDirection::Right),
@@ -147,8 +192,25 @@ pub fn handle_key_event_or_break(
                     }
                 }
                 KeyCode::Char('a') => app.skip_cursor_beginning(),
-                KeyCode::Char('e') => app.skip_cursor_end(),
-                KeyCode::Char('u') => app.clear_search(),
+                KeyCode::Char('e') => {
+                    if app.is_in_search_widget() || app.process_info_dialog_state.is_showing_info {
+                        app.skip_cursor_end()
+                    } else {
+                        app.export_data()
+                    }
+                }
+                KeyCode::Char('u') => {
+                    if app.is_in_search_widget() || app.process_info_dialog_state.is_showing_info {
+                        app.clear_search()
+                    } else if app.user_filter_state.is_showing_input {
+                        app.user_filter_state.input.clear()
+                    } else {
+                        app.open_user_filter()
+                    }
+                }
+                KeyCode::Char('o') => app.export_process_table(),
+                KeyCode::Char('w') => app.toggle_search_whole_word(),
+                KeyCode::Char('t') => app.cycle_state_filter(),
                 // KeyCode::Char('j') => {}, // Move down
                 // KeyCode::Char('k') => {}, // Move up
                 // KeyCode::Char('h') => {}, // Move right
@@ -210,8 +272,12 @@ pub fn read_config(config_location: Option<&str>) -> error::Result<Option<PathBu
 
 pub fn create_or_get_config(config_path: &Option<PathBuf>) -> error::Result<Config> {
     if let Some(path) = config_path {
-        if let Ok(config_string) = fs::read_to_string(path) {
-            // We found a config file!
+        if path.exists() {
+            // We found a config file!  Don't fall through to creating a new one if it can't be
+            // read, or we'd clobber the user's existing config.
+            let config_string = fs::read_to_string(path).map_err(|err| {
+                error::BottomError::ConfigError(format!("unable to read {:?}, {}", path, err))
+            })?;
             Ok(toml::from_str(config_string.as_str())?)
         } else {
             // Config file DNE...
@@ -236,9 +302,33 @@ pub fn try_drawing(
         return Err(err);
     }
 
+    if let Some(text) = app.clipboard_text.take() {
+        let message = match copy_to_clipboard(terminal, &text) {
+            Ok(()) => "Copied to the clipboard".to_string(),
+            Err(err) => format!("Copying failed: {}", err),
+        };
+        app.export_toast = Some((message, 2));
+    }
+
     Ok(())
 }
 
+/// Copies text to the clipboard using the OSC 52 escape sequence, so that it works over SSH too.
+/// This goes through the terminal backend between frames, so that it can't end up in the middle
+/// of one.
+fn copy_to_clipboard(
+    terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
+    text: &str,
+) -> std::io::Result<()> {
+    let backend = terminal.backend_mut();
+    write!(
+        backend,
+        "\x1b]52;c;{}\x07",
+        utils::gen_util::encode_base64(text.as_bytes())
+    )?;
+    backend.flush()
+}
+
 pub fn cleanup_terminal(
     terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
 ) -> error::Result<()> {
@@ -301,13 +391,23 @@ pub fn handle_force_redraws(app: &mut App) {
     }
 
     if app.cpu_state.force_update.is_some() {
-        app.canvas_data.cpu_data = convert_cpu_data_points(&app.data_collection, app.is_frozen);
+        app.canvas_data.cpu_data = convert_cpu_data_points(
+            &app.data_collection,
+            app.is_frozen,
+            app.app_config_fields.show_average_cpu,
+        );
+        let (system, iowait) = convert_cpu_breakdown_points(&app.data_collection, app.is_frozen);
+        app.canvas_data.cpu_system_data = system;
+        app.canvas_data.cpu_iowait_data = iowait;
+        app.canvas_data.cpu_stacked_data =
+            convert_cpu_stacked_points(&app.data_collection, app.is_frozen);
         app.cpu_state.force_update = None;
     }
 
     if app.mem_state.force_update.is_some() {
         app.canvas_data.mem_data = convert_mem_data_points(&app.data_collection, app.is_frozen);
         app.canvas_data.swap_data = convert_swap_data_points(&app.data_collection, app.is_frozen);
+        app.canvas_data.arc_data = convert_arc_data_points(&app.data_collection, app.is_frozen);
         app.mem_state.force_update = None;
     }
 
@@ -317,6 +417,27 @@ pub fn handle_force_redraws(app: &mut App) {
         app.canvas_data.network_data_tx = tx;
         app.net_state.force_update = None;
     }
+
+    if app.disk_state.force_update.is_some() {
+        app.canvas_data.disk_data = convert_disk_row(
+            &app.data_collection,
+            &app.filters.disk_filter,
+            app.disk_state.sort_type,
+            app.disk_state.is_sort_descending,
+            app.app_config_fields.disk_io_threshold,
+            app.app_config_fields.disk_usage_thresholds,
+        );
+        app.disk_state.force_update = None;
+    }
+
+    if app.gpu_state.force_update.is_some() {
+        app.canvas_data.gpu_data = convert_gpu_data_points(
+            &app.data_collection,
+            app.is_frozen,
+            &app.app_config_fields.temperature_type,
+        );
+        app.gpu_state.force_update = None;
+    }
 }
 
 #[allow(clippy::needless_collect)]
@@ -346,67 +467,228 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
                 .is_invalid_or_blank_search(),
             process_state.is_using_command,
             process_state.is_grouped,
+            process_state.grouping_key,
             process_state.is_tree_mode,
+            app.username_filter.clone(),
+            process_state.state_filter,
+            process_state.is_hiding_idle,
         )),
         None => None,
     };
 
-    if let Some((is_invalid_or_blank, is_using_command, is_grouped, is_tree)) = process_states {
+    if let Some((
+        is_invalid_or_blank,
+        is_using_command,
+        is_grouped,
+        grouping_key,
+        is_tree,
+        user_filter,
+        state_filter,
+        is_hiding_idle,
+    )) = process_states
+    {
         if !app.is_frozen {
             app.canvas_data.single_process_data = convert_process_data(&app.data_collection);
         }
+
+        // Only show the container column if there is something to put in it.
+        let has_containers = app
+            .canvas_data
+            .single_process_data
+            .iter()
+            .any(|process| !process.container.is_empty());
+        if let Some(proc_widget_state) = app.proc_state.get_mut_widget_state(widget_id) {
+            if proc_widget_state
+                .columns
+                .set_enabled(&ProcessSorting::Container, has_containers)
+            {
+                if !has_containers
+                    && proc_widget_state.process_sorting_type == ProcessSorting::Container
+                {
+                    proc_widget_state.process_sorting_type =
+                        proc_widget_state.columns.get_default_sorting();
+                    proc_widget_state.is_process_sort_descending = true;
+                }
+                proc_widget_state.requires_redraw = true;
+            }
+        }
+
         let process_filter = app.get_process_filter(widget_id);
-        let filtered_process_data: Vec<ConvertedProcessData> = if is_tree {
+        let matches_user_and_state = |process: &ConvertedProcessData| {
+            let is_user_match = match &user_filter {
+                Some(user) => process.user == *user,
+                None => true,
+            };
+            is_user_match && state_filter.matches(process.process_char)
+        };
+        let matches_filter = |process: &ConvertedProcessData| {
+            if !matches_user_and_state(process) {
+                return false;
+            }
+            if !is_invalid_or_blank {
+                if let Some(process_filter) = process_filter {
+                    return process_filter.check(process, is_using_command);
+                }
+            }
+            true
+        };
+
+        // Grouped entries are checked against their aggregated values, so group before filtering.
+        // PID, state, and user terms match a group if any process in it matches.
+        let mut total_groups = None;
+        let mut filtered_process_data: Vec<ConvertedProcessData> = if is_tree {
             app.canvas_data
                 .single_process_data
                 .iter()
                 .map(|process| {
                     let mut process_clone = process.clone();
-                    if !is_invalid_or_blank {
-                        if let Some(process_filter) = process_filter {
-                            process_clone.is_disabled_entry =
-                                !process_filter.check(&process_clone, is_using_command);
-                        }
-                    }
+                    process_clone.is_disabled_entry = !matches_filter(&process_clone);
                     process_clone
                 })
                 .collect::<Vec<_>>()
+        } else if is_grouped {
+            // Filter by user and state first, so that groups only count the matching processes.
+            let grouped_process_data =
+                if user_filter.is_some() || state_filter != ProcessStateFilter::All {
+                    group_process_data(
+                        &app.canvas_data
+                            .single_process_data
+                            .iter()
+                            .filter(|process| matches_user_and_state(process))
+                            .cloned()
+                            .collect::<Vec<_>>(),
+                        is_using_command,
+                        grouping_key,
+                        &app.app_config_fields.process_groups,
+                    )
+                } else {
+                    group_process_data(
+                        &app.canvas_data.single_process_data,
+                        is_using_command,
+                        grouping_key,
+                        &app.app_config_fields.process_groups,
+                    )
+                };
+            total_groups = Some(grouped_process_data.len());
+            grouped_process_data
+                .into_iter()
+                .filter(|process| matches_filter(process))
+                .collect::<Vec<_>>()
         } else {
             app.canvas_data
                 .single_process_data
                 .iter()
-                .filter(|process| {
-                    if !is_invalid_or_blank {
-                        if let Some(process_filter) = process_filter {
-                            process_filter.check(&process, is_using_command)
-                        } else {
-                            true
-                        }
-                    } else {
-                        true
-                    }
-                })
+                .filter(|process| matches_filter(process))
                 .cloned()
                 .collect::<Vec<_>>()
         };
 
+        // Idle processes are hidden after grouping, so that busy groups of idle processes stay.
+        let mut hidden_idle = 0;
+        if is_hiding_idle {
+            let is_idle = |process: &ConvertedProcessData| {
+                process.cpu_percent_usage <= 0.0
+                    && process.mem_percent_usage < IDLE_PROCESS_MAX_MEM_PERCENT
+            };
+
+            if is_tree {
+                // Tree mode needs every process to build the tree, so just disable them.
+                for process in filtered_process_data
+                    .iter_mut()
+                    .filter(|process| !process.is_disabled_entry && is_idle(process))
+                {
+                    process.is_disabled_entry = true;
+                    hidden_idle += 1;
+                }
+            } else {
+                let num_processes = filtered_process_data.len();
+                filtered_process_data.retain(|process| !is_idle(process));
+                hidden_idle = num_processes - filtered_process_data.len();
+            }
+        }
+
+        if !is_invalid_or_blank {
+            if let Some(process_filter) = process_filter {
+                for process in filtered_process_data
+                    .iter_mut()
+                    .filter(|process| !process.is_disabled_entry)
+                {
+                    process.search_match = process_filter.find_match(process, is_using_command);
+                }
+            }
+        }
+
         if let Some(proc_widget_state) = app.proc_state.get_mut_widget_state(widget_id) {
+            // What was selected in the list being replaced, so that the selection can stay on it.
+            let selected_process = app
+                .canvas_data
+                .finalized_process_data_map
+                .get(&widget_id)
+                .and_then(|prev_process_data| {
+                    prev_process_data.get(proc_widget_state.scroll_state.current_scroll_position)
+                })
+                .map(|process| {
+                    if is_grouped {
+                        app::FollowedProcess::Group(process.name.clone())
+                    } else {
+                        app::FollowedProcess::Pid(process.pid)
+                    }
+                });
+
             let mut finalized_process_data = if is_tree {
                 tree_process_data(
                     &filtered_process_data,
                     is_using_command,
                     &proc_widget_state.process_sorting_type,
                     proc_widget_state.is_process_sort_descending,
+                    &proc_widget_state.collapsed_pids,
                 )
-            } else if is_grouped {
-                group_process_data(&filtered_process_data, is_using_command)
             } else {
                 filtered_process_data
             };
 
-            // Note tree mode is sorted well before this, as it's special.
+            let process_count = match total_groups {
+                Some(total_groups) => ProcessCount {
+                    shown: finalized_process_data.len(),
+                    total: total_groups,
+                    total_pids: Some(app.canvas_data.single_process_data.len()),
+                    hidden_idle,
+                },
+                None => ProcessCount {
+                    // Tree mode keeps filtered out processes around, just disabled.
+                    shown: finalized_process_data
+                        .iter()
+                        .filter(|process| !process.is_disabled_entry)
+                        .count(),
+                    total: app.canvas_data.single_process_data.len(),
+                    total_pids: None,
+                    hidden_idle,
+                },
+            };
+            app.canvas_data
+                .process_count_map
+                .insert(widget_id, process_count);
+
+            // Note tree mode is sorted well before this, as it's special.  Pinned processes
+            // aren't moved in tree mode either, so that they stay under their parents.
             if !is_tree {
                 sort_process_data(&mut finalized_process_data, proc_widget_state);
+                let pinned_pids = &app.pinned_pids;
+                if !pinned_pids.is_empty() {
+                    finalized_process_data.sort_by_key(|process| !process.has_any_pid(pinned_pids));
+                }
+            }
+
+            if proc_widget_state.followed_process.is_some() {
+                follow_process(
+                    proc_widget_state,
+                    &finalized_process_data,
+                    &app.canvas_data.single_process_data,
+                );
+            } else if let Some(position) = selected_process.and_then(|selected_process| {
+                find_process_position(&selected_process, &finalized_process_data)
+            }) {
+                move_process_selection(proc_widget_state, position);
             }
 
             if proc_widget_state.scroll_state.current_scroll_position
@@ -420,7 +702,11 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
 
             app.canvas_data.stringified_process_data_map.insert(
                 widget_id,
-                stringify_process_data(&proc_widget_state, &finalized_process_data),
+                stringify_process_data(
+                    proc_widget_state,
+                    &finalized_process_data,
+                    &app.pinned_pids,
+                ),
             );
             app.canvas_data
                 .finalized_process_data_map
@@ -429,6 +715,61 @@ fn update_final_process_list(app: &mut App, widget_id: u64) {
     }
 }
 
+/// Moves the selection to wherever the followed process ended up.  If it no longer exists, the
+/// selection is left alone and we stop following it.
+fn follow_process(
+    proc_widget_state: &mut app::ProcWidgetState, finalized_process_data: &[ConvertedProcessData],
+    single_process_data: &[ConvertedProcessData],
+) {
+    let followed_process = match &proc_widget_state.followed_process {
+        Some(followed_process) => followed_process,
+        None => return,
+    };
+
+    let position = find_process_position(followed_process, finalized_process_data);
+    let exists = position.is_some()
+        || match followed_process {
+            app::FollowedProcess::Pid(pid) => single_process_data
+                .iter()
+                .any(|process| process.pid == *pid),
+            app::FollowedProcess::Group(name) => single_process_data.iter().any(|process| {
+                process.name == *name || process.command == *name || process.container == *name
+            }),
+        };
+
+    if let Some(position) = position {
+        move_process_selection(proc_widget_state, position);
+    } else if !exists {
+        proc_widget_state.followed_process = None;
+        proc_widget_state.follow_lost_instant = Some(Instant::now());
+    }
+}
+
+/// Finds where a process (or group) is in the list, if it's there at all.  A PID also matches
+/// the group containing it.
+fn find_process_position(
+    process: &app::FollowedProcess, finalized_process_data: &[ConvertedProcessData],
+) -> Option<usize> {
+    match process {
+        app::FollowedProcess::Pid(pid) => finalized_process_data
+            .iter()
+            .position(|process| process.pid == *pid || process.group_pids.contains(pid)),
+        app::FollowedProcess::Group(name) => finalized_process_data
+            .iter()
+            .position(|process| process.name == *name),
+    }
+}
+
+fn move_process_selection(proc_widget_state: &mut app::ProcWidgetState, position: usize) {
+    let scroll_state = &mut proc_widget_state.scroll_state;
+    scroll_state.scroll_direction = if position < scroll_state.current_scroll_position {
+        app::ScrollDirection::Up
+    } else {
+        app::ScrollDirection::Down
+    };
+    scroll_state.current_scroll_position = position;
+}
+
 fn sort_process_data(
     to_sort_vec: &mut Vec<ConvertedProcessData>, proc_widget_state: &app::ProcWidgetState,
 ) {
@@ -530,6 +871,68 @@ fn sort_process_data(
                 )
             });
         }
+        ProcessSorting::FdCount => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.fd_count,
+                    b.fd_count,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::Nice => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.nice,
+                    b.nice,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::ThreadCount => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.thread_count,
+                    b.thread_count,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::StartTime => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.start_time_secs,
+                    b.start_time_secs,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::Age => {
+            // Older processes started earlier.
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.start_time_secs.map(std::cmp::Reverse),
+                    b.start_time_secs.map(std::cmp::Reverse),
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::CpuTime => {
+            to_sort_vec.sort_by(|a, b| {
+                utils::gen_util::get_ordering(
+                    a.total_cpu_time,
+                    b.total_cpu_time,
+                    proc_widget_state.is_process_sort_descending,
+                )
+            });
+        }
+        ProcessSorting::User => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                &a.user.to_lowercase(),
+                &b.user.to_lowercase(),
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
         ProcessSorting::State => to_sort_vec.sort_by(|a, b| {
             utils::gen_util::get_ordering(
                 &a.process_state.to_lowercase(),
@@ -537,6 +940,13 @@ fn sort_process_data(
                 proc_widget_state.is_process_sort_descending,
             )
         }),
+        ProcessSorting::Container => to_sort_vec.sort_by(|a, b| {
+            utils::gen_util::get_ordering(
+                &a.container.to_lowercase(),
+                &b.container.to_lowercase(),
+                proc_widget_state.is_process_sort_descending,
+            )
+        }),
         ProcessSorting::Count => {
             if proc_widget_state.is_grouped {
                 to_sort_vec.sort_by(|a, b| {
@@ -584,6 +994,31 @@ pub fn create_input_thread(
     });
 }
 
+/// Collects all data once and prints it to stdout as JSON, for use with `--json`.
+pub fn print_json_snapshot(app_config_fields: &app::AppConfigFields) -> error::Result<()> {
+    let mut data_state = data_harvester::DataCollector::default();
+    data_state.set_collected_data(UsedWidgets::all());
+    data_state.set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
+    data_state.set_show_average_cpu(app_config_fields.show_average_cpu);
+    data_state.set_show_fans(true);
+    data_state.set_show_zfs(app_config_fields.show_zfs);
+    data_state.set_show_zram(app_config_fields.show_zram);
+    data_state.set_hide_kernel_threads(app_config_fields.hide_kernel_threads);
+    data_state.set_net_filter(app_config_fields.net_filter.clone());
+    data_state.set_docker_socket(app_config_fields.docker_socket.clone());
+
+    // The first collection only sets up the baselines for rates, so we need a second one.
+    data_state.init();
+    futures::executor::block_on(data_state.update_data());
+
+    let stdout = stdout();
+    let mut stdout = stdout.lock();
+    serde_json::to_writer(&mut stdout, &data_state.data)?;
+    writeln!(stdout)?;
+
+    Ok(())
+}
+
 pub fn create_collection_thread(
     sender: std::sync::mpsc::Sender<
         BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
@@ -591,17 +1026,29 @@ pub fn create_collection_thread(
     reset_receiver: std::sync::mpsc::Receiver<CollectionThreadEvent>,
     app_config_fields: &app::AppConfigFields, used_widget_set: UsedWidgets,
 ) {
-    let temp_type = app_config_fields.temperature_type.clone();
     let use_current_cpu_total = app_config_fields.use_current_cpu_total;
     let show_average_cpu = app_config_fields.show_average_cpu;
+    let show_fans = app_config_fields.show_fans;
+    let show_zfs = app_config_fields.show_zfs;
+    let show_zram = app_config_fields.show_zram;
+    let hide_kernel_threads = app_config_fields.hide_kernel_threads;
+    let collection_rates = app_config_fields.collection_rates;
+    let net_filter = app_config_fields.net_filter.clone();
+    let docker_socket = app_config_fields.docker_socket.clone();
     let update_rate_in_milliseconds = app_config_fields.update_rate_in_milliseconds;
 
     thread::spawn(move || {
         let mut data_state = data_harvester::DataCollector::default();
         data_state.set_collected_data(used_widget_set);
-        data_state.set_temperature_type(temp_type);
         data_state.set_use_current_cpu_total(use_current_cpu_total);
         data_state.set_show_average_cpu(show_average_cpu);
+        data_state.set_show_fans(show_fans);
+        data_state.set_show_zfs(show_zfs);
+        data_state.set_show_zram(show_zram);
+        data_state.set_hide_kernel_threads(hide_kernel_threads);
+        data_state.set_collection_rates(collection_rates);
+        data_state.set_net_filter(net_filter);
+        data_state.set_docker_socket(docker_socket);
 
         data_state.init();
         loop {
@@ -612,10 +1059,17 @@ pub fn create_collection_thread(
                         data_state.data.first_run_cleanup();
                     }
                     CollectionThreadEvent::UpdateConfig(app_config_fields) => {
-                        data_state.set_temperature_type(app_config_fields.temperature_type.clone());
                         data_state
                             .set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
-                        data_state.set_show_average_cpu(app_config_fields.show_average_cpu);
+                        // Whether the average is collected is fixed when the thread starts, as
+                        // the interface always wants it and hides it when converting instead.
+                        data_state.set_show_fans(app_config_fields.show_fans);
+                        data_state.set_show_zfs(app_config_fields.show_zfs);
+                        data_state.set_show_zram(app_config_fields.show_zram);
+                        data_state.set_hide_kernel_threads(app_config_fields.hide_kernel_threads);
+                        data_state.set_collection_rates(app_config_fields.collection_rates);
+                        data_state.set_net_filter(app_config_fields.net_filter.clone());
+                        data_state.set_docker_socket(app_config_fields.docker_socket.clone());
                     }
                     CollectionThreadEvent::UpdateUsedWidgets(used_widget_set) => {
                         data_state.set_collected_data(*used_widget_set);
@@ -3,13 +3,17 @@ use serde::{Deserialize, Serialize};
 use std::time::Instant;
 use std::{
     collections::{HashMap, HashSet},
+    net::{IpAddr, Ipv4Addr},
     path::PathBuf,
 };
 
 use crate::{
-    app::{layout_manager::*, *},
+    app::{data_harvester::CollectionRates, keybindings::KeybindingMap, layout_manager::*, *},
     constants::*,
-    utils::error::{self, BottomError},
+    utils::{
+        error::{self, BottomError},
+        exporters::influx::InfluxTarget,
+    },
 };
 
 use layout_options::*;
@@ -21,10 +25,17 @@ use anyhow::{Context, Result};
 #[derive(Clone, Default, Deserialize, Serialize)]
 pub struct Config {
     pub flags: Option<ConfigFlags>,
+    pub theme: Option<String>,
     pub colors: Option<ConfigColours>,
     pub row: Option<Vec<Row>>,
     pub disk_filter: Option<IgnoreList>,
     pub temp_filter: Option<IgnoreList>,
+    pub keybindings: Option<HashMap<String, String>>,
+    pub rates: Option<ConfigRates>,
+    pub network: Option<ConfigNetwork>,
+    pub temperature: Option<ConfigTemperature>,
+    pub disk: Option<ConfigDisk>,
+    pub process_groups: Option<Vec<ConfigProcessGroup>>,
 }
 
 #[derive(Clone, Default, Deserialize, Serialize)]
@@ -50,8 +61,26 @@ pub struct ConfigFlags {
     pub use_old_network_legend: Option<bool>,
     pub hide_table_gap: Option<bool>,
     pub battery: Option<bool>,
+    pub fans: Option<bool>,
+    pub sockets: Option<bool>,
+    pub zfs: Option<bool>,
+    pub zram: Option<bool>,
     pub disable_click: Option<bool>,
     pub no_write: Option<bool>,
+    pub show_cpu_freq: Option<bool>,
+    pub show_iowait: Option<bool>,
+    pub show_steal: Option<bool>,
+    pub show_thread_count: Option<bool>,
+    pub hide_kernel_threads: Option<bool>,
+    pub hide_idle: Option<bool>,
+    pub process_columns: Option<Vec<String>>,
+    pub net_filter: Option<Vec<String>>,
+    pub docker: Option<bool>,
+    pub docker_socket: Option<String>,
+    pub influx_host: Option<String>,
+    pub influx_db: Option<String>,
+    pub server_address: Option<String>,
+    pub disk_io_threshold: Option<u64>,
 }
 
 #[derive(Clone, Default, Deserialize, Serialize)]
@@ -62,6 +91,7 @@ pub struct ConfigColours {
     pub cpu_core_colors: Option<Vec<String>>,
     pub ram_color: Option<String>,
     pub swap_color: Option<String>,
+    pub arc_color: Option<String>,
     pub rx_color: Option<String>,
     pub tx_color: Option<String>,
     pub rx_total_color: Option<String>,
@@ -74,6 +104,80 @@ pub struct ConfigColours {
     pub widget_title_color: Option<String>,
     pub graph_color: Option<String>,
     pub battery_colors: Option<Vec<String>>,
+    pub high_battery_color: Option<String>,
+    pub medium_battery_color: Option<String>,
+    pub low_battery_color: Option<String>,
+}
+
+impl ConfigColours {
+    /// Fills in any colours that aren't set with the ones from `base`.
+    pub fn or(self, base: ConfigColours) -> ConfigColours {
+        ConfigColours {
+            table_header_color: self.table_header_color.or(base.table_header_color),
+            all_cpu_color: self.all_cpu_color.or(base.all_cpu_color),
+            avg_cpu_color: self.avg_cpu_color.or(base.avg_cpu_color),
+            cpu_core_colors: self.cpu_core_colors.or(base.cpu_core_colors),
+            ram_color: self.ram_color.or(base.ram_color),
+            swap_color: self.swap_color.or(base.swap_color),
+            arc_color: self.arc_color.or(base.arc_color),
+            rx_color: self.rx_color.or(base.rx_color),
+            tx_color: self.tx_color.or(base.tx_color),
+            rx_total_color: self.rx_total_color.or(base.rx_total_color),
+            tx_total_color: self.tx_total_color.or(base.tx_total_color),
+            border_color: self.border_color.or(base.border_color),
+            highlighted_border_color: self
+                .highlighted_border_color
+                .or(base.highlighted_border_color),
+            text_color: self.text_color.or(base.text_color),
+            selected_text_color: self.selected_text_color.or(base.selected_text_color),
+            selected_bg_color: self.selected_bg_color.or(base.selected_bg_color),
+            widget_title_color: self.widget_title_color.or(base.widget_title_color),
+            graph_color: self.graph_color.or(base.graph_color),
+            battery_colors: self.battery_colors.or(base.battery_colors),
+            high_battery_color: self.high_battery_color.or(base.high_battery_color),
+            medium_battery_color: self.medium_battery_color.or(base.medium_battery_color),
+            low_battery_color: self.low_battery_color.or(base.low_battery_color),
+        }
+    }
+}
+
+/// How often each kind of data is collected, in milliseconds.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ConfigRates {
+    pub cpu_ms: Option<u64>,
+    pub memory_ms: Option<u64>,
+    pub disk_ms: Option<u64>,
+    pub processes_ms: Option<u64>,
+    pub network_ms: Option<u64>,
+    pub temperature_ms: Option<u64>,
+}
+
+/// Thresholds drawn as lines on the network graph, in bytes per second.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ConfigNetwork {
+    pub rx_threshold_bps: Option<u64>,
+    pub tx_threshold_bps: Option<u64>,
+}
+
+/// Temperatures at which temperature rows are coloured, in Celsius.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ConfigTemperature {
+    pub high_threshold_celsius: Option<f32>,
+    pub critical_threshold_celsius: Option<f32>,
+}
+
+/// Percentages of used space at which the disk usage bar changes colour.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ConfigDisk {
+    pub high_usage_percent: Option<f64>,
+    pub critical_usage_percent: Option<f64>,
+}
+
+/// Groups processes whose names match `regex` under `name` when grouping by name.
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct ConfigProcessGroup {
+    pub regex: String,
+    pub name: String,
 }
 
 #[derive(Clone, Default, Deserialize, Serialize)]
@@ -97,9 +201,12 @@ pub fn build_app(
 
     // For processes
     let is_grouped = get_app_grouping(matches, config);
+    let is_hiding_idle = get_hide_idle(matches, config);
     let is_case_sensitive = get_app_case_sensitive(matches, config);
     let is_match_whole_word = get_app_match_whole_word(matches, config);
     let is_use_regex = get_app_use_regex(matches, config);
+    let process_columns = get_process_columns(matches, config)
+        .context("Update 'process_columns' in your config file.")?;
 
     let mut widget_map = HashMap::new();
     let mut cpu_state_map: HashMap<u64, CpuWidgetState> = HashMap::new();
@@ -109,6 +216,7 @@ pub fn build_app(
     let mut temp_state_map: HashMap<u64, TempWidgetState> = HashMap::new();
     let mut disk_state_map: HashMap<u64, DiskWidgetState> = HashMap::new();
     let mut battery_state_map: HashMap<u64, BatteryWidgetState> = HashMap::new();
+    let mut gpu_state_map: HashMap<u64, GpuWidgetState> = HashMap::new();
 
     let autohide_timer = if autohide_time {
         Some(Instant::now())
@@ -186,6 +294,8 @@ pub fn build_app(
                                     is_match_whole_word,
                                     is_use_regex,
                                     is_grouped,
+                                    is_hiding_idle,
+                                    process_columns.as_deref(),
                                 ),
                             );
                         }
@@ -199,6 +309,12 @@ pub fn build_app(
                             battery_state_map
                                 .insert(widget.widget_id, BatteryWidgetState::default());
                         }
+                        Gpu => {
+                            gpu_state_map.insert(
+                                widget.widget_id,
+                                GpuWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
                         _ => {}
                     }
                 }
@@ -237,6 +353,14 @@ pub fn build_app(
         temperature_type: get_temperature(matches, config)
             .context("Update 'temperature_type' in your config file.")?,
         show_average_cpu: get_show_average_cpu(matches, config),
+        show_cpu_freq: get_show_cpu_freq(matches, config),
+        show_iowait: get_show_iowait(matches, config),
+        show_steal: get_show_steal(matches, config),
+        show_fans: get_use_fans(matches, config),
+        // The config file values are booleans, so only the arguments can be invalid.
+        show_zfs: get_show_zfs(matches, config).context("Update the --zfs argument.")?,
+        show_zram: get_show_zram(matches, config).context("Update the --zram argument.")?,
+        hide_kernel_threads: get_hide_kernel_threads(matches, config),
         use_dot: get_use_dot(matches, config),
         left_legend: get_use_left_legend(matches, config),
         use_current_cpu_total: get_use_current_cpu_total(matches, config),
@@ -254,6 +378,23 @@ pub fn build_app(
         },
         disable_click: get_disable_click(matches, config),
         no_write: get_no_write(matches, config),
+        collection_rates: get_collection_rates(&config.rates)
+            .context("Update 'rates' in your config file.")?,
+        net_filter: get_net_filter(matches, config)
+            .context("Update 'net_filter' in your config file.")?,
+        docker_socket: get_docker_socket(matches, config),
+        influx_target: get_influx_target(matches, config)
+            .context("Update 'influx_host' and 'influx_db' in your config file.")?,
+        disk_io_threshold: get_disk_io_threshold(matches, config)
+            .context("Update 'disk_io_threshold' in your config file.")?,
+        network_thresholds: get_network_thresholds(&config.network)
+            .context("Update 'network' in your config file.")?,
+        temperature_thresholds: get_temperature_thresholds(&config.temperature)
+            .context("Update 'temperature' in your config file.")?,
+        disk_usage_thresholds: get_disk_usage_thresholds(&config.disk)
+            .context("Update 'disk' in your config file.")?,
+        process_groups: get_process_groups(&config.process_groups)
+            .context("Update 'process_groups' in your config file.")?,
     };
 
     let used_widgets = UsedWidgets {
@@ -264,12 +405,16 @@ pub fn build_app(
         use_disk: used_widget_set.get(&Disk).is_some(),
         use_temp: used_widget_set.get(&Temp).is_some(),
         use_battery: used_widget_set.get(&Battery).is_some(),
+        use_gpu: used_widget_set.contains(&Gpu),
+        use_sockets: used_widget_set.contains(&Sockets),
     };
 
     let disk_filter =
         get_ignore_list(&config.disk_filter).context("Update 'disk_filter' in your config file")?;
     let temp_filter =
         get_ignore_list(&config.temp_filter).context("Update 'temp_filter' in your config file")?;
+    let key_bindings = get_key_bindings(&config.keybindings)
+        .context("Update 'keybindings' in your config file")?;
 
     Ok(App::builder()
         .app_config_fields(app_config_fields)
@@ -280,6 +425,7 @@ pub fn build_app(
         .disk_state(DiskState::init(disk_state_map))
         .temp_state(TempState::init(temp_state_map))
         .battery_state(BatteryState::init(battery_state_map))
+        .gpu_state(GpuState::init(gpu_state_map))
         .basic_table_widget_state(basic_table_widget_state)
         .current_widget(widget_map.get(&initial_widget_id).unwrap().clone()) // FIXME: [UNWRAP] - many of the unwraps are fine (like this one) but do a once-over and/or switch to expect?
         .widget_map(widget_map)
@@ -288,6 +434,7 @@ pub fn build_app(
             disk_filter,
             temp_filter,
         })
+        .key_bindings(key_bindings)
         .config(config.clone())
         .config_path(config_path)
         .build())
@@ -306,7 +453,7 @@ pub fn get_widget_layout(
 
         BottomLayout::init_basic_default(get_use_battery(matches, config))
     } else {
-        let ref_row: Vec<Row>; // Required to handle reference
+        let mut ref_row: Vec<Row>; // Required to handle reference
         let rows = match &config.row {
             Some(r) => r,
             None => {
@@ -318,6 +465,22 @@ pub fn get_widget_layout(
                 })?
                 .row
                 .unwrap();
+
+                if get_use_sockets(matches, config) {
+                    // Stack the socket summary below the temperature and disk widgets.
+                    if let Some(RowChildren::Col { child, .. }) = ref_row
+                        .get_mut(1)
+                        .and_then(|row| row.child.as_mut())
+                        .and_then(|children| children.get_mut(1))
+                    {
+                        child.push(FinalWidget {
+                            ratio: None,
+                            widget_type: "sockets".to_string(),
+                            default: None,
+                        });
+                    }
+                }
+
                 &ref_row
             }
         };
@@ -501,17 +664,17 @@ fn get_default_time_value(
     Ok(default_time as u64)
 }
 
-fn get_time_interval(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<u64> {
+/// Returns the fixed amount to zoom by, or `None` if none was set and zooming should halve or
+/// double the time window instead.
+fn get_time_interval(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<Option<u64>> {
     let time_interval = if let Some(time_interval) = matches.value_of("time_delta") {
         time_interval.parse::<u128>()?
-    } else if let Some(flags) = &config.flags {
-        if let Some(time_interval) = flags.time_delta {
-            time_interval as u128
-        } else {
-            TIME_CHANGE_MILLISECONDS as u128
-        }
+    } else if let Some(time_interval) = config.flags.as_ref().and_then(|flags| flags.time_delta) {
+        time_interval as u128
     } else {
-        TIME_CHANGE_MILLISECONDS as u128
+        return Ok(None);
     };
 
     if time_interval < 1000 {
@@ -525,7 +688,7 @@ fn get_time_interval(matches: &clap::ArgMatches<'static>, config: &Config) -> er
         )));
     }
 
-    Ok(time_interval as u64)
+    Ok(Some(time_interval as u64))
 }
 
 pub fn get_app_grouping(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
@@ -539,6 +702,17 @@ pub fn get_app_grouping(matches: &clap::ArgMatches<'static>, config: &Config) ->
     false
 }
 
+fn get_hide_idle(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("hide_idle") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(hide_idle) = flags.hide_idle {
+            return hide_idle;
+        }
+    }
+    false
+}
+
 pub fn get_app_case_sensitive(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("case_sensitive") {
         return true;
@@ -650,6 +824,278 @@ fn get_default_widget_and_count(
     }
 }
 
+/// Returns the process columns to show, if they were set.  Some names cover a pair of columns
+/// that are switched between in-app, like the PID and the group count.
+fn get_process_columns(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<Option<Vec<data_harvester::processes::ProcessSorting>>> {
+    use data_harvester::processes::ProcessSorting::*;
+
+    let column_names: Vec<String> =
+        if let Some(process_columns) = matches.value_of("process_columns") {
+            process_columns
+                .split(',')
+                .map(|name| name.trim().to_string())
+                .collect()
+        } else if let Some(process_columns) = config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.process_columns.as_ref())
+        {
+            process_columns.clone()
+        } else if get_show_thread_count(matches, config) {
+            // Show the default columns, plus the thread count right after the FD count.
+            let mut shown_columns = ProcColumn::default().ordered_columns;
+            let thread_count_index = shown_columns
+                .iter()
+                .position(|column| *column == FdCount)
+                .map_or(shown_columns.len(), |index| index + 1);
+            shown_columns.insert(thread_count_index, ThreadCount);
+            return Ok(Some(shown_columns));
+        } else {
+            return Ok(None);
+        };
+
+    let mut shown_columns = Vec::new();
+    for name in &column_names {
+        let columns: &[data_harvester::processes::ProcessSorting] =
+            match name.to_lowercase().as_str() {
+                "pid" | "count" => &[Count, Pid],
+                "name" | "command" => &[ProcessName, Command],
+                "cpu" => &[CpuPercent],
+                "mem" => &[Mem, MemPercent],
+                "time" => &[CpuTime],
+                "started" => &[StartTime],
+                "age" => &[Age],
+                "read" => &[ReadPerSecond],
+                "write" => &[WritePerSecond],
+                "total_read" => &[TotalRead],
+                "total_write" => &[TotalWrite],
+                "fds" => &[FdCount],
+                "threads" => &[ThreadCount],
+                "nice" => &[Nice],
+                "user" => &[User],
+                "state" => &[State],
+                "container" => &[Container],
+                _ => {
+                    return Err(BottomError::ConfigError(format!(
+                        "\"{}\" is an invalid process column",
+                        name
+                    )))
+                }
+            };
+
+        if shown_columns.contains(&columns[0]) {
+            return Err(BottomError::ConfigError(format!(
+                "the process column \"{}\" is listed more than once",
+                name
+            )));
+        }
+        shown_columns.extend_from_slice(columns);
+    }
+
+    if shown_columns.is_empty() {
+        return Err(BottomError::ConfigError(
+            "at least one process column must be shown".to_string(),
+        ));
+    }
+    if get_show_thread_count(matches, config) && !shown_columns.contains(&ThreadCount) {
+        shown_columns.push(ThreadCount);
+    }
+
+    Ok(Some(shown_columns))
+}
+
+/// Returns the graph colours given via `--colors`, if any.
+pub fn get_palette(matches: &clap::ArgMatches<'static>) -> Option<Vec<String>> {
+    matches.value_of("colors").map(|colours| {
+        colours
+            .split(',')
+            .map(|colour| colour.trim().to_string())
+            .filter(|colour| !colour.is_empty())
+            .collect()
+    })
+}
+
+fn get_net_filter(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<Option<globset::GlobSet>> {
+    let patterns: Vec<String> = if let Some(net_filter) = matches.value_of("net_filter") {
+        net_filter
+            .split(',')
+            .map(|pattern| pattern.trim().to_string())
+            .filter(|pattern| !pattern.is_empty())
+            .collect()
+    } else if let Some(net_filter) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.net_filter.as_ref())
+    {
+        net_filter.clone()
+    } else {
+        return Ok(None);
+    };
+
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in &patterns {
+        builder.add(globset::Glob::new(pattern).map_err(|_| {
+            BottomError::ConfigError(format!(
+                "\"{}\" is an invalid network interface pattern",
+                pattern
+            ))
+        })?);
+    }
+
+    Ok(Some(builder.build().map_err(|err| {
+        BottomError::ConfigError(format!("could not build the network filter: {}", err))
+    })?))
+}
+
+fn get_docker_socket(matches: &clap::ArgMatches<'static>, config: &Config) -> Option<String> {
+    let flags = config.flags.as_ref();
+    if matches.is_present("docker") || flags.and_then(|flags| flags.docker).unwrap_or(false) {
+        Some(
+            flags
+                .and_then(|flags| flags.docker_socket.clone())
+                .unwrap_or_else(|| DEFAULT_DOCKER_SOCKET.to_string()),
+        )
+    } else {
+        None
+    }
+}
+
+/// Returns where to write metrics to InfluxDB, if both a host and a database are set.  The host
+/// may leave out the "http://" scheme and the port, which defaults to 8086.
+fn get_influx_target(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<Option<InfluxTarget>> {
+    let flags = config.flags.as_ref();
+    let host = matches
+        .value_of("influx_host")
+        .map(str::to_string)
+        .or_else(|| flags.and_then(|flags| flags.influx_host.clone()));
+    let database = matches
+        .value_of("influx_db")
+        .map(str::to_string)
+        .or_else(|| flags.and_then(|flags| flags.influx_db.clone()));
+
+    let (host, database) = match (host, database) {
+        (None, None) => return Ok(None),
+        (Some(host), Some(database)) if !database.is_empty() => (host, database),
+        _ => {
+            return Err(BottomError::ConfigError(
+                "set both an InfluxDB host and database to write metrics.".to_string(),
+            ))
+        }
+    };
+
+    if host.starts_with("https://") {
+        return Err(BottomError::ConfigError(
+            "InfluxDB can only be written to over plain HTTP.".to_string(),
+        ));
+    }
+    let address = host
+        .strip_prefix("http://")
+        .unwrap_or(&host)
+        .trim_end_matches('/');
+    if address.is_empty() || address.contains('/') {
+        return Err(BottomError::ConfigError(format!(
+            "\"{}\" is an invalid InfluxDB host, use a form like \"http://localhost:8086\".",
+            host
+        )));
+    }
+
+    // Anything after the last colon is the port, unless it closes an IPv6 address.
+    let address = match address.rfind(':') {
+        Some(colon) if !address[colon..].contains(']') => {
+            if address[colon + 1..].parse::<u16>().is_err() {
+                return Err(BottomError::ConfigError(format!(
+                    "\"{}\" has an invalid InfluxDB port.",
+                    host
+                )));
+            }
+            address.to_string()
+        }
+        _ => format!("{}:{}", address, DEFAULT_INFLUX_PORT),
+    };
+
+    Ok(Some(InfluxTarget { address, database }))
+}
+
+/// Returns the combined I/O rate, in bytes per second, above which disks are highlighted.  It is
+/// set in megabytes per second.
+fn get_disk_io_threshold(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<Option<u64>> {
+    let threshold = if let Some(threshold) = matches.value_of("disk_io_threshold") {
+        threshold.parse::<u64>()?
+    } else if let Some(threshold) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.disk_io_threshold)
+    {
+        threshold
+    } else {
+        return Ok(None);
+    };
+
+    if threshold == 0 {
+        return Err(BottomError::ConfigError(
+            "set your disk I/O threshold to be at least 1 MB/s.".to_string(),
+        ));
+    }
+
+    Ok(Some(threshold.saturating_mul(1_000_000)))
+}
+
+fn get_show_iowait(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("show_iowait") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(show_iowait) = flags.show_iowait {
+            return show_iowait;
+        }
+    }
+    false
+}
+
+fn get_show_steal(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("show_steal") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(show_steal) = flags.show_steal {
+            return show_steal;
+        }
+    }
+    false
+}
+
+fn get_show_cpu_freq(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("show_cpu_freq") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(show_cpu_freq) = flags.show_cpu_freq {
+            return show_cpu_freq;
+        }
+    }
+    false
+}
+
+fn get_show_thread_count(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("show_thread_count") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(show_thread_count) = flags.show_thread_count {
+            return show_thread_count;
+        }
+    }
+    false
+}
+
 fn get_disable_click(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("disable_click") {
         return true;
@@ -694,6 +1140,129 @@ pub fn get_use_battery(matches: &clap::ArgMatches<'static>, config: &Config) ->
     false
 }
 
+pub fn get_use_sockets(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("sockets") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(sockets) = flags.sockets {
+            return sockets;
+        }
+    }
+    false
+}
+
+pub fn get_use_fans(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("fans") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(fans) = flags.fans {
+            return fans;
+        }
+    }
+    false
+}
+
+/// Whether to show the ZFS ARC.  Unless forced on or off, it is shown if ZFS is loaded.
+pub fn get_show_zfs(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<bool> {
+    if let Some(zfs) = matches.value_of("zfs") {
+        return match zfs.to_lowercase().as_str() {
+            "on" => Ok(true),
+            "off" => Ok(false),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid ZFS setting, use \"on\" or \"off\"",
+                zfs
+            ))),
+        };
+    } else if let Some(flags) = &config.flags {
+        if let Some(zfs) = flags.zfs {
+            return Ok(zfs);
+        }
+    }
+    Ok(data_harvester::zfs::is_zfs_present())
+}
+
+/// Whether to read zram statistics.  Unless forced on or off, they are read if there are zram
+/// devices.
+pub fn get_show_zram(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<bool> {
+    if let Some(zram) = matches.value_of("zram") {
+        return match zram.to_lowercase().as_str() {
+            "on" => Ok(true),
+            "off" => Ok(false),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{}\" is an invalid zram setting, use \"on\" or \"off\"",
+                zram
+            ))),
+        };
+    } else if let Some(flags) = &config.flags {
+        if let Some(zram) = flags.zram {
+            return Ok(zram);
+        }
+    }
+    Ok(data_harvester::mem::is_zram_present())
+}
+
+pub fn get_server_port(matches: &clap::ArgMatches<'static>) -> error::Result<u16> {
+    if let Some(port) = matches.value_of("port") {
+        match port.parse::<u16>() {
+            Ok(port) if port > 0 => Ok(port),
+            _ => Err(BottomError::ConfigError(
+                "set your port to be between 1 and 65535.".to_string(),
+            )),
+        }
+    } else {
+        Ok(DEFAULT_SERVER_PORT)
+    }
+}
+
+/// Returns the address to serve metrics on, which is only reachable locally by default.
+pub fn get_server_address(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<IpAddr> {
+    let address = matches.value_of("bind").map(str::to_string).or_else(|| {
+        config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.server_address.clone())
+    });
+
+    match address {
+        Some(address) => address.parse::<IpAddr>().map_err(|_| {
+            BottomError::ConfigError(format!(
+                "\"{}\" is an invalid address to serve on, use an IP address like 127.0.0.1",
+                address
+            ))
+        }),
+        None => Ok(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+    }
+}
+
+/// Returns the port to serve Prometheus metrics on, if enabled.
+pub fn get_prometheus_port(matches: &clap::ArgMatches<'static>) -> error::Result<Option<u16>> {
+    if let Some(port) = matches.value_of("prometheus") {
+        match port.parse::<u16>() {
+            Ok(port) if port > 0 => Ok(Some(port)),
+            _ => Err(BottomError::ConfigError(
+                "set your Prometheus port to be between 1 and 65535.".to_string(),
+            )),
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn get_hide_kernel_threads(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    } else if matches.is_present("hide_kernel_threads") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(hide_kernel_threads) = flags.hide_kernel_threads {
+            return hide_kernel_threads;
+        }
+    }
+    false
+}
+
 pub fn get_no_write(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
     if matches.is_present("no_write") {
         return true;
@@ -746,3 +1315,154 @@ pub fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> error::Result<Option
         Ok(None)
     }
 }
+
+pub fn get_collection_rates(rates: &Option<ConfigRates>) -> error::Result<CollectionRates> {
+    if let Some(rates) = rates {
+        let collection_rates = CollectionRates {
+            cpu: rates.cpu_ms,
+            memory: rates.memory_ms,
+            disk: rates.disk_ms,
+            processes: rates.processes_ms,
+            network: rates.network_ms,
+            temperature: rates.temperature_ms,
+        };
+
+        let all_rates = [
+            collection_rates.cpu,
+            collection_rates.memory,
+            collection_rates.disk,
+            collection_rates.processes,
+            collection_rates.network,
+            collection_rates.temperature,
+        ];
+        if all_rates.iter().flatten().any(|rate| *rate < 250) {
+            return Err(BottomError::ConfigError(
+                "set your rates to be at least 250 milliseconds.".to_string(),
+            ));
+        }
+
+        Ok(collection_rates)
+    } else {
+        Ok(CollectionRates::default())
+    }
+}
+
+/// Returns the RX and TX thresholds drawn on the network graph, in bytes per second.
+pub fn get_network_thresholds(
+    network: &Option<ConfigNetwork>,
+) -> error::Result<(Option<u64>, Option<u64>)> {
+    if let Some(network) = network {
+        if network.rx_threshold_bps == Some(0) || network.tx_threshold_bps == Some(0) {
+            return Err(BottomError::ConfigError(
+                "set your network thresholds to be at least 1 byte per second.".to_string(),
+            ));
+        }
+
+        Ok((network.rx_threshold_bps, network.tx_threshold_bps))
+    } else {
+        Ok((None, None))
+    }
+}
+
+/// Returns the high and critical temperature thresholds, in Celsius.
+pub fn get_temperature_thresholds(
+    temperature: &Option<ConfigTemperature>,
+) -> error::Result<(f32, f32)> {
+    let (high, critical) = match temperature {
+        Some(temperature) => (
+            temperature
+                .high_threshold_celsius
+                .unwrap_or(DEFAULT_HIGH_TEMPERATURE_CELSIUS),
+            temperature
+                .critical_threshold_celsius
+                .unwrap_or(DEFAULT_CRITICAL_TEMPERATURE_CELSIUS),
+        ),
+        None => (
+            DEFAULT_HIGH_TEMPERATURE_CELSIUS,
+            DEFAULT_CRITICAL_TEMPERATURE_CELSIUS,
+        ),
+    };
+
+    if !high.is_finite() || !critical.is_finite() {
+        Err(BottomError::ConfigError(
+            "set your temperature thresholds to finite numbers.".to_string(),
+        ))
+    } else if high > critical {
+        Err(BottomError::ConfigError(
+            "set high_threshold_celsius to be at most critical_threshold_celsius.".to_string(),
+        ))
+    } else {
+        Ok((high, critical))
+    }
+}
+
+/// Returns the high and critical disk usage thresholds, in percent.
+pub fn get_disk_usage_thresholds(disk: &Option<ConfigDisk>) -> error::Result<(f64, f64)> {
+    let (high, critical) = match disk {
+        Some(disk) => (
+            disk.high_usage_percent
+                .unwrap_or(DEFAULT_HIGH_DISK_USAGE_PERCENT),
+            disk.critical_usage_percent
+                .unwrap_or(DEFAULT_CRITICAL_DISK_USAGE_PERCENT),
+        ),
+        None => (
+            DEFAULT_HIGH_DISK_USAGE_PERCENT,
+            DEFAULT_CRITICAL_DISK_USAGE_PERCENT,
+        ),
+    };
+
+    if !(0.0..=100.0).contains(&high) || !(0.0..=100.0).contains(&critical) {
+        Err(BottomError::ConfigError(
+            "set your disk usage thresholds to be between 0 and 100.".to_string(),
+        ))
+    } else if high > critical {
+        Err(BottomError::ConfigError(
+            "set high_usage_percent to be at most critical_usage_percent.".to_string(),
+        ))
+    } else {
+        Ok((high, critical))
+    }
+}
+
+pub fn get_process_groups(
+    process_groups: &Option<Vec<ConfigProcessGroup>>,
+) -> error::Result<Vec<ProcessGroupRule>> {
+    if let Some(process_groups) = process_groups {
+        process_groups
+            .iter()
+            .enumerate()
+            .map(|(index, process_group)| {
+                if process_group.name.is_empty() {
+                    return Err(BottomError::ConfigError(format!(
+                        "process_groups[{}].name must not be empty.",
+                        index
+                    )));
+                }
+
+                let regex = Regex::new(&process_group.regex).map_err(|err| {
+                    BottomError::ConfigError(format!(
+                        "process_groups[{}].regex \"{}\" is an invalid regex: {}",
+                        index, process_group.regex, err
+                    ))
+                })?;
+
+                Ok(ProcessGroupRule {
+                    regex,
+                    name: process_group.name.clone(),
+                })
+            })
+            .collect()
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+pub fn get_key_bindings(
+    keybindings: &Option<HashMap<String, String>>,
+) -> error::Result<KeybindingMap> {
+    if let Some(keybindings) = keybindings {
+        KeybindingMap::from_table(keybindings)
+    } else {
+        Ok(KeybindingMap::default())
+    }
+}
@@ -1,5 +1,5 @@
 use crate::app::layout_manager::*;
-use crate::error::Result;
+use crate::error::{BottomError, Result};
 use serde::{Deserialize, Serialize};
 
 /// Represents a row.  This has a length of some sort (optional) and a vector
@@ -19,7 +19,7 @@ impl Row {
     ) -> Result<BottomRow> {
         // TODO: In the future we want to also add percentages.
         // But for MVP, we aren't going to bother.
-        let row_ratio = self.ratio.unwrap_or(1);
+        let row_ratio = get_ratio(self.ratio)?;
         let mut children = Vec::new();
 
         *total_height_ratio += row_ratio;
@@ -30,7 +30,7 @@ impl Row {
                 match row_child {
                     RowChildren::Widget(widget) => {
                         *iter_id += 1;
-                        let width_ratio = widget.ratio.unwrap_or(1);
+                        let width_ratio = get_ratio(widget.ratio)?;
                         total_col_ratio += width_ratio;
                         let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
 
@@ -156,7 +156,13 @@ impl Row {
                         });
                     }
                     RowChildren::Col { ratio, child } => {
-                        let col_width_ratio = ratio.unwrap_or(1);
+                        let col_width_ratio = get_ratio(*ratio)?;
+                        if child.is_empty() {
+                            return Err(BottomError::ConfigError(
+                                "please have at least one widget under each '[[row.child]]' column."
+                                    .to_string(),
+                            ));
+                        }
                         total_col_ratio += col_width_ratio;
                         let mut total_col_row_ratio = 0;
                         let mut contains_proc = false;
@@ -166,7 +172,7 @@ impl Row {
                         for widget in child {
                             let widget_type = widget.widget_type.parse::<BottomWidgetType>()?;
                             *iter_id += 1;
-                            let col_row_height_ratio = widget.ratio.unwrap_or(1);
+                            let col_row_height_ratio = get_ratio(widget.ratio)?;
                             total_col_row_ratio += col_row_height_ratio;
 
                             if let Some(default_widget_type_val) = default_widget_type {
@@ -329,6 +335,18 @@ impl Row {
     }
 }
 
+/// Returns a layout ratio, defaulting to 1.  A ratio of 0 is rejected, as it would
+/// leave its parent with nothing to divide.
+fn get_ratio(ratio: Option<u32>) -> Result<u32> {
+    match ratio {
+        Some(0) => Err(BottomError::ConfigError(
+            "set your layout ratios to be greater than 0.".to_string(),
+        )),
+        Some(ratio) => Ok(ratio),
+        None => Ok(1),
+    }
+}
+
 /// Represents a child of a Row - either a Col (column) or a FinalWidget.
 ///
 /// A Col can also have an optional length and children.  We only allow columns
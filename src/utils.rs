@@ -1,3 +1,4 @@
 pub mod error;
+pub mod exporters;
 pub mod gen_util;
 pub mod logging;
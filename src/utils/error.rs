@@ -31,6 +31,12 @@ pub enum BottomError {
     /// An error to represent errors with querying.
     #[error("Query error, {0}")]
     QueryError(Cow<'static, str>),
+    /// An error when a PID search isn't a valid list.  Unlike other query errors, the search
+    /// doesn't fall back to matching names, as that would silently match nothing.
+    #[error(
+        "Query error, Invalid PID list, use PIDs and ranges like \"pid=1234,5678\" or \"pid=1000-2000\""
+    )]
+    InvalidPidList,
     /// An error that just signifies something minor went wrong; no message.
     #[error("Minor error.")]
     MinorError,
@@ -73,6 +79,12 @@ impl From<toml::de::Error> for BottomError {
     }
 }
 
+impl From<serde_json::Error> for BottomError {
+    fn from(err: serde_json::Error) -> Self {
+        BottomError::ConversionError(err.to_string())
+    }
+}
+
 impl From<fern::InitError> for BottomError {
     fn from(err: fern::InitError) -> Self {
         BottomError::FernError(err.to_string())
@@ -0,0 +1,4 @@
+//! Exporters that send collected data to external services as it comes in.
+
+pub mod influx;
+pub mod prometheus;
@@ -0,0 +1,318 @@
+//! Writes collected data to InfluxDB through its `/write` API, in the line protocol.  CPU,
+//! memory, disk, network, and temperature data each get their own measurement, and every point
+//! is tagged with the hostname.  Only plain HTTP is supported.
+
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Write as _},
+    net::{TcpStream, ToSocketAddrs},
+    sync::mpsc,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::app::data_harvester::Data;
+
+/// How many batches of points can wait to be written before newer ones are dropped.
+const MAX_QUEUED_WRITES: usize = 4;
+
+/// How long to wait on InfluxDB when connecting, writing, or reading the response.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where to write points to.
+#[derive(Clone, Debug)]
+pub struct InfluxTarget {
+    /// The host and port, like "localhost:8086".
+    pub address: String,
+    pub database: String,
+}
+
+#[derive(Clone, Copy)]
+enum FieldValue {
+    Float(f64),
+    Integer(u64),
+}
+
+/// Sends batches of points to a background thread, which writes them to InfluxDB.
+pub struct InfluxExporter {
+    sender: mpsc::SyncSender<String>,
+    host_tag: String,
+}
+
+impl InfluxExporter {
+    pub fn spawn(target: InfluxTarget) -> Self {
+        // A bounded channel, so that a slow or unreachable server only costs a few dropped
+        // batches rather than a growing backlog.
+        let (sender, receiver) = mpsc::sync_channel::<String>(MAX_QUEUED_WRITES);
+        thread::spawn(move || {
+            for body in receiver {
+                if let Err(err) = write_points(&target, &body) {
+                    debug!("Failed to write to InfluxDB: {}", err);
+                }
+            }
+        });
+
+        InfluxExporter {
+            sender,
+            host_tag: get_hostname(),
+        }
+    }
+
+    /// Queues the data to be written, unless too many earlier writes are still waiting.
+    pub fn export(&self, data: &Data) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+        let body = to_line_protocol(data, &self.host_tag, timestamp);
+        if body.is_empty() {
+            return;
+        }
+
+        if let Err(mpsc::TrySendError::Full(_)) = self.sender.try_send(body) {
+            debug!("InfluxDB is falling behind, dropping a write.");
+        }
+    }
+}
+
+fn to_line_protocol(data: &Data, host: &str, timestamp: u128) -> String {
+    let mut lines = String::new();
+
+    if let Some(cpu) = &data.cpu {
+        for core in cpu {
+            let name = match core.cpu_count {
+                Some(cpu_count) => format!("{}{}", core.cpu_prefix, cpu_count),
+                None => core.cpu_prefix.clone(),
+            }
+            .to_lowercase();
+            write_point(
+                &mut lines,
+                "cpu",
+                &[("host", host), ("cpu", &name)],
+                &[
+                    ("usage", FieldValue::Float(core.cpu_usage)),
+                    ("iowait", FieldValue::Float(core.iowait_percent)),
+                    ("steal", FieldValue::Float(core.steal_percent)),
+                    ("irq", FieldValue::Float(core.irq_percent)),
+                    ("softirq", FieldValue::Float(core.softirq_percent)),
+                ],
+                timestamp,
+            );
+        }
+    }
+
+    let mut memory_fields = Vec::new();
+    for (prefix, harvest) in &[("ram", &data.memory), ("swap", &data.swap)] {
+        if let Some(harvest) = harvest {
+            memory_fields.push((
+                format!("{}_used_mb", prefix),
+                FieldValue::Integer(harvest.mem_used_in_mb),
+            ));
+            memory_fields.push((
+                format!("{}_total_mb", prefix),
+                FieldValue::Integer(harvest.mem_total_in_mb),
+            ));
+            if let Some(cache_in_mb) = harvest.cache_in_mb {
+                memory_fields.push((
+                    format!("{}_cache_mb", prefix),
+                    FieldValue::Integer(cache_in_mb),
+                ));
+            }
+            if harvest.mem_total_in_mb > 0 {
+                memory_fields.push((
+                    format!("{}_used_percent", prefix),
+                    FieldValue::Float(
+                        harvest.mem_used_in_mb as f64 / harvest.mem_total_in_mb as f64 * 100.0,
+                    ),
+                ));
+            }
+        }
+    }
+    write_point(
+        &mut lines,
+        "memory",
+        &[("host", host)],
+        &memory_fields
+            .iter()
+            .map(|(key, value)| (key.as_str(), *value))
+            .collect::<Vec<_>>(),
+        timestamp,
+    );
+
+    if let Some(disks) = &data.disks {
+        for disk in disks {
+            let mut fields = vec![
+                ("used_bytes", FieldValue::Integer(disk.used_space)),
+                ("free_bytes", FieldValue::Integer(disk.free_space)),
+                ("total_bytes", FieldValue::Integer(disk.total_space)),
+            ];
+            // The counters are keyed by the last part of the device path, like "sda1".
+            if let Some(Some(io)) = data.io.as_ref().and_then(|io| {
+                disk.name
+                    .rsplit('/')
+                    .next()
+                    .and_then(|device| io.get(device))
+            }) {
+                fields.push(("read_bytes", FieldValue::Integer(io.read_bytes)));
+                fields.push(("write_bytes", FieldValue::Integer(io.write_bytes)));
+            }
+            write_point(
+                &mut lines,
+                "disk",
+                &[
+                    ("host", host),
+                    ("device", &disk.name),
+                    ("mount", &disk.mount_point),
+                ],
+                &fields,
+                timestamp,
+            );
+        }
+    }
+
+    if let Some(network) = &data.network {
+        write_point(
+            &mut lines,
+            "network",
+            &[("host", host)],
+            &[
+                ("rx_bytes_per_sec", FieldValue::Integer(network.rx)),
+                ("tx_bytes_per_sec", FieldValue::Integer(network.tx)),
+                ("rx_total_bytes", FieldValue::Integer(network.total_rx)),
+                ("tx_total_bytes", FieldValue::Integer(network.total_tx)),
+                (
+                    "rx_packets_per_sec",
+                    FieldValue::Float(network.rx_packets_per_sec),
+                ),
+                (
+                    "tx_packets_per_sec",
+                    FieldValue::Float(network.tx_packets_per_sec),
+                ),
+            ],
+            timestamp,
+        );
+    }
+
+    if let Some(temperature_sensors) = &data.temperature_sensors {
+        for sensor in temperature_sensors {
+            let name = match (&sensor.component_name, &sensor.component_label) {
+                (Some(name), Some(label)) => format!("{}: {}", name, label),
+                (None, Some(label)) => label.to_string(),
+                (Some(name), None) => name.to_string(),
+                (None, None) => String::default(),
+            };
+            write_point(
+                &mut lines,
+                "temperature",
+                &[("host", host), ("sensor", &name)],
+                &[("celsius", FieldValue::Float(f64::from(sensor.temperature)))],
+                timestamp,
+            );
+        }
+    }
+
+    lines
+}
+
+/// Appends a point to `lines`.  Empty tags and fields that aren't finite numbers are left out,
+/// as InfluxDB rejects them, and a point left with no fields is skipped entirely.
+fn write_point(
+    lines: &mut String, measurement: &str, tags: &[(&str, &str)], fields: &[(&str, FieldValue)],
+    timestamp: u128,
+) {
+    let fields = fields
+        .iter()
+        .filter_map(|(key, value)| match value {
+            FieldValue::Float(value) if value.is_finite() => {
+                Some(format!("{}={}", escape(key), value))
+            }
+            FieldValue::Float(_) => None,
+            FieldValue::Integer(value) => Some(format!("{}={}i", escape(key), value)),
+        })
+        .collect::<Vec<_>>();
+    if fields.is_empty() {
+        return;
+    }
+
+    lines.push_str(measurement);
+    for (key, value) in tags.iter().filter(|(_, value)| !value.is_empty()) {
+        let _ = write!(lines, ",{}={}", escape(key), escape(value));
+    }
+    let _ = writeln!(lines, " {} {}", fields.join(","), timestamp);
+}
+
+/// Escapes the characters that separate keys, values, and tags in the line protocol.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c == ',' || c == '=' || c == ' ' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Percent-encodes anything besides unreserved characters, for use in a query string.
+fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{:02X}", byte);
+        }
+    }
+    encoded
+}
+
+fn write_points(target: &InfluxTarget, body: &str) -> io::Result<()> {
+    let address = target.address.to_socket_addrs()?.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("could not resolve {}", target.address),
+        )
+    })?;
+    let mut stream = TcpStream::connect_timeout(&address, WRITE_TIMEOUT)?;
+    stream.set_read_timeout(Some(WRITE_TIMEOUT))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    write!(
+        stream,
+        "POST /write?db={} HTTP/1.1\r\nHost: {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        encode_query_value(&target.database),
+        target.address,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+
+    // Successful writes get a 204 No Content.
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+    match status_line.split_whitespace().nth(1) {
+        Some(status) if status.starts_with('2') => Ok(()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unexpected response \"{}\"", status_line.trim()),
+        )),
+    }
+}
+
+fn get_hostname() -> String {
+    #[cfg(unix)]
+    {
+        let mut buffer = [0_u8; 256];
+        if unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len()) } == 0
+        {
+            let len = buffer.iter().position(|byte| *byte == 0).unwrap_or(0);
+            if let Ok(hostname) = std::str::from_utf8(&buffer[..len]) {
+                if !hostname.is_empty() {
+                    return hostname.to_string();
+                }
+            }
+        }
+    }
+
+    std::env::var("COMPUTERNAME").unwrap_or_else(|_| "localhost".to_string())
+}
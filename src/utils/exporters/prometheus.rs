@@ -0,0 +1,433 @@
+//! Serves the collected data as Prometheus metrics over HTTP, alongside the interface.  Metrics
+//! are rendered from the same [`DataCollection`] the widgets draw from, so they only cover what
+//! the current layout collects.
+
+use std::{
+    fmt::Write as _,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, RwLock},
+    thread,
+};
+
+use crate::{
+    app::{
+        data_farmer::DataCollection,
+        data_harvester::{cpu::CpuData, disks::DiskHarvest},
+    },
+    utils::error::{self, BottomError},
+};
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+enum MetricType {
+    Gauge,
+    Counter,
+}
+
+/// A metric family, as described in its `# HELP` and `# TYPE` lines.
+struct Metric {
+    name: &'static str,
+    help: &'static str,
+    metric_type: MetricType,
+}
+
+const fn gauge(name: &'static str, help: &'static str) -> Metric {
+    Metric {
+        name,
+        help,
+        metric_type: MetricType::Gauge,
+    }
+}
+
+const fn counter(name: &'static str, help: &'static str) -> Metric {
+    Metric {
+        name,
+        help,
+        metric_type: MetricType::Counter,
+    }
+}
+
+const CPU_USAGE: Metric = gauge(
+    "bottom_cpu_usage_percent",
+    "CPU usage of each core, and of all cores on average.",
+);
+const CPU_IOWAIT: Metric = gauge(
+    "bottom_cpu_iowait_percent",
+    "Share of each core's time spent waiting on I/O.",
+);
+const CPU_STEAL: Metric = gauge(
+    "bottom_cpu_steal_percent",
+    "Share of each core's time taken by the hypervisor.",
+);
+const CPU_IRQ: Metric = gauge(
+    "bottom_cpu_irq_percent",
+    "Share of each core's time spent servicing hardware interrupts.",
+);
+const CPU_SOFTIRQ: Metric = gauge(
+    "bottom_cpu_softirq_percent",
+    "Share of each core's time spent servicing software interrupts.",
+);
+const CPU_FREQUENCY: Metric = gauge(
+    "bottom_cpu_frequency_mhz",
+    "Current frequency of each core.",
+);
+const LOAD_AVERAGE: Metric = gauge(
+    "bottom_load_average",
+    "System load average over 1, 5, and 15 minutes.",
+);
+const MEMORY_USED: Metric = gauge("bottom_memory_used_bytes", "RAM in use.");
+const MEMORY_TOTAL: Metric = gauge("bottom_memory_total_bytes", "Total RAM.");
+const MEMORY_CACHE: Metric = gauge(
+    "bottom_memory_cache_bytes",
+    "RAM used for buffers and the page cache.",
+);
+const SWAP_USED: Metric = gauge("bottom_swap_used_bytes", "Swap in use.");
+const SWAP_TOTAL: Metric = gauge("bottom_swap_total_bytes", "Total swap.");
+const NETWORK_RECEIVE_RATE: Metric = gauge(
+    "bottom_network_receive_bytes_per_second",
+    "Rate of data received over all shown interfaces.",
+);
+const NETWORK_TRANSMIT_RATE: Metric = gauge(
+    "bottom_network_transmit_bytes_per_second",
+    "Rate of data transmitted over all shown interfaces.",
+);
+const NETWORK_RECEIVED: Metric = counter(
+    "bottom_network_received_bytes_total",
+    "Data received over all shown interfaces.",
+);
+const NETWORK_TRANSMITTED: Metric = counter(
+    "bottom_network_transmitted_bytes_total",
+    "Data transmitted over all shown interfaces.",
+);
+const DISK_USED: Metric = gauge("bottom_disk_used_bytes", "Space in use on each disk.");
+const DISK_FREE: Metric = gauge("bottom_disk_free_bytes", "Space free on each disk.");
+const DISK_TOTAL: Metric = gauge("bottom_disk_total_bytes", "Total space on each disk.");
+const DISK_READ_RATE: Metric = gauge(
+    "bottom_disk_read_bytes_per_second",
+    "Rate of data read from each disk.",
+);
+const DISK_WRITE_RATE: Metric = gauge(
+    "bottom_disk_write_bytes_per_second",
+    "Rate of data written to each disk.",
+);
+const TEMPERATURE: Metric = gauge("bottom_temperature_celsius", "Temperature of each sensor.");
+const FAN_SPEED: Metric = gauge("bottom_fan_speed_rpm", "Speed of each fan.");
+const BATTERY_CHARGE: Metric = gauge("bottom_battery_charge_percent", "Charge of each battery.");
+const BATTERY_POWER: Metric = gauge(
+    "bottom_battery_power_watts",
+    "Power drawn from or supplied to each battery.",
+);
+const SOCKETS: Metric = gauge("bottom_sockets", "Open sockets of each kind.");
+
+/// Serves the latest rendered metrics on `/metrics` from a background thread.
+pub struct PrometheusExporter {
+    metrics: Arc<RwLock<Arc<String>>>,
+}
+
+impl PrometheusExporter {
+    pub fn spawn(address: IpAddr, port: u16) -> error::Result<Self> {
+        let socket_address = SocketAddr::new(address, port);
+        let server = tiny_http::Server::http(socket_address).map_err(|err| {
+            BottomError::GenericError(format!(
+                "Unable to start the Prometheus endpoint on {}: {}",
+                socket_address, err
+            ))
+        })?;
+
+        // Updates swap in a freshly rendered copy, so requests only hold the lock long enough to
+        // clone the pointer, and neither side ever waits on the other.
+        let metrics = Arc::new(RwLock::new(Arc::new(String::new())));
+        let server_metrics = metrics.clone();
+        thread::spawn(move || {
+            let content_type = tiny_http::Header::from_bytes(
+                &b"Content-Type"[..],
+                &b"text/plain; version=0.0.4"[..],
+            )
+            .unwrap();
+
+            for request in server.incoming_requests() {
+                let body = match (request.method(), request.url()) {
+                    (tiny_http::Method::Get, "/metrics") => match server_metrics.read() {
+                        Ok(metrics) => Some(metrics.clone()),
+                        Err(_) => continue,
+                    },
+                    _ => None,
+                };
+
+                let result = match body {
+                    Some(body) => request.respond(
+                        tiny_http::Response::from_string(body.as_str())
+                            .with_header(content_type.clone()),
+                    ),
+                    None => request.respond(
+                        tiny_http::Response::from_string("Not found").with_status_code(404),
+                    ),
+                };
+                if let Err(err) = result {
+                    debug!("Failed to respond to a Prometheus scrape: {}", err);
+                }
+            }
+        });
+
+        Ok(PrometheusExporter { metrics })
+    }
+
+    pub fn update(&self, data_collection: &DataCollection) {
+        let rendered = Arc::new(render_metrics(data_collection));
+        if let Ok(mut metrics) = self.metrics.write() {
+            *metrics = rendered;
+        }
+    }
+}
+
+fn render_metrics(data: &DataCollection) -> String {
+    let mut out = String::new();
+
+    let cores = data
+        .cpu_harvest
+        .iter()
+        .map(|cpu| {
+            let name = match cpu.cpu_count {
+                Some(cpu_count) => format!("{}{}", cpu.cpu_prefix, cpu_count),
+                None => cpu.cpu_prefix.clone(),
+            };
+            (vec![("core", name.to_lowercase())], cpu)
+        })
+        .collect::<Vec<_>>();
+    write_metric(
+        &mut out,
+        &CPU_USAGE,
+        cores
+            .iter()
+            .map(|(labels, cpu)| (labels.clone(), cpu.cpu_usage)),
+    );
+    // Platforms without a per-core breakdown leave these at zero, so they are only shown
+    // alongside one.
+    if data.cpu_breakdown_harvest.is_some() {
+        for (metric, get_value) in &[
+            (
+                &CPU_IOWAIT,
+                (|cpu| cpu.iowait_percent) as fn(&CpuData) -> f64,
+            ),
+            (&CPU_STEAL, |cpu| cpu.steal_percent),
+            (&CPU_IRQ, |cpu| cpu.irq_percent),
+            (&CPU_SOFTIRQ, |cpu| cpu.softirq_percent),
+        ] {
+            write_metric(
+                &mut out,
+                metric,
+                cores
+                    .iter()
+                    .map(|(labels, cpu)| (labels.clone(), get_value(cpu))),
+            );
+        }
+    }
+    write_metric(
+        &mut out,
+        &CPU_FREQUENCY,
+        cores
+            .iter()
+            .filter_map(|(labels, cpu)| Some((labels.clone(), cpu.frequency_mhz? as f64))),
+    );
+
+    if let Some(load_avg) = &data.load_avg_harvest {
+        write_metric(
+            &mut out,
+            &LOAD_AVERAGE,
+            vec![
+                (vec![("period", "1m".to_string())], load_avg.one),
+                (vec![("period", "5m".to_string())], load_avg.five),
+                (vec![("period", "15m".to_string())], load_avg.fifteen),
+            ],
+        );
+    }
+
+    let memory = &data.memory_harvest;
+    let swap = &data.swap_harvest;
+    if memory.mem_total_in_mb > 0 {
+        write_metric(
+            &mut out,
+            &MEMORY_USED,
+            Some((vec![], memory.mem_used_in_mb as f64 * BYTES_PER_MB)),
+        );
+        write_metric(
+            &mut out,
+            &MEMORY_TOTAL,
+            Some((vec![], memory.mem_total_in_mb as f64 * BYTES_PER_MB)),
+        );
+        write_metric(
+            &mut out,
+            &MEMORY_CACHE,
+            memory
+                .cache_in_mb
+                .map(|cache_in_mb| (vec![], cache_in_mb as f64 * BYTES_PER_MB)),
+        );
+        write_metric(
+            &mut out,
+            &SWAP_USED,
+            Some((vec![], swap.mem_used_in_mb as f64 * BYTES_PER_MB)),
+        );
+        write_metric(
+            &mut out,
+            &SWAP_TOTAL,
+            Some((vec![], swap.mem_total_in_mb as f64 * BYTES_PER_MB)),
+        );
+    }
+
+    let network = &data.network_harvest;
+    write_metric(
+        &mut out,
+        &NETWORK_RECEIVE_RATE,
+        Some((vec![], network.rx as f64)),
+    );
+    write_metric(
+        &mut out,
+        &NETWORK_TRANSMIT_RATE,
+        Some((vec![], network.tx as f64)),
+    );
+    write_metric(
+        &mut out,
+        &NETWORK_RECEIVED,
+        Some((vec![], network.total_rx as f64)),
+    );
+    write_metric(
+        &mut out,
+        &NETWORK_TRANSMITTED,
+        Some((vec![], network.total_tx as f64)),
+    );
+
+    let disks = data
+        .disk_harvest
+        .iter()
+        .map(|disk| {
+            (
+                vec![
+                    ("device", disk.name.clone()),
+                    ("mount", disk.mount_point.clone()),
+                ],
+                disk,
+            )
+        })
+        .collect::<Vec<_>>();
+    for (metric, get_value) in &[
+        (
+            &DISK_USED,
+            (|disk| disk.used_space as f64) as fn(&DiskHarvest) -> f64,
+        ),
+        (&DISK_FREE, |disk| disk.free_space as f64),
+        (&DISK_TOTAL, |disk| disk.total_space as f64),
+        (&DISK_READ_RATE, |disk| disk.read_bytes_per_sec),
+        (&DISK_WRITE_RATE, |disk| disk.write_bytes_per_sec),
+    ] {
+        write_metric(
+            &mut out,
+            metric,
+            disks
+                .iter()
+                .map(|(labels, disk)| (labels.clone(), get_value(disk))),
+        );
+    }
+
+    write_metric(
+        &mut out,
+        &TEMPERATURE,
+        data.temp_harvest.iter().map(|sensor| {
+            let name = match (&sensor.component_name, &sensor.component_label) {
+                (Some(name), Some(label)) => format!("{}: {}", name, label),
+                (None, Some(label)) => label.to_string(),
+                (Some(name), None) => name.to_string(),
+                (None, None) => String::default(),
+            };
+            (vec![("sensor", name)], f64::from(sensor.temperature))
+        }),
+    );
+    write_metric(
+        &mut out,
+        &FAN_SPEED,
+        data.fan_harvest
+            .iter()
+            .map(|fan| (vec![("fan", fan.name.clone())], f64::from(fan.rpm))),
+    );
+
+    write_metric(
+        &mut out,
+        &BATTERY_CHARGE,
+        data.battery_harvest
+            .iter()
+            .enumerate()
+            .map(|(itx, battery)| (vec![("battery", itx.to_string())], battery.charge_percent)),
+    );
+    write_metric(
+        &mut out,
+        &BATTERY_POWER,
+        data.battery_harvest
+            .iter()
+            .enumerate()
+            .map(|(itx, battery)| {
+                (
+                    vec![("battery", itx.to_string())],
+                    battery.power_consumption_rate_watts,
+                )
+            }),
+    );
+
+    if let Some(sockets) = &data.socket_harvest {
+        write_metric(
+            &mut out,
+            &SOCKETS,
+            vec![
+                ("tcp", sockets.tcp),
+                ("tcp_established", sockets.tcp_established),
+                ("tcp_time_wait", sockets.tcp_time_wait),
+                ("tcp_close_wait", sockets.tcp_close_wait),
+                ("udp", sockets.udp),
+                ("raw", sockets.raw),
+                ("unix", sockets.unix),
+            ]
+            .into_iter()
+            .map(|(kind, count)| (vec![("kind", kind.to_string())], count as f64)),
+        );
+    }
+
+    out
+}
+
+/// Writes a metric family with its samples.  Families without any samples are left out.
+fn write_metric(
+    out: &mut String, metric: &Metric,
+    samples: impl IntoIterator<Item = (Vec<(&'static str, String)>, f64)>,
+) {
+    let mut samples = samples.into_iter().peekable();
+    if samples.peek().is_none() {
+        return;
+    }
+
+    let _ = writeln!(out, "# HELP {} {}", metric.name, metric.help);
+    let _ = writeln!(
+        out,
+        "# TYPE {} {}",
+        metric.name,
+        match metric.metric_type {
+            MetricType::Gauge => "gauge",
+            MetricType::Counter => "counter",
+        }
+    );
+    for (labels, value) in samples {
+        out.push_str(metric.name);
+        if !labels.is_empty() {
+            let labels = labels
+                .iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+                .collect::<Vec<_>>();
+            let _ = write!(out, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(out, " {}", value);
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
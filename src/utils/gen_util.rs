@@ -1,5 +1,8 @@
 use std::cmp::Ordering;
 
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
 pub const KILO_LIMIT: u64 = 1000;
 pub const MEGA_LIMIT: u64 = 1_000_000;
 pub const GIGA_LIMIT: u64 = 1_000_000_000;
@@ -65,11 +68,23 @@ pub fn get_exact_byte_values(bytes: u64, spacing: bool) -> (f64, String) {
         ),
         b if b < MEBI_LIMIT => (bytes as f64 / 1024.0, "KiB".to_string()),
         b if b < GIBI_LIMIT => (bytes as f64 / 1_048_576.0, "MiB".to_string()),
-        b if b < TERA_LIMIT => (bytes as f64 / 1_073_741_824.0, "GiB".to_string()),
+        b if b < TEBI_LIMIT => (bytes as f64 / 1_073_741_824.0, "GiB".to_string()),
         _ => (bytes as f64 / 1_099_511_627_776.0, "TiB".to_string()),
     }
 }
 
+/// Formats a size for display, like "456MiB" or "1.9GiB".  Sizes are always shown in binary
+/// units (B, KiB, MiB, GiB, or TiB), picking the largest unit that keeps the value above 1, and
+/// values under 100 get a decimal place.
+pub fn humanize_bytes(bytes: u64) -> String {
+    let (value, unit) = get_exact_byte_values(bytes, false);
+    if bytes < KIBI_LIMIT || value >= 100.0 {
+        format!("{:.0}{}", value, unit)
+    } else {
+        format!("{:.1}{}", value, unit)
+    }
+}
+
 /// Returns a tuple containing the value and the unit.  In units of 1000.
 /// This only supports up to a terabyte.  Note the "byte" unit will have a space appended to match the others.
 pub fn get_simple_byte_values(bytes: u64, spacing: bool) -> (f64, String) {
@@ -114,3 +129,21 @@ pub fn get_ordering<T: std::cmp::PartialOrd>(
         None => Ordering::Equal,
     }
 }
+
+/// Encodes bytes as padded base64.
+pub fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = (u32::from(chunk[0]) << 16)
+            | (u32::from(*chunk.get(1).unwrap_or(&0)) << 8)
+            | u32::from(*chunk.get(2).unwrap_or(&0));
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[(group >> (18 - 6 * index) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -170,3 +170,158 @@ fn test_missing_default_widget_type() -> Result<(), Box<dyn std::error::Error>>
 
     Ok(())
 }
+
+#[test]
+fn test_invalid_process_column() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--process_columns")
+        .arg("pid,name,gpu")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "\"gpu\" is an invalid process column",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_net_filter() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--net_filter")
+        .arg("lo,docker[")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "\"docker[\" is an invalid network interface pattern",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_port() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--server")
+        .arg("--port")
+        .arg("70000")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "set your port to be between 1 and 65535.",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_bind_address() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--server")
+        .arg("--bind")
+        .arg("localhost:80")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "\"localhost:80\" is an invalid address to serve on",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_prometheus_with_server() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--server")
+        .arg("--prometheus")
+        .arg("9100")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    Ok(())
+}
+
+#[test]
+fn test_json_has_memory() -> Result<(), Box<dyn std::error::Error>> {
+    let output = Command::new(get_binary_location()).arg("--json").output()?;
+    assert!(output.status.success());
+
+    let snapshot: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert!(!snapshot["memory"].is_null());
+    assert!(!snapshot["swap"].is_null());
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_zfs() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--zfs")
+        .arg("maybe")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "\"maybe\" is an invalid ZFS setting",
+        ))
+        .stderr(predicate::str::contains("Update the --zfs argument."));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_zram() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--zram")
+        .arg("maybe")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "\"maybe\" is an invalid zram setting",
+        ))
+        .stderr(predicate::str::contains("Update the --zram argument."));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_disk_io_threshold() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--disk_io_threshold")
+        .arg("0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "set your disk I/O threshold to be at least 1 MB/s.",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_colors() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--colors")
+        .arg("red,notacolour")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "\"notacolour\" is an invalid named colour",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_prometheus_port() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("--prometheus")
+        .arg("70000")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "set your Prometheus port to be between 1 and 65535.",
+        ));
+
+    Ok(())
+}
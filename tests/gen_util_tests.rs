@@ -0,0 +1,21 @@
+//! Checks the general helpers against known values.
+
+use bottom::utils::gen_util::encode_base64;
+
+#[test]
+fn test_encode_base64() {
+    // The test vectors from RFC 4648, which cover every amount of padding.
+    assert_eq!(encode_base64(b""), "");
+    assert_eq!(encode_base64(b"f"), "Zg==");
+    assert_eq!(encode_base64(b"fo"), "Zm8=");
+    assert_eq!(encode_base64(b"foo"), "Zm9v");
+    assert_eq!(encode_base64(b"foob"), "Zm9vYg==");
+    assert_eq!(encode_base64(b"fooba"), "Zm9vYmE=");
+    assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+}
+
+#[test]
+fn test_encode_base64_high_bytes() {
+    assert_eq!(encode_base64(&[0xff, 0xfe, 0xfd]), "//79");
+    assert_eq!(encode_base64("é".as_bytes()), "w6k=");
+}
@@ -41,6 +41,65 @@ fn test_invalid_layout_widget_type() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_zero_layout_ratio() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/zero_layout_ratio.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("greater than 0"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_rates() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_rates.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at least 250 milliseconds"));
+    Ok(())
+}
+
+#[test]
+fn test_zero_network_threshold() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/zero_network_threshold.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at least 1 byte per second"));
+    Ok(())
+}
+
+#[test]
+fn test_inverted_temperature_thresholds() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/inverted_temperature_thresholds.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "to be at most critical_threshold_celsius",
+        ));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_disk_usage_threshold() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_disk_usage_threshold.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "set your disk usage thresholds to be between 0 and 100.",
+        ));
+    Ok(())
+}
+
 /// This test isn't really needed as this is technically covered by TOML spec.
 /// However, I feel like it's worth checking anyways - not like it takes long.
 #[test]
@@ -169,3 +228,109 @@ fn test_invalid_default_widget_count() -> Result<(), Box<dyn std::error::Error>>
         .stderr(predicate::str::contains("invalid number"));
     Ok(())
 }
+
+/// An unreadable config should be reported, not silently replaced with a fresh one.
+#[test]
+fn test_invalid_utf8() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_utf8.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unable to read"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_keybinding_action() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_keybinding_action.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid action name"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_keybinding_key() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_keybinding_key.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid key"));
+    Ok(())
+}
+
+#[test]
+fn test_conflicting_keybindings() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/conflicting_keybindings.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is bound to both"));
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_process_column() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/duplicate_process_column.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is listed more than once"));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_theme() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_theme.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is an invalid theme"));
+    Ok(())
+}
+
+#[test]
+fn test_zero_disk_io_threshold() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/zero_disk_io_threshold.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "set your disk I/O threshold to be at least 1 MB/s.",
+        ));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_process_group_regex() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_process_group_regex.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "process_groups[1].regex \"^(java\" is an invalid regex",
+        ));
+    Ok(())
+}
+
+#[test]
+fn test_lone_influx_host() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_binary_location())
+        .arg("-C")
+        .arg("./tests/invalid_configs/lone_influx_host.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "set both an InfluxDB host and database to write metrics.",
+        ));
+    Ok(())
+}
@@ -0,0 +1,136 @@
+//! Checks how key presses are handled, without drawing anything.
+
+use std::sync::mpsc;
+
+use bottom::{
+    app::{layout_manager::BottomWidgetType, App},
+    clap::build_app,
+    handle_key_event_or_break,
+    options::{self, get_widget_layout, Config},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn create_app() -> App {
+    create_app_with_config(Config::default())
+}
+
+fn create_app_with_config(config: Config) -> App {
+    let matches = build_app().get_matches_from(vec!["btm"]);
+    let (widget_layout, default_widget_id, default_widget_type_option) =
+        get_widget_layout(&matches, &config).unwrap();
+
+    options::build_app(
+        &matches,
+        &config,
+        &widget_layout,
+        default_widget_id,
+        &default_widget_type_option,
+        None,
+    )
+    .unwrap()
+}
+
+fn press_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> bool {
+    let (sender, _receiver) = mpsc::channel();
+    handle_key_event_or_break(KeyEvent::new(code, modifiers), app, &sender)
+}
+
+fn press(app: &mut App, c: char) -> bool {
+    press_key(app, KeyCode::Char(c), KeyModifiers::NONE)
+}
+
+#[test]
+fn test_q_quits() {
+    let mut app = create_app();
+    assert!(press(&mut app, 'q'));
+}
+
+#[test]
+fn test_rebinding_quit_replaces_default_keys() {
+    let config = Config {
+        keybindings: Some(
+            vec![("quit".to_string(), "ctrl-q".to_string())]
+                .into_iter()
+                .collect(),
+        ),
+        ..Config::default()
+    };
+    let mut app = create_app_with_config(config);
+
+    assert!(!press(&mut app, 'q'));
+    assert!(!press_key(
+        &mut app,
+        KeyCode::Char('c'),
+        KeyModifiers::CONTROL
+    ));
+    assert!(press_key(
+        &mut app,
+        KeyCode::Char('q'),
+        KeyModifiers::CONTROL
+    ));
+}
+
+#[test]
+fn test_q_types_into_dialog_filter() {
+    let mut app = create_app();
+    app.process_info_dialog_state
+        .open("Environment".to_string(), vec!["QUIET=1".to_string()]);
+
+    assert!(!press(&mut app, 'q'));
+    assert_eq!(
+        app.process_info_dialog_state
+            .filter
+            .search_state
+            .current_search_query,
+        "q"
+    );
+}
+
+#[test]
+fn test_dialog_filter_uses_search_options() {
+    let mut app = create_app();
+    app.process_info_dialog_state.open(
+        "Environment".to_string(),
+        vec!["PATH=/bin".to_string(), "MANPATH=/usr/man".to_string()],
+    );
+
+    press_key(&mut app, KeyCode::Char('r'), KeyModifiers::ALT);
+    for c in "^path".chars() {
+        press(&mut app, c);
+    }
+    assert_eq!(
+        app.process_info_dialog_state.get_filtered_entries(),
+        vec!["PATH=/bin"]
+    );
+
+    // Matching case hides everything, as the filter is lowercase.
+    press_key(&mut app, KeyCode::Char('c'), KeyModifiers::ALT);
+    assert!(app
+        .process_info_dialog_state
+        .get_filtered_entries()
+        .is_empty());
+}
+
+#[test]
+fn test_username_filter_input() {
+    let mut app = create_app();
+    app.current_widget = app
+        .widget_map
+        .values()
+        .find(|widget| widget.widget_type == BottomWidgetType::Proc)
+        .unwrap()
+        .clone();
+
+    press_key(&mut app, KeyCode::Char('u'), KeyModifiers::CONTROL);
+    assert!(app.user_filter_state.is_showing_input);
+
+    // Whatever the selected process' user was, replace it.
+    press_key(&mut app, KeyCode::Char('u'), KeyModifiers::CONTROL);
+    for c in "quinn".chars() {
+        assert!(!press(&mut app, c));
+    }
+    press_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+    assert!(!app.user_filter_state.is_showing_input);
+    assert_eq!(app.username_filter.as_deref(), Some("quinn"));
+}
@@ -0,0 +1,67 @@
+//! Checks that memory usage is read correctly from `/proc/meminfo` snapshots.
+
+use bottom::app::data_harvester::mem::{parse_meminfo, MemHarvest};
+
+const MEMINFO: &str = "MemTotal:       16318164 kB
+MemFree:         1106860 kB
+MemAvailable:    9847120 kB
+Buffers:          412504 kB
+Cached:          8107620 kB
+SwapCached:        10244 kB
+Active:          6964852 kB
+Inactive:        6850576 kB
+SReclaimable:     506188 kB
+SwapTotal:       2097148 kB
+SwapFree:        1838840 kB
+AnonHugePages:    266240 kB
+HugePages_Total:       0
+HugePages_Free:        0
+Hugepagesize:       2048 kB
+";
+
+/// From a kernel older than 3.14, which doesn't report `MemAvailable`.
+const OLD_MEMINFO: &str = "MemTotal:        8167848 kB
+MemFree:         2096128 kB
+Buffers:          204800 kB
+Cached:          3072000 kB
+SReclaimable:     102400 kB
+";
+
+#[test]
+fn test_used_memory_from_mem_available() {
+    let mut mem_harvest = MemHarvest::default();
+    parse_meminfo(MEMINFO, &mut mem_harvest);
+
+    assert_eq!(mem_harvest.mem_total_in_mb, 15935);
+    // 16318164 kB - 9847120 kB, rather than the total minus MemFree.
+    assert_eq!(mem_harvest.mem_used_in_mb, 6319);
+    assert_eq!(mem_harvest.mem_free_in_mb, Some(1080));
+    assert_eq!(mem_harvest.mem_available_in_mb, Some(9616));
+    assert_eq!(mem_harvest.cache_in_mb, Some(8814));
+    assert_eq!(mem_harvest.anon_huge_pages_kb, Some(266_240));
+}
+
+#[test]
+fn test_used_memory_without_mem_available() {
+    let mut mem_harvest = MemHarvest::default();
+    parse_meminfo(OLD_MEMINFO, &mut mem_harvest);
+
+    // Free memory and cache are both counted as unused.
+    assert_eq!(mem_harvest.mem_available_in_mb, None);
+    // 8167848 kB - 2096128 kB - (204800 + 3072000 + 102400) kB
+    assert_eq!(mem_harvest.mem_used_in_mb, 2629);
+}
+
+#[test]
+fn test_unreadable_meminfo_keeps_values() {
+    let mut mem_harvest = MemHarvest {
+        mem_total_in_mb: 1024,
+        mem_used_in_mb: 512,
+        ..MemHarvest::default()
+    };
+    parse_meminfo("", &mut mem_harvest);
+
+    assert_eq!(mem_harvest.mem_total_in_mb, 1024);
+    assert_eq!(mem_harvest.mem_used_in_mb, 512);
+    assert_eq!(mem_harvest.cache_in_mb, None);
+}
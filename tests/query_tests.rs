@@ -0,0 +1,137 @@
+//! Checks that process search queries match the right processes.
+
+use bottom::app::query::ProcessQuery;
+use bottom::app::states::{ProcWidgetState, ProcessGroupingKey};
+use bottom::data_conversion::{group_process_data, ConvertedProcessData};
+use unicode_segmentation::GraphemeCursor;
+
+fn get_process(pid: u32, name: &str, user: &str) -> ConvertedProcessData {
+    ConvertedProcessData {
+        pid: pid as _,
+        name: name.to_string(),
+        command: name.to_string(),
+        group_pids: vec![pid as _],
+        group_states: vec!["Sleeping".to_string()],
+        group_users: vec![user.to_string()],
+        process_state: "Sleeping".to_string(),
+        process_char: 'S',
+        user: user.to_string(),
+        ..ConvertedProcessData::default()
+    }
+}
+
+fn get_group_matches(search_query: &str) -> Vec<String> {
+    let mut proc_widget_state = ProcWidgetState::init(false, false, false, true, false, None);
+    proc_widget_state
+        .process_search_state
+        .search_state
+        .current_search_query = search_query.to_string();
+    let query = proc_widget_state.parse_query().unwrap();
+
+    let processes = vec![
+        get_process(100, "firefox", "alice"),
+        get_process(200, "firefox", "alice"),
+        get_process(300, "firefox", "bob"),
+        get_process(400, "bash", "alice"),
+    ];
+    let mut matches = group_process_data(&processes, false, ProcessGroupingKey::Name, &[])
+        .into_iter()
+        .filter(|process| query.check(process, false))
+        .map(|process| process.name)
+        .collect::<Vec<_>>();
+    matches.sort();
+    matches
+}
+
+#[test]
+fn test_group_matches_any_pid() {
+    assert_eq!(get_group_matches("pid 300"), vec!["firefox"]);
+    assert_eq!(get_group_matches("pid 150-250"), vec!["firefox"]);
+    assert_eq!(get_group_matches("pid > 350"), vec!["bash"]);
+}
+
+#[test]
+fn test_group_matches_any_user() {
+    // Most of the firefox group is run by alice, but bob runs one of them too.
+    assert_eq!(get_group_matches("user bob"), vec!["firefox"]);
+}
+
+#[test]
+fn test_group_matches_any_state() {
+    assert_eq!(get_group_matches("state sleep"), vec!["bash", "firefox"]);
+}
+
+fn parse_query(search_query: &str) -> bool {
+    let mut proc_widget_state = ProcWidgetState::init(false, false, false, true, false, None);
+    proc_widget_state
+        .process_search_state
+        .search_state
+        .current_search_query = search_query.to_string();
+    proc_widget_state.parse_query().is_ok()
+}
+
+#[test]
+fn test_pid_comparisons() {
+    assert_eq!(get_group_matches("pid >= 400"), vec!["bash"]);
+    assert_eq!(get_group_matches("pid < 150"), vec!["firefox"]);
+}
+
+#[test]
+fn test_colon_joined_comparisons() {
+    assert_eq!(get_group_matches("pid:>350"), vec!["bash"]);
+    assert_eq!(get_group_matches("pid:>=400"), vec!["bash"]);
+    assert_eq!(get_group_matches("bash && pid:>100"), vec!["bash"]);
+}
+
+#[test]
+fn test_colon_joined_comparisons_need_a_numeric_prefix() {
+    assert!(!parse_query("user:>x"));
+    assert!(!parse_query("state:>x"));
+}
+
+fn move_search_cursor(proc_widget_state: &mut ProcWidgetState, position: usize) {
+    let search_state = &mut proc_widget_state.process_search_state.search_state;
+    search_state.grapheme_cursor =
+        GraphemeCursor::new(position, search_state.current_search_query.len(), true);
+    search_state.char_cursor_position = position;
+}
+
+#[test]
+fn test_completion_cycles_through_names() {
+    let names = ["bash", "bat"];
+    let mut proc_widget_state = ProcWidgetState::init(false, false, false, false, false, None);
+    proc_widget_state.set_search_query("x ba".to_string());
+
+    proc_widget_state.complete_search_token(names.iter().copied());
+    assert_eq!(proc_widget_state.get_current_search_query(), "x bash");
+    proc_widget_state.complete_search_token(names.iter().copied());
+    assert_eq!(proc_widget_state.get_current_search_query(), "x bat");
+}
+
+#[test]
+fn test_completion_restarts_after_moving_the_cursor() {
+    let names = ["bash", "bat"];
+    let mut proc_widget_state = ProcWidgetState::init(false, false, false, false, false, None);
+    proc_widget_state.set_search_query("x ba".to_string());
+    proc_widget_state.complete_search_token(names.iter().copied());
+
+    // There's nothing before the cursor to complete anymore.
+    move_search_cursor(&mut proc_widget_state, 0);
+    proc_widget_state.complete_search_token(names.iter().copied());
+    assert_eq!(proc_widget_state.get_current_search_query(), "x bash");
+}
+
+#[test]
+fn test_completion_replaces_the_whole_token() {
+    let names = ["firefox"];
+    let mut proc_widget_state = ProcWidgetState::init(false, false, false, false, false, None);
+    proc_widget_state.set_search_query("firx cpu > 5".to_string());
+    move_search_cursor(&mut proc_widget_state, 3);
+
+    proc_widget_state.complete_search_token(names.iter().copied());
+    assert_eq!(
+        proc_widget_state.get_current_search_query(),
+        "firefox cpu > 5"
+    );
+    assert_eq!(proc_widget_state.get_cursor_position(), 7);
+}